@@ -0,0 +1,2 @@
+-- 为 search_engines 表添加快捷关键字列（例如 `gh` 或 `!gh`）
+ALTER TABLE search_engines ADD COLUMN keyword TEXT;
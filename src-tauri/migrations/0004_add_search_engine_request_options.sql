@@ -0,0 +1,4 @@
+-- 为 search_engines 表添加请求方式、POST 表单模板和字符编码列，为空时分别为 GET、无表单和 UTF-8
+ALTER TABLE search_engines ADD COLUMN method TEXT;
+ALTER TABLE search_engines ADD COLUMN post_body TEXT;
+ALTER TABLE search_engines ADD COLUMN encoding TEXT;
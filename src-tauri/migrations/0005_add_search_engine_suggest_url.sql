@@ -0,0 +1,2 @@
+-- 为 search_engines 表添加搜索建议地址模板列（OpenSearch Suggestions JSON 接口）
+ALTER TABLE search_engines ADD COLUMN suggest_url_template TEXT;
@@ -0,0 +1,3 @@
+-- 为 websites 表添加从浏览器书签导入的快捷关键字和标签（逗号分隔）列
+ALTER TABLE websites ADD COLUMN keyword TEXT;
+ALTER TABLE websites ADD COLUMN tags TEXT;
@@ -1,3 +1,6 @@
+use crate::invokes::bookmark_parser::BookmarkImportState;
+use crate::invokes::metadata_batch::MetadataBatchState;
+use crate::invokes::search_suggest::SearchSuggestState;
 use crate::modules::{db, logger, tray};
 use crate::utils::{HttpClientConfig, build_http_client};
 use log::{error, info};
@@ -78,6 +81,9 @@ pub fn setup(app: &mut tauri::App) {
 pub fn manage(app: &mut tauri::App) {
     let client = build_http_client(HttpClientConfig::builder()).unwrap();
     app.manage(client);
+    app.manage(BookmarkImportState::default());
+    app.manage(SearchSuggestState::default());
+    app.manage(MetadataBatchState::default());
 
     info!("【初始化】`state` 设置完成")
 }
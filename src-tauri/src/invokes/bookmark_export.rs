@@ -0,0 +1,259 @@
+//! 该模块负责把启动台中的分组和网站导出为 Netscape 书签格式的 HTML 文件。
+//!
+//! 导出的文件可以被任意浏览器导入，也可以再次通过 `bookmark_parser` 导入到 Vust Desk 中。
+
+use crate::modules::db::{TableName, sqlite_pool};
+use crate::utils::{app_icons_dir_path, escape_html};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use log::{info, warn};
+use sqlx::FromRow;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// 所有分组都放在这个工具栏文件夹下，浏览器导入后会出现在书签栏中。
+const TOOLBAR_FOLDER_NAME: &str = "Vust Desk";
+
+/// 导出时读取的分组记录。
+#[derive(FromRow, Debug)]
+struct GroupRow {
+    uuid: String,
+    name: String,
+    add_date: Option<i64>,
+    last_modified: Option<i64>,
+}
+
+/// 导出时读取的网站记录。
+#[derive(FromRow, Debug)]
+struct WebsiteRow {
+    group_uuid: String,
+    title: String,
+    url: String,
+    local_icon_path: Option<String>,
+    description: Option<String>,
+    keyword: Option<String>,
+    tags: Option<String>,
+    add_date: Option<i64>,
+    last_modified: Option<i64>,
+}
+
+/// [Tauri Command] 将指定用户的启动台数据导出为 Netscape 书签 HTML 文件。
+///
+/// 每个分组对应一个 `<H3>` 文件夹，每个网站对应一个 `<A>` 书签，
+/// 其中 `ADD_DATE` 取自 `created_at`，`ICON` 为由本地缓存图标生成的 data URI，
+/// 导入时保存的关键字和标签分别写入 `SHORTCUTURL` 和 `TAGS`。
+///
+/// # Arguments
+/// * `user_uuid` - 要导出数据的用户。
+/// * `path` - 导出文件的保存路径。
+///
+/// # Returns
+/// * `Ok(usize)` - 导出的网站数量。
+/// * `Err(String)` - 读取数据库或写入文件失败时返回错误描述。
+#[tauri::command]
+pub async fn export_bookmarks(
+    app: AppHandle,
+    user_uuid: String,
+    path: PathBuf,
+) -> Result<usize, String> {
+    info!("正在导出书签到: {path:?}");
+    let pool = sqlite_pool(&app).await?;
+
+    let groups: Vec<GroupRow> = sqlx::query_as(&format!(
+        "SELECT uuid, name,
+                CAST(strftime('%s', created_at) AS INTEGER) AS add_date,
+                CAST(strftime('%s', updated_at) AS INTEGER) AS last_modified
+         FROM {} WHERE user_uuid = ? AND is_deleted = 0
+         ORDER BY sort_order, id",
+        TableName::WebsiteGroups
+    ))
+    .bind(&user_uuid)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| format!("读取分组失败: {e}"))?;
+
+    let websites: Vec<WebsiteRow> = sqlx::query_as(&format!(
+        "SELECT group_uuid, title, url, local_icon_path, description, keyword, tags,
+                CAST(strftime('%s', created_at) AS INTEGER) AS add_date,
+                CAST(strftime('%s', updated_at) AS INTEGER) AS last_modified
+         FROM {} WHERE user_uuid = ? AND is_deleted = 0
+         ORDER BY sort_order, id",
+        TableName::WebsiteItems
+    ))
+    .bind(&user_uuid)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| format!("读取网站失败: {e}"))?;
+
+    let icons_dir = app_icons_dir_path(&app);
+    let html = render_bookmarks(&groups, &websites, &icons_dir);
+    fs::write(&path, html).map_err(|e| format!("写入书签文件失败: {e}"))?;
+
+    info!("书签导出完成，共 {} 个网站", websites.len());
+    Ok(websites.len())
+}
+
+/// 生成完整的 Netscape 书签 HTML 文本。
+fn render_bookmarks(groups: &[GroupRow], websites: &[WebsiteRow], icons_dir: &Path) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE NETSCAPE-Bookmark-file-1>\n\
+         <!-- This is an automatically generated file.\n     \
+         It will be read and overwritten.\n     \
+         DO NOT EDIT! -->\n\
+         <META HTTP-EQUIV=\"Content-Type\" CONTENT=\"text/html; charset=UTF-8\">\n\
+         <TITLE>Bookmarks</TITLE>\n\
+         <H1>Bookmarks</H1>\n\
+         <DL><p>\n",
+    );
+
+    let _ = writeln!(
+        html,
+        "    <DT><H3 PERSONAL_TOOLBAR_FOLDER=\"true\">{}</H3>",
+        escape_html(TOOLBAR_FOLDER_NAME)
+    );
+    html.push_str("    <DL><p>\n");
+
+    for group in groups {
+        let _ = writeln!(
+            html,
+            "        <DT><H3{}{}>{}</H3>",
+            date_attr("ADD_DATE", group.add_date),
+            date_attr("LAST_MODIFIED", group.last_modified),
+            escape_html(&group.name)
+        );
+        html.push_str("        <DL><p>\n");
+
+        for website in websites.iter().filter(|w| w.group_uuid == group.uuid) {
+            let icon = website
+                .local_icon_path
+                .as_deref()
+                .and_then(|file_name| icon_data_uri(icons_dir, file_name))
+                .map(|uri| format!(" ICON=\"{uri}\""))
+                .unwrap_or_default();
+            let _ = writeln!(
+                html,
+                "            <DT><A HREF=\"{}\"{}{}{}{}{}>{}</A>",
+                escape_html(&website.url),
+                date_attr("ADD_DATE", website.add_date),
+                date_attr("LAST_MODIFIED", website.last_modified),
+                text_attr("SHORTCUTURL", website.keyword.as_deref()),
+                text_attr("TAGS", website.tags.as_deref()),
+                icon,
+                escape_html(&website.title)
+            );
+            if let Some(description) = website.description.as_deref()
+                && !description.trim().is_empty()
+            {
+                let _ = writeln!(html, "            <DD>{}", escape_html(description.trim()));
+            }
+        }
+
+        html.push_str("        </DL><p>\n");
+    }
+
+    html.push_str("    </DL><p>\n</DL><p>\n");
+    html
+}
+
+/// 生成形如 ` TAGS="dev,rust"` 的文本属性，值为空时返回空字符串。
+fn text_attr(name: &str, value: Option<&str>) -> String {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| format!(" {name}=\"{}\"", escape_html(value)))
+        .unwrap_or_default()
+}
+
+/// 生成形如 ` ADD_DATE="1700000000"` 的时间属性，时间缺失时返回空字符串。
+fn date_attr(name: &str, value: Option<i64>) -> String {
+    value
+        .map(|secs| format!(" {name}=\"{secs}\""))
+        .unwrap_or_default()
+}
+
+/// 读取图标目录中的缓存图标并编码为 data URI。
+fn icon_data_uri(icons_dir: &Path, file_name: &str) -> Option<String> {
+    // 文件名来自同步或导入的数据，只接受图标目录中的文件，不允许绝对路径或 `..`
+    let path = Path::new(file_name);
+    if path.file_name() != Some(file_name.as_ref()) {
+        warn!("图标文件名无效，导出时跳过该图标 {file_name}");
+        return None;
+    }
+    let extension = path
+        .extension()
+        .and_then(std::ffi::OsStr::to_str)?
+        .to_lowercase();
+    let mime = match extension.as_str() {
+        "png" => "image/png",
+        "ico" => "image/x-icon",
+        "svg" => "image/svg+xml",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        _ => return None,
+    };
+
+    match fs::read(icons_dir.join(file_name)) {
+        Ok(bytes) => Some(format!("data:{mime};base64,{}", STANDARD.encode(bytes))),
+        Err(e) => {
+            warn!("读取图标失败，导出时跳过该图标 {file_name}: {e}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::invokes::bookmark_parser::test_support::{TempFile, names, parse_netscape, titles};
+
+    #[test]
+    fn export_then_import_round_trip() {
+        let groups = [GroupRow {
+            uuid: "g1".to_string(),
+            name: "Dev & Ops".to_string(),
+            add_date: Some(1_700_000_000),
+            last_modified: Some(1_700_000_100),
+        }];
+        let websites = [
+            WebsiteRow {
+                group_uuid: "g1".to_string(),
+                title: "Vec<T> \"docs\"".to_string(),
+                url: "https://doc.rust-lang.org/std/vec/?a=1&b=2".to_string(),
+                local_icon_path: None,
+                description: Some("  Growable <array> & more  ".to_string()),
+                keyword: Some("vec".to_string()),
+                tags: Some("rust,docs".to_string()),
+                add_date: Some(1_700_000_200),
+                last_modified: Some(1_700_000_300),
+            },
+            WebsiteRow {
+                group_uuid: "g1".to_string(),
+                title: "Grafana".to_string(),
+                url: "https://grafana.example.com/".to_string(),
+                local_icon_path: None,
+                description: None,
+                keyword: None,
+                tags: None,
+                add_date: None,
+                last_modified: None,
+            },
+        ];
+
+        let html = render_bookmarks(&groups, &websites, &std::env::temp_dir());
+        let file = TempFile::new("bookmarks.html", html);
+        let imported = parse_netscape(&file.path).unwrap();
+
+        assert_eq!(names(&imported), ["Dev & Ops"]);
+        assert_eq!(titles(&imported[0]), ["Vec<T> \"docs\"", "Grafana"]);
+        let vec = &imported[0].items[0];
+        assert_eq!(vec.url, "https://doc.rust-lang.org/std/vec/?a=1&b=2");
+        assert_eq!(vec.description.as_deref(), Some("Growable <array> & more"));
+        assert_eq!(vec.keyword.as_deref(), Some("vec"));
+        assert_eq!(vec.tags, ["rust", "docs"]);
+        assert_eq!(vec.add_date, Some(1_700_000_200));
+        assert_eq!(vec.last_modified, Some(1_700_000_300));
+        assert_eq!(imported[0].items[1].description, None);
+    }
+}
@@ -0,0 +1,601 @@
+//! 该模块负责书签导入前的预览和导入计划的执行。
+//!
+//! 预览会把解析出的书签与用户已有的网站逐一比对，标记出新增项、完全重复项
+//! 以及规范化后相同的近似重复项；前端据此选择要执行的操作，
+//! 再通过 `apply_bookmark_import` 在同一个事务中写入数据库。
+
+use crate::invokes::bookmark_parser::{BookmarkGroup, BookmarkItem};
+use crate::invokes::metadata::save_data_uri_icon;
+use crate::modules::db::{TableName, sqlite_pool};
+use crate::utils::app_icons_dir_path;
+use log::info;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tauri::AppHandle;
+use url::Url;
+
+/// 导入的网站在没有图标时使用的默认图标，与前端新增网站时的默认值一致。
+const DEFAULT_ICON: &str = "ion:globe-outline";
+
+/// 用户已有的网站，用于与导入的书签比对。
+#[derive(Serialize, FromRow, Clone, Debug)]
+pub struct ExistingWebsite {
+    uuid: String,
+    group_uuid: String,
+    title: String,
+    url: String,
+}
+
+/// 用户已有的分组。
+#[derive(FromRow, Debug)]
+struct ExistingGroup {
+    uuid: String,
+    name: String,
+}
+
+/// 单个书签与已有数据比对后的状态。
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateStatus {
+    /// 启动台中不存在相同的网站。
+    New,
+    /// 启动台中已有 URL 完全相同的网站。
+    ExactDuplicate,
+    /// 启动台中已有主机、路径和查询参数相同的网站（忽略协议、`www.` 前缀、末尾斜杠和跟踪参数等差异）。
+    NearDuplicate,
+    /// 与本次导入中更早出现的书签重复。
+    DuplicateInImport,
+}
+
+/// 单个书签的预览结果。
+#[derive(Serialize, Debug)]
+pub struct ItemPreview {
+    item: BookmarkItem,
+    status: DuplicateStatus,
+    /// 与之重复的已有网站。
+    existing: Option<ExistingWebsite>,
+}
+
+/// 单个分组的预览结果。
+#[derive(Serialize, Debug)]
+pub struct GroupPreview {
+    name: String,
+    /// 同名的已有分组，不存在时导入会新建分组。
+    existing_group_uuid: Option<String>,
+    items: Vec<ItemPreview>,
+}
+
+/// 整个导入的预览结果。
+#[derive(Serialize, Debug, Default)]
+pub struct ImportPreview {
+    groups: Vec<GroupPreview>,
+    new_count: usize,
+    exact_duplicate_count: usize,
+    near_duplicate_count: usize,
+    duplicate_in_import_count: usize,
+    existing_group_count: usize,
+}
+
+/// 对单个书签执行的操作。
+#[derive(Deserialize, Debug)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum ItemAction {
+    /// 作为新网站添加。
+    Add,
+    /// 用书签的数据覆盖指定的已有网站。
+    Update { uuid: String },
+    /// 跳过该书签。
+    Skip,
+}
+
+/// 导入计划中的一个书签及其操作。
+#[derive(Deserialize, Debug)]
+pub struct PlannedItem {
+    item: BookmarkItem,
+    #[serde(flatten)]
+    action: ItemAction,
+}
+
+/// 导入计划中的一个分组。
+#[derive(Deserialize, Debug)]
+pub struct PlannedGroup {
+    name: String,
+    /// 目标分组，为空时按 `name` 新建分组。
+    group_uuid: Option<String>,
+    items: Vec<PlannedItem>,
+}
+
+/// 执行导入计划后的统计。
+#[derive(Serialize, Debug, Default)]
+pub struct ImportResult {
+    created_groups: usize,
+    added_items: usize,
+    updated_items: usize,
+    skipped_items: usize,
+    /// 新添加的网站，可以交给 `fetch_websites_metadata` 补全标题和图标。
+    added_uuids: Vec<String>,
+}
+
+/// [Tauri Command] 生成书签导入预览。
+///
+/// 把解析出的书签与用户已有的网站比对，返回每个书签的重复状态以及已存在的同名分组。
+/// 嵌套的子文件夹会作为独立的分组参与比对。
+///
+/// # Arguments
+/// * `user_uuid` - 导入的目标用户。
+/// * `groups` - `bookmark_parser` 等命令解析出的书签分组。
+#[tauri::command]
+pub async fn preview_bookmark_import(
+    app: AppHandle,
+    user_uuid: String,
+    groups: Vec<BookmarkGroup>,
+) -> Result<ImportPreview, String> {
+    let pool = sqlite_pool(&app).await?;
+
+    let websites: Vec<ExistingWebsite> = sqlx::query_as(&format!(
+        "SELECT uuid, group_uuid, title, url FROM {} WHERE user_uuid = ? AND is_deleted = 0",
+        TableName::WebsiteItems
+    ))
+    .bind(&user_uuid)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| format!("读取网站失败: {e}"))?;
+
+    let existing_groups: Vec<ExistingGroup> = sqlx::query_as(&format!(
+        "SELECT uuid, name FROM {} WHERE user_uuid = ? AND is_deleted = 0",
+        TableName::WebsiteGroups
+    ))
+    .bind(&user_uuid)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| format!("读取分组失败: {e}"))?;
+
+    Ok(build_preview(groups, &websites, &existing_groups))
+}
+
+/// [Tauri Command] 在一个事务中执行导入计划。
+///
+/// 没有 `group_uuid` 的分组会被新建，同名的分组只新建一次；
+/// 指定的分组必须属于该用户且未被删除。`add` 的书签追加到分组末尾，
+/// `update` 的书签覆盖已有网站的标题、URL 和图标（用户上传的图标不会被覆盖），`skip` 的书签被忽略。
+/// 书签内嵌的图标只在 `add` 和 `update` 时才保存到图标目录，写入时 `icon_source` 标记为 `auto_fetched`。
+/// 书签的关键字和标签（如 Firefox 的快捷关键字和标签）保存在 `keyword` 和 `tags` 列中。
+/// 任何一步失败都会回滚整个导入。
+///
+/// # Arguments
+/// * `user_uuid` - 导入的目标用户。
+/// * `plan` - 前端根据预览结果生成的导入计划。
+#[tauri::command]
+pub async fn apply_bookmark_import(
+    app: AppHandle,
+    user_uuid: String,
+    plan: Vec<PlannedGroup>,
+) -> Result<ImportResult, String> {
+    let pool = sqlite_pool(&app).await?;
+    let icons_dir = app_icons_dir_path(&app);
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let mut result = ImportResult::default();
+
+    let mut next_group_order: i64 = sqlx::query_scalar(&format!(
+        "SELECT COALESCE(MAX(sort_order), -1) + 1 FROM {} WHERE user_uuid = ? AND is_deleted = 0",
+        TableName::WebsiteGroups
+    ))
+    .bind(&user_uuid)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| format!("读取分组排序失败: {e}"))?;
+
+    // 本次导入中新建的分组，同名的文件夹（如 PerFolder 模式下的多个 `Docs`）合并到同一个分组中
+    let mut created_groups: HashMap<String, String> = HashMap::new();
+
+    for group in plan {
+        if group
+            .items
+            .iter()
+            .all(|planned| matches!(planned.action, ItemAction::Skip))
+        {
+            result.skipped_items += group.items.len();
+            continue;
+        }
+
+        let group_uuid = match group.group_uuid {
+            Some(uuid) => {
+                // 分组来自前端，可能已被删除或不属于当前用户
+                let exists: bool = sqlx::query_scalar(&format!(
+                    "SELECT EXISTS(SELECT 1 FROM {} WHERE uuid = ? AND user_uuid = ? AND is_deleted = 0)",
+                    TableName::WebsiteGroups
+                ))
+                .bind(&uuid)
+                .bind(&user_uuid)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| format!("读取分组 {} 失败: {e}", group.name))?;
+                if !exists {
+                    return Err(format!("分组 {} 不存在或已被删除", group.name));
+                }
+                uuid
+            }
+            None if created_groups.contains_key(&group.name) => created_groups[&group.name].clone(),
+            None => {
+                let uuid = uuid::Uuid::new_v4().to_string();
+                sqlx::query(&format!(
+                    "INSERT INTO {} (uuid, user_uuid, name, sort_order) VALUES (?, ?, ?, ?)",
+                    TableName::WebsiteGroups
+                ))
+                .bind(&uuid)
+                .bind(&user_uuid)
+                .bind(&group.name)
+                .bind(next_group_order)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("创建分组 {} 失败: {e}", group.name))?;
+                next_group_order += 1;
+                result.created_groups += 1;
+                created_groups.insert(group.name.clone(), uuid.clone());
+                uuid
+            }
+        };
+
+        let mut next_item_order: i64 = sqlx::query_scalar(&format!(
+            "SELECT COALESCE(MAX(sort_order), -1) + 1 FROM {} WHERE group_uuid = ? AND is_deleted = 0",
+            TableName::WebsiteItems
+        ))
+        .bind(&group_uuid)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("读取网站排序失败: {e}"))?;
+
+        for planned in group.items {
+            let item = planned.item;
+            match planned.action {
+                ItemAction::Add => {
+                    let uuid = uuid::Uuid::new_v4().to_string();
+                    let local_icon_path = local_icon_path(&item, &icons_dir);
+                    sqlx::query(&format!(
+                        "INSERT INTO {} (uuid, user_uuid, group_uuid, title, url, url_lan, description, default_icon, local_icon_path, icon_source, keyword, tags, sort_order, created_at)
+                         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?,
+                                 COALESCE(strftime('%Y-%m-%dT%H:%M:%fZ', ?, 'unixepoch'),
+                                          strftime('%Y-%m-%dT%H:%M:%fZ', 'now')))",
+                        TableName::WebsiteItems
+                    ))
+                    .bind(&uuid)
+                    .bind(&user_uuid)
+                    .bind(&group_uuid)
+                    .bind(&item.title)
+                    .bind(&item.url)
+                    .bind(&item.url_lan)
+                    .bind(&item.description)
+                    .bind(item.default_icon.as_deref().unwrap_or(DEFAULT_ICON))
+                    .bind(&local_icon_path)
+                    .bind(local_icon_path.as_ref().map(|_| "auto_fetched"))
+                    .bind(&item.keyword)
+                    .bind(joined_tags(&item.tags))
+                    .bind(next_item_order)
+                    .bind(item.add_date)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| format!("添加网站 {} 失败: {e}", item.url))?;
+                    next_item_order += 1;
+                    result.added_items += 1;
+                    result.added_uuids.push(uuid);
+                }
+                ItemAction::Update { uuid } => {
+                    let local_icon_path = local_icon_path(&item, &icons_dir);
+                    let updated = sqlx::query(&format!(
+                        "UPDATE {} SET title = ?, url = ?, url_lan = COALESCE(?, url_lan),
+                                description = COALESCE(?, description),
+                                default_icon = COALESCE(?, default_icon),
+                                local_icon_path = CASE WHEN COALESCE(icon_source, '') = 'user_uploaded'
+                                    THEN local_icon_path ELSE COALESCE(?, local_icon_path) END,
+                                icon_source = CASE WHEN COALESCE(icon_source, '') = 'user_uploaded' OR ? IS NULL
+                                    THEN icon_source ELSE 'auto_fetched' END,
+                                keyword = COALESCE(?, keyword),
+                                tags = COALESCE(?, tags),
+                                updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                         WHERE uuid = ? AND user_uuid = ? AND is_deleted = 0",
+                        TableName::WebsiteItems
+                    ))
+                    .bind(&item.title)
+                    .bind(&item.url)
+                    .bind(&item.url_lan)
+                    .bind(&item.description)
+                    .bind(&item.default_icon)
+                    .bind(&local_icon_path)
+                    .bind(&local_icon_path)
+                    .bind(&item.keyword)
+                    .bind(joined_tags(&item.tags))
+                    .bind(&uuid)
+                    .bind(&user_uuid)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| format!("更新网站 {} 失败: {e}", item.url))?;
+                    // 预览之后被删除的网站不会被恢复
+                    if updated.rows_affected() == 0 {
+                        result.skipped_items += 1;
+                    } else {
+                        result.updated_items += 1;
+                    }
+                }
+                ItemAction::Skip => result.skipped_items += 1,
+            }
+        }
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("提交导入事务失败: {e}"))?;
+    info!("书签导入完成: {result:?}");
+    Ok(result)
+}
+
+/// 返回书签图标在图标目录中的文件名，内嵌的 `data:` 图标此时才写入文件。
+fn local_icon_path(item: &BookmarkItem, icons_dir: &Path) -> Option<String> {
+    item.local_icon_path.clone().or_else(|| {
+        item.icon_data_uri
+            .as_deref()
+            .and_then(|uri| save_data_uri_icon(uri, icons_dir))
+    })
+}
+
+/// 把标签去重后用逗号连接，与 Netscape 书签的 `TAGS` 属性格式一致；没有标签时返回 `None`。
+fn joined_tags(tags: &[String]) -> Option<String> {
+    let mut seen = HashSet::new();
+    let tags: Vec<&str> = tags
+        .iter()
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty() && seen.insert(tag.to_lowercase()))
+        .collect();
+    (!tags.is_empty()).then(|| tags.join(","))
+}
+
+/// 根据已有数据生成导入预览。
+fn build_preview(
+    groups: Vec<BookmarkGroup>,
+    websites: &[ExistingWebsite],
+    existing_groups: &[ExistingGroup],
+) -> ImportPreview {
+    let mut exact: HashMap<String, &ExistingWebsite> = HashMap::new();
+    let mut near: HashMap<String, &ExistingWebsite> = HashMap::new();
+    for website in websites {
+        exact.entry(exact_key(&website.url)).or_insert(website);
+        if let Some(key) = near_key(&website.url) {
+            near.entry(key).or_insert(website);
+        }
+    }
+    let group_uuids: HashMap<&str, &str> = existing_groups
+        .iter()
+        .map(|group| (group.name.as_str(), group.uuid.as_str()))
+        .collect();
+
+    let mut folders = Vec::new();
+    for group in groups {
+        collect_folders(group, &mut folders);
+    }
+
+    let mut preview = ImportPreview::default();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for (name, items) in folders {
+        let existing_group_uuid = group_uuids.get(name.as_str()).map(|uuid| uuid.to_string());
+        if existing_group_uuid.is_some() {
+            preview.existing_group_count += 1;
+        }
+
+        let items = items
+            .into_iter()
+            .map(|item| {
+                let key = exact_key(&item.url);
+                let (status, existing) = if let Some(website) = exact.get(&key) {
+                    (DuplicateStatus::ExactDuplicate, Some((*website).clone()))
+                } else if let Some(website) = near_key(&item.url).and_then(|k| near.get(&k)) {
+                    (DuplicateStatus::NearDuplicate, Some((*website).clone()))
+                } else if seen.contains(&key) {
+                    (DuplicateStatus::DuplicateInImport, None)
+                } else {
+                    (DuplicateStatus::New, None)
+                };
+                seen.insert(key);
+
+                match status {
+                    DuplicateStatus::New => preview.new_count += 1,
+                    DuplicateStatus::ExactDuplicate => preview.exact_duplicate_count += 1,
+                    DuplicateStatus::NearDuplicate => preview.near_duplicate_count += 1,
+                    DuplicateStatus::DuplicateInImport => preview.duplicate_in_import_count += 1,
+                }
+                ItemPreview {
+                    item,
+                    status,
+                    existing,
+                }
+            })
+            .collect();
+
+        preview.groups.push(GroupPreview {
+            name,
+            existing_group_uuid,
+            items,
+        });
+    }
+
+    preview
+}
+
+/// 把分组及其子文件夹展开为 `(名称, 书签)` 列表。
+fn collect_folders(group: BookmarkGroup, folders: &mut Vec<(String, Vec<BookmarkItem>)>) {
+    if !group.items.is_empty() {
+        folders.push((group.name, group.items));
+    }
+    for child in group.children {
+        collect_folders(child, folders);
+    }
+}
+
+/// 完全重复的比对键：经过 `Url` 解析后的标准形式（主机名小写、补全根路径等）。
+fn exact_key(url: &str) -> String {
+    Url::parse(url.trim())
+        .map(|url| url.to_string())
+        .unwrap_or_else(|_| url.trim().to_string())
+}
+
+/// 近似重复时忽略的跟踪参数，`utm_` 开头的参数也会被忽略。
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "msclkid", "yclid", "igshid", "mc_cid", "mc_eid", "ref_src", "spm",
+    "_ga",
+];
+
+/// 近似重复的比对键：忽略协议、`www.` 前缀、默认端口、片段、末尾斜杠、
+/// 跟踪参数以及查询参数的顺序，保留主机、端口、路径和其余查询参数。
+///
+/// 查询参数往往决定了页面内容（如 `youtube.com/watch?v=...`），因此不能忽略。
+fn near_key(url: &str) -> Option<String> {
+    let url = Url::parse(url.trim()).ok()?;
+    let host = url.host_str()?.trim_start_matches("www.");
+    let port = url
+        .port()
+        .map(|port| format!(":{port}"))
+        .unwrap_or_default();
+    let path = url.path().trim_end_matches('/');
+
+    let mut query: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| !is_tracking_param(name))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    query.sort();
+    let query = if query.is_empty() {
+        String::new()
+    } else {
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        serializer.extend_pairs(query);
+        format!("?{}", serializer.finish())
+    };
+    Some(format!("{host}{port}{path}{query}"))
+}
+
+/// 是否为广告或统计平台附加的跟踪参数。
+fn is_tracking_param(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.starts_with("utm_") || TRACKING_PARAMS.contains(&name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn existing_website(uuid: &str, url: &str) -> ExistingWebsite {
+        ExistingWebsite {
+            uuid: uuid.to_string(),
+            group_uuid: "g1".to_string(),
+            title: uuid.to_string(),
+            url: url.to_string(),
+        }
+    }
+
+    #[test]
+    fn near_key_normalization() {
+        let cases = [
+            ("https://www.example.com/", Some("example.com")),
+            ("http://example.com", Some("example.com")),
+            ("https://Example.COM/Docs/", Some("example.com/Docs")),
+            ("https://example.com:443/docs", Some("example.com/docs")),
+            ("http://example.com:80/docs", Some("example.com/docs")),
+            (
+                "http://example.com:8080/docs",
+                Some("example.com:8080/docs"),
+            ),
+            ("https://example.com/page#section", Some("example.com/page")),
+            (
+                "https://example.com/watch?v=1&utm_source=x&fbclid=y&gclid=z",
+                Some("example.com/watch?v=1"),
+            ),
+            ("https://example.com/?b=2&a=1", Some("example.com?a=1&b=2")),
+            ("  https://example.com/a  ", Some("example.com/a")),
+            ("mailto:someone@example.com", None),
+            ("not a url", None),
+        ];
+        for (url, expected) in cases {
+            assert_eq!(near_key(url).as_deref(), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn query_strings_are_part_of_near_key() {
+        assert_ne!(
+            near_key("https://www.youtube.com/watch?v=a"),
+            near_key("https://www.youtube.com/watch?v=b")
+        );
+    }
+
+    #[test]
+    fn preview_classifies_duplicates() {
+        let websites = [
+            existing_website("exact", "https://example.com/a"),
+            existing_website("near", "https://www.rust-lang.org/learn/"),
+        ];
+        let existing_groups = [ExistingGroup {
+            uuid: "g1".to_string(),
+            name: "Work".to_string(),
+        }];
+        let groups: Vec<BookmarkGroup> = serde_json::from_value(json!([{
+            "name": "Work",
+            "items": [
+                { "title": "A", "url": "https://example.com/a" },
+                { "title": "Rust", "url": "http://rust-lang.org/learn?utm_source=x" },
+                { "title": "New", "url": "https://new.example.com" },
+            ],
+            "children": [{
+                "name": "Sub",
+                "items": [{ "title": "New again", "url": "https://new.example.com/" }],
+            }],
+        }]))
+        .unwrap();
+
+        let preview = build_preview(groups, &websites, &existing_groups);
+
+        let names: Vec<&str> = preview.groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["Work", "Sub"]);
+        assert_eq!(preview.groups[0].existing_group_uuid.as_deref(), Some("g1"));
+        assert_eq!(preview.groups[1].existing_group_uuid, None);
+        assert_eq!(preview.existing_group_count, 1);
+
+        let statuses: Vec<(DuplicateStatus, Option<&str>)> = preview
+            .groups
+            .iter()
+            .flat_map(|group| &group.items)
+            .map(|item| {
+                (
+                    item.status,
+                    item.existing.as_ref().map(|website| website.uuid.as_str()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                (DuplicateStatus::ExactDuplicate, Some("exact")),
+                (DuplicateStatus::NearDuplicate, Some("near")),
+                (DuplicateStatus::New, None),
+                (DuplicateStatus::DuplicateInImport, None),
+            ]
+        );
+        assert_eq!(
+            (
+                preview.new_count,
+                preview.exact_duplicate_count,
+                preview.near_duplicate_count,
+                preview.duplicate_in_import_count,
+            ),
+            (1, 1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn joined_tags_dedups_case_insensitively() {
+        let tags = ["dev", " Docs ", "", "DEV", "docs"].map(String::from);
+        assert_eq!(joined_tags(&tags).as_deref(), Some("dev,Docs"));
+        assert_eq!(joined_tags(&[]), None);
+    }
+}
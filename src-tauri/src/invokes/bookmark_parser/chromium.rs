@@ -0,0 +1,143 @@
+//! 解析 Chromium 系浏览器（Chrome、Edge、Brave、Vivaldi 等）配置目录中的 `Bookmarks` JSON 文件。
+//!
+//! 该文件包含 `bookmark_bar`、`other`、`synced` 三个根节点，
+//! 每个节点都是 `folder` 或 `url` 类型，文件夹通过 `children` 嵌套。
+
+use super::title::normalize_text;
+use super::{BookmarkGroup, BookmarkItem};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Chromium 时间戳的起点（1601-01-01）与 Unix 纪元之间相差的秒数。
+const WEBKIT_EPOCH_OFFSET_SECS: i64 = 11_644_473_600;
+
+/// `Bookmarks` 文件的顶层结构。
+#[derive(Deserialize, Debug)]
+struct BookmarksFile {
+    roots: Roots,
+}
+
+/// 三个固定的根文件夹，任意一个都可能缺失。
+#[derive(Deserialize, Debug)]
+struct Roots {
+    bookmark_bar: Option<Node>,
+    other: Option<Node>,
+    synced: Option<Node>,
+}
+
+/// 书签树中的一个节点，可能是文件夹也可能是书签。
+#[derive(Deserialize, Debug)]
+struct Node {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    url: Option<String>,
+    /// 自 1601-01-01 起的微秒数，以字符串形式保存。
+    date_added: Option<String>,
+    #[serde(default)]
+    children: Vec<Node>,
+}
+
+/// 读取并解析 Chromium `Bookmarks` 文件，返回与 Netscape 解析器相同结构的文件夹树。
+///
+/// “书签栏”下的书签整理成一个分组放在最前面，书签栏中的每个文件夹各自成为一个顶层分组；
+/// “其他书签”和“移动设备书签”则分别作为一个顶层分组，其中的文件夹保存在 `children` 中。
+pub(super) fn parse_file(path: &Path) -> Result<Vec<BookmarkGroup>, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let file: BookmarksFile =
+        serde_json::from_str(&content).map_err(|e| format!("解析书签 JSON 失败: {e}"))?;
+
+    let mut groups: Vec<BookmarkGroup> = Vec::new();
+
+    if let Some(bar) = file.roots.bookmark_bar {
+        let bar = build_folder(bar, Vec::new());
+        if !bar.items.is_empty() {
+            let mut top_level = BookmarkGroup::new(bar.name.clone(), vec![bar.name]);
+            top_level.items = bar.items;
+            groups.push(top_level);
+        }
+        groups.extend(bar.children);
+    }
+
+    for root in [file.roots.other, file.roots.synced].into_iter().flatten() {
+        let path = vec![normalize_text(&root.name)];
+        groups.push(build_folder(root, path));
+    }
+
+    Ok(groups)
+}
+
+/// 递归地把一个 `folder` 节点还原成 `BookmarkGroup`。
+///
+/// # Arguments
+/// * `node` - 文件夹节点。
+/// * `path` - 该文件夹的完整路径，子文件夹的路径以此为前缀。
+fn build_folder(node: Node, path: Vec<String>) -> BookmarkGroup {
+    let mut folder = BookmarkGroup::new(normalize_text(&node.name), path);
+
+    for child in node.children {
+        match child.kind.as_str() {
+            "folder" => {
+                let mut path = folder.path.clone();
+                path.push(normalize_text(&child.name));
+                folder.children.push(build_folder(child, path));
+            }
+            "url" => {
+                let Some(url) = child.url.filter(|url| !url.is_empty()) else {
+                    continue;
+                };
+                folder.items.push(BookmarkItem {
+                    add_date: child.date_added.as_deref().and_then(webkit_to_unix),
+                    ..BookmarkItem::new(&child.name, url)
+                });
+            }
+            _ => {}
+        }
+    }
+    folder
+}
+
+/// 把 Chromium 的微秒时间戳字符串转换为 Unix 秒，`0` 或无法解析时返回 `None`。
+fn webkit_to_unix(value: &str) -> Option<i64> {
+    let micros: i64 = value.parse().ok()?;
+    if micros <= 0 {
+        return None;
+    }
+    Some(micros / 1_000_000 - WEBKIT_EPOCH_OFFSET_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{names, parse_fixture, titles};
+    use super::*;
+
+    #[test]
+    fn chromium_profile() {
+        let groups = parse_fixture("chromium.json", parse_file);
+        assert_eq!(
+            names(&groups),
+            [
+                "Bookmarks bar",
+                "Dev & Ops",
+                "Other bookmarks",
+                "Mobile bookmarks"
+            ]
+        );
+        assert_eq!(titles(&groups[0]), ["Rust"]);
+        assert_eq!(groups[0].items[0].url, "https://www.rust-lang.org/");
+        assert_eq!(groups[0].items[0].add_date, Some(1_701_205_301));
+
+        assert_eq!(groups[1].path, ["Dev & Ops"]);
+        assert_eq!(groups[1].items[0].url, "http://grafana.lan:3000/");
+        assert_eq!(groups[1].items[0].add_date, None);
+
+        // 根文件夹的路径与名称一样经过规范化，空 URL 的书签被忽略
+        let other = &groups[2];
+        assert_eq!(other.path, ["Other bookmarks"]);
+        assert!(other.items.is_empty());
+        assert_eq!(other.children[0].path, ["Other bookmarks", "Apps"]);
+        assert_eq!(titles(&other.children[0]), ["Tauri"]);
+    }
+}
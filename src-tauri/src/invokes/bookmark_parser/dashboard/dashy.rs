@@ -0,0 +1,53 @@
+//! 解析 Dashy 的 `conf.yml`。
+//!
+//! `sections` 中的每个分区成为一个分组，分区下的 `items`（以及其中的 `subItems`）成为书签；
+//! 状态检查使用的 `statusCheckUrl` 与 `url` 不同时作为内网地址。
+
+use super::super::BookmarkGroup;
+use super::super::title::normalize_text;
+use super::{IconResolver, lan_url, service_item, yaml_str};
+use serde_yaml::Value;
+
+/// Dashy 把本地图标放在 `item-icons` 目录中，旧版本位于 `public/item-icons`。
+const ICON_SEARCH_DIRS: &[&str] = &["item-icons", "../public/item-icons", "public/item-icons"];
+
+/// 把 Dashy 配置转换为分组列表。
+pub(super) fn parse_config(config: &Value, icons: &IconResolver) -> Vec<BookmarkGroup> {
+    let Some(sections) = config.get("sections").and_then(Value::as_sequence) else {
+        return Vec::new();
+    };
+
+    sections
+        .iter()
+        .map(|section| {
+            let name = normalize_text(yaml_str(section, "name").unwrap_or_default());
+            let mut group = BookmarkGroup::new(name.clone(), vec![name]);
+            let items = section.get("items").and_then(Value::as_sequence);
+            for item in items.into_iter().flatten() {
+                push_item(&mut group, item, icons);
+            }
+            group
+        })
+        .collect()
+}
+
+/// 把一个条目及其 `subItems` 加入分组。
+fn push_item(group: &mut BookmarkGroup, item: &Value, icons: &IconResolver) {
+    if let Some(url) = yaml_str(item, "url") {
+        let icon = yaml_str(item, "icon")
+            .map(|reference| icons.resolve(reference, ICON_SEARCH_DIRS))
+            .unwrap_or_default();
+        group.items.push(service_item(
+            yaml_str(item, "title").unwrap_or_default(),
+            url,
+            yaml_str(item, "description"),
+            lan_url(url, yaml_str(item, "statusCheckUrl")),
+            icon,
+        ));
+    }
+
+    let sub_items = item.get("subItems").and_then(Value::as_sequence);
+    for sub_item in sub_items.into_iter().flatten() {
+        push_item(group, sub_item, icons);
+    }
+}
@@ -0,0 +1,148 @@
+//! 解析 Heimdall 的 `app.sqlite` 数据库。
+//!
+//! `items` 表中 `type = 0` 的记录是应用，`type = 1` 的记录是标签，二者通过 `item_tag` 关联。
+//! 每个标签成为一个分组，未加入任何标签的应用放在名为 `Heimdall` 的分组中。
+//! 增强型应用的配置（`description` 列中的 JSON）里的 `override_url` 作为内网地址。
+
+use super::super::BookmarkGroup;
+use super::super::title::normalize_text;
+use super::{IconRef, IconResolver, lan_url, service_item};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::{Connection, FromRow};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 未加入任何标签的应用所在的分组名称。
+const DEFAULT_GROUP_NAME: &str = "Heimdall";
+
+/// Heimdall 中代表首页的内置标签 ID，不作为单独的分组。
+const DASHBOARD_TAG_ID: i64 = 0;
+
+/// 图标保存在 `storage/app/public/icons` 中，Docker 镜像中则与数据库位于同一目录。
+const ICON_SEARCH_DIRS: &[&str] = &["../storage/app/public", "storage/app/public"];
+
+/// `items` 表中的一条记录。
+#[derive(FromRow, Debug)]
+struct ItemRow {
+    id: i64,
+    title: Option<String>,
+    url: Option<String>,
+    icon: Option<String>,
+    /// 增强型应用的 JSON 配置。
+    config: Option<String>,
+    /// 应用描述，旧版本的数据库中没有该列。
+    app_description: Option<String>,
+    kind: i64,
+}
+
+/// 读取 Heimdall 数据库，返回分组列表。
+pub(super) async fn parse_file(
+    path: &Path,
+    icons: &IconResolver<'_>,
+) -> Result<Vec<BookmarkGroup>, String> {
+    let options = SqliteConnectOptions::new().filename(path).read_only(true);
+    let mut conn = SqliteConnection::connect_with(&options)
+        .await
+        .map_err(|e| format!("打开 app.sqlite 失败: {e}"))?;
+
+    let has_app_description: bool = sqlx::query_scalar(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('items') WHERE name = 'appdescription'",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .map_err(|e| format!("读取 items 表结构失败: {e}"))?;
+
+    let rows: Vec<ItemRow> = sqlx::query_as(&format!(
+        "SELECT id, title, url, icon, description AS config, {} AS app_description, type AS kind
+         FROM items WHERE deleted_at IS NULL ORDER BY \"order\", id",
+        if has_app_description {
+            "appdescription"
+        } else {
+            "NULL"
+        }
+    ))
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| format!("读取 items 失败: {e}"))?;
+
+    let links: Vec<(i64, i64)> = sqlx::query_as("SELECT item_id, tag_id FROM item_tag")
+        .fetch_all(&mut conn)
+        .await
+        .map_err(|e| format!("读取 item_tag 失败: {e}"))?;
+
+    let _ = conn.close().await;
+
+    let mut tags_by_item: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (item_id, tag_id) in links {
+        if tag_id != DASHBOARD_TAG_ID {
+            tags_by_item.entry(item_id).or_default().push(tag_id);
+        }
+    }
+
+    let mut default_group = BookmarkGroup::new(
+        DEFAULT_GROUP_NAME.to_string(),
+        vec![DEFAULT_GROUP_NAME.to_string()],
+    );
+    let mut tag_groups: Vec<(i64, BookmarkGroup)> = rows
+        .iter()
+        .filter(|row| row.kind == 1)
+        .map(|row| {
+            let name = normalize_text(row.title.as_deref().unwrap_or_default());
+            (row.id, BookmarkGroup::new(name.clone(), vec![name]))
+        })
+        .collect();
+
+    for row in rows.iter().filter(|row| row.kind == 0) {
+        let Some(url) = row
+            .url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+        else {
+            continue;
+        };
+        let override_url = row
+            .config
+            .as_deref()
+            .and_then(|config| serde_json::from_str::<serde_json::Value>(config).ok())
+            .and_then(|config| {
+                config
+                    .get("override_url")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::trim)
+                    .map(String::from)
+            });
+        let item = service_item(
+            row.title.as_deref().unwrap_or_default(),
+            url,
+            row.app_description
+                .as_deref()
+                .map(str::trim)
+                .filter(|d| !d.is_empty()),
+            lan_url(url, override_url.as_deref()),
+            row.icon
+                .as_deref()
+                .map(|icon| icons.resolve(icon, ICON_SEARCH_DIRS))
+                .unwrap_or_else(IconRef::default),
+        );
+
+        let tag_ids = tags_by_item
+            .get(&row.id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let mut placed = false;
+        for (tag_id, group) in &mut tag_groups {
+            if tag_ids.contains(tag_id) {
+                group.items.push(item.clone());
+                placed = true;
+            }
+        }
+        if !placed {
+            default_group.items.push(item);
+        }
+    }
+
+    let mut groups = vec![default_group];
+    groups.extend(tag_groups.into_iter().map(|(_, group)| group));
+    Ok(groups)
+}
@@ -0,0 +1,94 @@
+//! 解析 gethomepage 的 `services.yaml` 和 `bookmarks.yaml`。
+//!
+//! 两个文件的顶层都是 `- 分组名: [...]` 形式的列表，分组中的每一项也是只有一个键的映射：
+//! 值为映射时是服务（`services.yaml`），值为包含 `href` 的列表时是书签（`bookmarks.yaml`），
+//! 值为其他列表时是嵌套的子分组。服务的 `widget.url` 或 `siteMonitor` 与 `href` 不同时作为内网地址。
+
+use super::super::BookmarkGroup;
+use super::super::title::normalize_text;
+use super::{IconResolver, lan_url, service_item, yaml_str};
+use serde_yaml::Value;
+
+/// 本地图标位于 Homepage 的 `public` 目录中，而配置文件位于与其同级的 `config` 目录中。
+const ICON_SEARCH_DIRS: &[&str] = &["../public"];
+
+/// 把 Homepage 配置转换为分组列表。
+pub(super) fn parse_config(config: &Value, icons: &IconResolver) -> Vec<BookmarkGroup> {
+    config
+        .as_sequence()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| parse_group(entry, &[], icons))
+        .collect()
+}
+
+/// 解析一个 `- 分组名: [...]` 形式的分组，子分组保存在 `children` 中。
+fn parse_group(
+    entry: &Value,
+    parent_path: &[String],
+    icons: &IconResolver,
+) -> Option<BookmarkGroup> {
+    let (name, members) = single_entry(entry)?;
+    let members = members.as_sequence()?;
+
+    let name = normalize_text(name);
+    let mut path = parent_path.to_vec();
+    path.push(name.clone());
+    let mut group = BookmarkGroup::new(name, path);
+
+    for member in members {
+        let Some((title, value)) = single_entry(member) else {
+            continue;
+        };
+        match value {
+            Value::Mapping(_) => {
+                let Some(url) = yaml_str(value, "href") else {
+                    continue;
+                };
+                let internal = value
+                    .get("widget")
+                    .and_then(|widget| yaml_str(widget, "url"))
+                    .or_else(|| yaml_str(value, "siteMonitor"));
+                group.items.push(service_item(
+                    title,
+                    url,
+                    yaml_str(value, "description"),
+                    lan_url(url, internal),
+                    resolve_icon(value, icons),
+                ));
+            }
+            Value::Sequence(entries) => {
+                if let Some(bookmark) = entries.first().filter(|first| first.get("href").is_some())
+                {
+                    let Some(url) = yaml_str(bookmark, "href") else {
+                        continue;
+                    };
+                    group.items.push(service_item(
+                        title,
+                        url,
+                        yaml_str(bookmark, "description"),
+                        None,
+                        resolve_icon(bookmark, icons),
+                    ));
+                } else if let Some(child) = parse_group(member, &group.path, icons) {
+                    group.children.push(child);
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(group)
+}
+
+/// 读取只有一个键的映射，返回其键和值。
+fn single_entry(value: &Value) -> Option<(&str, &Value)> {
+    let (key, value) = value.as_mapping()?.iter().next()?;
+    Some((key.as_str()?, value))
+}
+
+/// 解析服务或书签的 `icon` 字段。
+fn resolve_icon(value: &Value, icons: &IconResolver) -> super::IconRef {
+    yaml_str(value, "icon")
+        .map(|reference| icons.resolve(reference, ICON_SEARCH_DIRS))
+        .unwrap_or_default()
+}
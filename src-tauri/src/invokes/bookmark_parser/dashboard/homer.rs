@@ -0,0 +1,45 @@
+//! 解析 Homer 的 `config.yml`。
+//!
+//! `services` 中的每个分区成为一个分组，分区下的 `items` 成为书签；
+//! 书签的 `subtitle` 作为描述，`logo`（优先）或 `icon` 作为图标，
+//! 智能卡片使用的 `endpoint` 与 `url` 不同时作为内网地址。
+
+use super::super::BookmarkGroup;
+use super::super::title::normalize_text;
+use super::{IconResolver, lan_url, service_item, yaml_str};
+use serde_yaml::Value;
+
+/// 把 Homer 配置转换为分组列表。
+pub(super) fn parse_config(config: &Value, icons: &IconResolver) -> Vec<BookmarkGroup> {
+    let Some(sections) = config.get("services").and_then(Value::as_sequence) else {
+        return Vec::new();
+    };
+
+    sections
+        .iter()
+        .map(|section| {
+            let name = normalize_text(yaml_str(section, "name").unwrap_or_default());
+            let mut group = BookmarkGroup::new(name.clone(), vec![name]);
+
+            let items = section.get("items").and_then(Value::as_sequence);
+            for item in items.into_iter().flatten() {
+                let Some(url) = yaml_str(item, "url") else {
+                    continue;
+                };
+                // `logo` 是相对于 Homer 网站根目录的路径，而 `config.yml` 位于其下的 `assets` 目录中
+                let icon = yaml_str(item, "logo")
+                    .or_else(|| yaml_str(item, "icon"))
+                    .map(|reference| icons.resolve(reference, &[".."]))
+                    .unwrap_or_default();
+                group.items.push(service_item(
+                    yaml_str(item, "name").unwrap_or_default(),
+                    url,
+                    yaml_str(item, "subtitle"),
+                    lan_url(url, yaml_str(item, "endpoint")),
+                    icon,
+                ));
+            }
+            group
+        })
+        .collect()
+}
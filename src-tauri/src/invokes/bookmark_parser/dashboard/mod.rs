@@ -0,0 +1,313 @@
+//! 解析自托管导航页（Homer、Homepage、Dashy、Heimdall）的配置。
+//!
+//! 这些导航页都以“分区 + 服务”的方式组织链接：分区对应 `website_groups`，
+//! 服务对应 `websites`，其中的描述、图标以及单独配置的内网地址（`url_lan`）也会一并导入。
+
+mod dashy;
+mod heimdall;
+mod homepage;
+mod homer;
+
+use super::{BookmarkGroup, BookmarkItem};
+use crate::invokes::metadata::save_icon_to_dir;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::fs;
+use std::path::Path;
+use url::Url;
+
+/// 支持导入的导航页类型。
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DashboardKind {
+    /// Homer 的 `config.yml`。
+    Homer,
+    /// gethomepage 的 `services.yaml` 或 `bookmarks.yaml`。
+    Homepage,
+    /// Dashy 的 `conf.yml`。
+    Dashy,
+    /// Heimdall 的 `app.sqlite`。
+    Heimdall,
+}
+
+/// 读取并解析导航页配置，返回与其他解析器相同结构的文件夹树。
+///
+/// 未指定 `kind` 时根据文件内容自动判断：SQLite 数据库视为 Heimdall，
+/// 顶层为列表的 YAML 视为 Homepage，含有 `sections` 的视为 Dashy，含有 `services` 的视为 Homer。
+/// 配置中引用的本地图标文件会被复制到 `icons_dir` 中。
+pub(super) async fn parse_file(
+    path: &Path,
+    kind: Option<DashboardKind>,
+    icons_dir: &Path,
+) -> Result<Vec<BookmarkGroup>, String> {
+    let kind = match kind {
+        Some(kind) => kind,
+        None => detect_kind(path)?,
+    };
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    let icons = IconResolver {
+        base_dir,
+        icons_dir,
+    };
+
+    match kind {
+        DashboardKind::Homer => Ok(homer::parse_config(&read_yaml(path)?, &icons)),
+        DashboardKind::Homepage => Ok(homepage::parse_config(&read_yaml(path)?, &icons)),
+        DashboardKind::Dashy => Ok(dashy::parse_config(&read_yaml(path)?, &icons)),
+        DashboardKind::Heimdall => heimdall::parse_file(path, &icons).await,
+    }
+}
+
+/// 读取 YAML 配置文件。
+fn read_yaml(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_yaml::from_str(&content).map_err(|e| format!("解析 YAML 配置失败: {e}"))
+}
+
+/// 根据文件内容判断导航页类型。
+fn detect_kind(path: &Path) -> Result<DashboardKind, String> {
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    if bytes.starts_with(b"SQLite format 3\0") {
+        return Ok(DashboardKind::Heimdall);
+    }
+
+    let config: Value =
+        serde_yaml::from_slice(&bytes).map_err(|e| format!("解析 YAML 配置失败: {e}"))?;
+    if config.is_sequence() {
+        Ok(DashboardKind::Homepage)
+    } else if config.get("sections").is_some() {
+        Ok(DashboardKind::Dashy)
+    } else if config.get("services").is_some() {
+        Ok(DashboardKind::Homer)
+    } else {
+        Err("无法识别的导航页配置".to_string())
+    }
+}
+
+/// 读取 YAML 映射中的字符串字段，去掉首尾空白，空字符串视为不存在。
+fn yaml_str<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// 当内网地址是一个与外网地址不同的 HTTP(S) 链接时，将其作为 `url_lan`。
+fn lan_url(url: &str, candidate: Option<&str>) -> Option<String> {
+    let candidate = candidate?;
+    let parsed = Url::parse(candidate).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") || candidate == url {
+        return None;
+    }
+    Some(candidate.to_string())
+}
+
+/// 根据导航页中一个服务的配置构建书签项。
+fn service_item(
+    title: &str,
+    url: &str,
+    description: Option<&str>,
+    url_lan: Option<String>,
+    icon: IconRef,
+) -> BookmarkItem {
+    BookmarkItem {
+        description: description.map(String::from),
+        url_lan,
+        default_icon: icon.default_icon,
+        local_icon_path: icon.local_icon_path,
+        ..BookmarkItem::new(title, url.to_string())
+    }
+}
+
+/// 导航页配置中图标引用的解析结果。
+#[derive(Default, Debug)]
+struct IconRef {
+    /// Iconify 图标名称。
+    default_icon: Option<String>,
+    /// 复制到图标目录中的本地图标文件名。
+    local_icon_path: Option<String>,
+}
+
+/// 把导航页配置中的图标引用转换为 Iconify 图标名称或本地图标文件。
+struct IconResolver<'a> {
+    /// 配置文件所在的目录，相对路径的图标以此为基准查找。
+    base_dir: &'a Path,
+    icons_dir: &'a Path,
+}
+
+impl IconResolver<'_> {
+    /// 解析一个图标引用。
+    ///
+    /// 支持 Font Awesome 类名（`fas fa-server`）、Material Design Icons（`mdi-server`）、
+    /// Simple Icons（`si-grafana`）和 Iconify 名称（`mdi:server`）；
+    /// 其他引用被视为相对于配置文件（或 `search_dirs` 中的目录）的本地图片路径。
+    /// 远程图片地址和 `favicon` 之类的特殊值不在这里处理，留给元数据获取。
+    fn resolve(&self, reference: &str, search_dirs: &[&str]) -> IconRef {
+        let reference = reference.trim();
+        if reference.is_empty() || reference.contains("://") {
+            return IconRef::default();
+        }
+        if let Some(name) = iconify_name(reference) {
+            return IconRef {
+                default_icon: Some(name),
+                local_icon_path: None,
+            };
+        }
+
+        let relative = reference.trim_start_matches('/');
+        let candidates = std::iter::once(self.base_dir.join(relative)).chain(
+            search_dirs
+                .iter()
+                .map(|dir| self.base_dir.join(dir).join(relative)),
+        );
+        for candidate in candidates {
+            if candidate.is_file() {
+                return IconRef {
+                    default_icon: None,
+                    local_icon_path: self.copy_icon(&candidate),
+                };
+            }
+        }
+        IconRef::default()
+    }
+
+    /// 把本地图标文件复制到图标目录，返回保存后的文件名。
+    fn copy_icon(&self, path: &Path) -> Option<String> {
+        let extension = path
+            .extension()
+            .and_then(std::ffi::OsStr::to_str)?
+            .to_lowercase();
+        if !matches!(
+            extension.as_str(),
+            "png" | "svg" | "ico" | "jpg" | "jpeg" | "gif" | "webp"
+        ) {
+            return None;
+        }
+        let bytes = fs::read(path).ok()?;
+        match save_icon_to_dir(self.icons_dir, &bytes) {
+            Ok(file_name) => Some(file_name),
+            Err(e) => {
+                warn!("复制导航页图标失败 {path:?}: {e}");
+                None
+            }
+        }
+    }
+}
+
+/// 把常见的图标类名转换为 Iconify 图标名称。
+fn iconify_name(reference: &str) -> Option<String> {
+    // Font Awesome：`fas fa-server`、`fa-solid fa-server`、`fab fa-github`
+    let mut tokens = reference.split_whitespace();
+    if let (Some(style), Some(icon)) = (tokens.next(), tokens.next()) {
+        let collection = match style {
+            "fa" | "fas" | "fa-solid" => Some("fa6-solid"),
+            "far" | "fa-regular" => Some("fa6-regular"),
+            "fab" | "fa-brands" => Some("fa6-brands"),
+            _ => None,
+        };
+        if let Some(collection) = collection
+            && let Some(name) = icon.strip_prefix("fa-")
+        {
+            return Some(format!("{collection}:{name}"));
+        }
+        return None;
+    }
+
+    if let Some(name) = reference.strip_prefix("mdi-") {
+        return Some(format!("mdi:{name}"));
+    }
+    if let Some(name) = reference.strip_prefix("si-") {
+        return Some(format!("simple-icons:{name}"));
+    }
+    // 已经是 Iconify 名称，如 `mdi:server`
+    if let Some((prefix, name)) = reference.split_once(':')
+        && !prefix.is_empty()
+        && !name.is_empty()
+        && !reference.contains(['/', '.'])
+    {
+        return Some(reference.to_string());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{fixture_path, names, titles};
+    use super::*;
+
+    /// 自动判断类型并解析测试配置，测试配置中没有需要复制的本地图标。
+    async fn parse_fixture(name: &str) -> Vec<BookmarkGroup> {
+        parse_file(&fixture_path(name), None, &std::env::temp_dir())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn homer_config() {
+        let groups = parse_fixture("homer.yml").await;
+        assert_eq!(names(&groups), ["Monitoring", "Media Apps"]);
+        assert_eq!(groups[1].path, ["Media Apps"]);
+        assert_eq!(titles(&groups[0]), ["Grafana", "Uptime Kuma"]);
+
+        let grafana = &groups[0].items[0];
+        assert_eq!(grafana.url, "https://grafana.example.com");
+        assert_eq!(grafana.description.as_deref(), Some("Dashboards"));
+        assert_eq!(grafana.url_lan.as_deref(), Some("http://10.0.0.5:3000"));
+
+        let kuma = &groups[0].items[1];
+        assert_eq!(kuma.url_lan, None);
+        assert_eq!(kuma.default_icon.as_deref(), Some("fa6-solid:server"));
+        // 没有 `url` 的服务被忽略
+        assert_eq!(titles(&groups[1]), ["Jellyfin"]);
+    }
+
+    #[tokio::test]
+    async fn homepage_config() {
+        let groups = parse_fixture("homepage.yaml").await;
+        assert_eq!(names(&groups), ["Infrastructure", "Developer"]);
+
+        let proxmox = &groups[0].items[0];
+        assert_eq!(proxmox.title, "Proxmox");
+        assert_eq!(proxmox.url, "https://pve.example.com");
+        assert_eq!(proxmox.description.as_deref(), Some("Virtualization"));
+        assert_eq!(proxmox.url_lan.as_deref(), Some("http://192.168.1.2:8006"));
+
+        let nested = &groups[0].children[0];
+        assert_eq!(nested.path, ["Infrastructure", "Nested"]);
+        let router = &nested.items[0];
+        assert_eq!(router.url_lan.as_deref(), Some("http://192.168.1.1"));
+        assert_eq!(router.default_icon.as_deref(), Some("mdi:router-wireless"));
+
+        let github = &groups[1].items[0];
+        assert_eq!(github.title, "GitHub");
+        assert_eq!(github.url, "https://github.com/");
+        assert_eq!(github.default_icon.as_deref(), Some("simple-icons:github"));
+    }
+
+    #[tokio::test]
+    async fn dashy_config() {
+        let groups = parse_fixture("dashy.yml").await;
+        assert_eq!(names(&groups), ["Productivity", "Empty"]);
+        assert_eq!(titles(&groups[0]), ["Nextcloud", "IT Tools", "CyberChef"]);
+
+        let nextcloud = &groups[0].items[0];
+        assert_eq!(nextcloud.url, "https://cloud.example.com");
+        assert_eq!(nextcloud.description.as_deref(), Some("Files"));
+        assert_eq!(nextcloud.url_lan.as_deref(), Some("http://nextcloud.lan"));
+        assert_eq!(nextcloud.default_icon.as_deref(), Some("mdi:cloud"));
+        assert!(groups[1].items.is_empty());
+    }
+
+    #[test]
+    fn iconify_names() {
+        assert_eq!(
+            iconify_name("fab fa-github").as_deref(),
+            Some("fa6-brands:github")
+        );
+        assert_eq!(iconify_name("mdi-server").as_deref(), Some("mdi:server"));
+        assert_eq!(iconify_name("logo.png"), None);
+        assert_eq!(iconify_name("c:/icons/logo.png"), None);
+    }
+}
@@ -0,0 +1,191 @@
+//! 解析 Firefox 配置目录中的 `places.sqlite` 书签数据库。
+//!
+//! Firefox 运行时会锁定 `places.sqlite`，因此先把数据库（以及尚未合并的 `-wal` 文件）
+//! 复制到临时目录，再以只读方式打开副本，通过 `moz_bookmarks`/`moz_places`
+//! 还原文件夹树，并读取 `moz_keywords` 中的关键字和标签文件夹中的标签。
+
+use super::title::normalize_text;
+use super::{BookmarkGroup, BookmarkItem};
+use crate::utils::copy_database_to_temp;
+use log::warn;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::{Connection, FromRow};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// `moz_bookmarks.type` 中表示书签的值。
+const TYPE_BOOKMARK: i64 = 1;
+/// `moz_bookmarks.type` 中表示文件夹的值。
+const TYPE_FOLDER: i64 = 2;
+
+/// 各个内置根文件夹的 GUID。
+const TOOLBAR_GUID: &str = "toolbar_____";
+const MENU_GUID: &str = "menu________";
+const UNFILED_GUID: &str = "unfiled_____";
+const MOBILE_GUID: &str = "mobile______";
+const TAGS_GUID: &str = "tags________";
+
+/// `moz_bookmarks` 与 `moz_places`、`moz_keywords` 联合查询得到的一行记录。
+///
+/// 同一网址可以有多个关键字，只取其中按字母顺序最小的一个，保证每个书签只有一行。
+#[derive(FromRow, Debug)]
+struct BookmarkRow {
+    id: i64,
+    kind: i64,
+    parent: Option<i64>,
+    fk: Option<i64>,
+    title: Option<String>,
+    guid: String,
+    url: Option<String>,
+    date_added: Option<i64>,
+    last_modified: Option<i64>,
+    keyword: Option<String>,
+}
+
+/// 读取并解析 Firefox 的 `places.sqlite`，返回与 Netscape 解析器相同结构的文件夹树。
+///
+/// “书签工具栏”下的书签整理成一个分组放在最前面，工具栏中的每个文件夹各自成为一个顶层分组；
+/// “书签菜单”、“其他书签”和“移动设备书签”则分别作为一个顶层分组。
+pub(super) async fn parse_file(path: &Path) -> Result<Vec<BookmarkGroup>, String> {
+    let copy_dir = copy_database_to_temp(path)?;
+    let result = read_groups(&copy_dir.join(path.file_name().unwrap_or_default())).await;
+    if let Err(e) = fs::remove_dir_all(&copy_dir) {
+        warn!("清理 places.sqlite 临时副本失败 {copy_dir:?}: {e}");
+    }
+    result
+}
+
+/// 以只读方式打开数据库副本并还原书签树。
+async fn read_groups(db_path: &Path) -> Result<Vec<BookmarkGroup>, String> {
+    let options = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true);
+    let mut conn = SqliteConnection::connect_with(&options)
+        .await
+        .map_err(|e| format!("打开 places.sqlite 失败: {e}"))?;
+
+    let rows: Vec<BookmarkRow> = sqlx::query_as(
+        "SELECT b.id, b.type AS kind, b.parent, b.fk, b.title, b.guid, p.url,
+                b.dateAdded AS date_added, b.lastModified AS last_modified,
+                (SELECT MIN(k.keyword) FROM moz_keywords k WHERE k.place_id = b.fk) AS keyword
+         FROM moz_bookmarks b
+         LEFT JOIN moz_places p ON p.id = b.fk
+         ORDER BY b.parent, b.position",
+    )
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| format!("读取 moz_bookmarks 失败: {e}"))?;
+
+    let _ = conn.close().await;
+
+    // 标签在 Firefox 中以“标签根文件夹 -> 标签文件夹 -> 书签”的形式存储
+    let tags_root = rows
+        .iter()
+        .find(|row| row.guid == TAGS_GUID)
+        .map(|row| row.id);
+    let tag_folders: HashMap<i64, String> = rows
+        .iter()
+        .filter(|row| row.kind == TYPE_FOLDER && row.parent.is_some() && row.parent == tags_root)
+        .map(|row| (row.id, row.title.clone().unwrap_or_default()))
+        .collect();
+    let mut tags: HashMap<i64, Vec<String>> = HashMap::new();
+    for row in &rows {
+        if row.kind == TYPE_BOOKMARK
+            && let Some(fk) = row.fk
+            && let Some(tag) = row.parent.and_then(|parent| tag_folders.get(&parent))
+        {
+            tags.entry(fk).or_default().push(tag.clone());
+        }
+    }
+
+    let mut by_parent: HashMap<i64, Vec<&BookmarkRow>> = HashMap::new();
+    for row in &rows {
+        if let Some(parent) = row.parent {
+            by_parent.entry(parent).or_default().push(row);
+        }
+    }
+
+    let tree = FolderTree { by_parent, tags };
+    let root = |guid: &str| rows.iter().find(|row| row.guid == guid);
+    let mut groups: Vec<BookmarkGroup> = Vec::new();
+
+    if let Some(toolbar) = root(TOOLBAR_GUID) {
+        let name = root_display_name(toolbar);
+        let toolbar = tree.build_folder(toolbar.id, BookmarkGroup::new(name.clone(), Vec::new()));
+        if !toolbar.items.is_empty() {
+            let mut top_level = BookmarkGroup::new(name.clone(), vec![name]);
+            top_level.items = toolbar.items;
+            groups.push(top_level);
+        }
+        groups.extend(toolbar.children);
+    }
+
+    for guid in [MENU_GUID, UNFILED_GUID, MOBILE_GUID] {
+        if let Some(row) = root(guid) {
+            let name = root_display_name(row);
+            groups.push(tree.build_folder(row.id, BookmarkGroup::new(name.clone(), vec![name])));
+        }
+    }
+
+    Ok(groups)
+}
+
+/// 按父级 ID 索引的书签记录，以及按 `moz_places.id` 索引的标签。
+struct FolderTree<'a> {
+    by_parent: HashMap<i64, Vec<&'a BookmarkRow>>,
+    tags: HashMap<i64, Vec<String>>,
+}
+
+impl FolderTree<'_> {
+    /// 递归地把 ID 为 `id` 的文件夹下的记录填充到 `folder` 中。
+    fn build_folder(&self, id: i64, mut folder: BookmarkGroup) -> BookmarkGroup {
+        let Some(children) = self.by_parent.get(&id) else {
+            return folder;
+        };
+
+        for row in children {
+            match row.kind {
+                TYPE_FOLDER => {
+                    let name = normalize_text(row.title.as_deref().unwrap_or_default());
+                    let mut path = folder.path.clone();
+                    path.push(name.clone());
+                    folder
+                        .children
+                        .push(self.build_folder(row.id, BookmarkGroup::new(name, path)));
+                }
+                TYPE_BOOKMARK => {
+                    let Some(url) = row.url.clone().filter(|url| !url.is_empty()) else {
+                        continue;
+                    };
+                    folder.items.push(BookmarkItem {
+                        // Firefox 的时间戳单位为微秒
+                        add_date: row.date_added.map(|micros| micros / 1_000_000),
+                        last_modified: row.last_modified.map(|micros| micros / 1_000_000),
+                        keyword: row.keyword.clone(),
+                        tags: row
+                            .fk
+                            .and_then(|fk| self.tags.get(&fk))
+                            .cloned()
+                            .unwrap_or_default(),
+                        ..BookmarkItem::new(row.title.as_deref().unwrap_or_default(), url)
+                    });
+                }
+                // 分隔符等其他类型直接忽略
+                _ => {}
+            }
+        }
+        folder
+    }
+}
+
+/// 内置根文件夹在数据库中的标题是 `toolbar`、`menu` 这类内部名称，这里换成可读的名称。
+fn root_display_name(row: &BookmarkRow) -> String {
+    match row.guid.as_str() {
+        TOOLBAR_GUID => "Bookmarks Toolbar".to_string(),
+        MENU_GUID => "Bookmarks Menu".to_string(),
+        UNFILED_GUID => "Other Bookmarks".to_string(),
+        MOBILE_GUID => "Mobile Bookmarks".to_string(),
+        _ => normalize_text(row.title.as_deref().unwrap_or_default()),
+    }
+}
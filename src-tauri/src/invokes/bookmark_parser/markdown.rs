@@ -0,0 +1,261 @@
+//! 解析以 Markdown 维护的链接列表，如 `awesome-*` 风格的收藏文档。
+//!
+//! `#` 标题构成文件夹，标题层级决定嵌套关系；标题下的 `[标题](链接)` 和 `<链接>`
+//! 成为该文件夹中的书签，没有 Markdown 链接的行则尝试提取其中的裸链接。
+//! 围栏代码块中的内容会被忽略。
+
+use super::outline::OutlineBuilder;
+use super::plain_text::line_bookmark;
+use super::title::normalize_text;
+use super::{BookmarkGroup, BookmarkItem, file_stem_name};
+use std::fs;
+use std::path::Path;
+use url::Url;
+
+/// 读取并解析 Markdown 文件，返回顶层分组构成的文件夹树。
+///
+/// 如果文档只有一个最高级别的标题，且其下还有更低级别的标题，
+/// 则它被视为文档标题，用来命名标题之外的链接所在的分组，而不是单独成为一个文件夹。
+pub(super) fn parse_file(path: &Path) -> Result<Vec<BookmarkGroup>, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let lines = content_lines(&content);
+
+    let headings: Vec<(usize, usize)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| heading(line).map(|(level, _)| (index, level)))
+        .collect();
+    let min_level = headings.iter().map(|(_, level)| *level).min();
+    let document_title = match (headings.first(), min_level) {
+        (Some(&(index, level)), Some(min))
+            if level == min
+                && headings.iter().filter(|(_, level)| *level == min).count() == 1
+                && headings.len() > 1 =>
+        {
+            Some(index)
+        }
+        _ => None,
+    };
+
+    let root_name = document_title
+        .and_then(|index| heading(lines[index]))
+        .map(|(_, text)| normalize_text(&strip_links(text)))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| file_stem_name(path));
+    let mut builder = OutlineBuilder::new(root_name);
+
+    for (index, line) in lines.iter().enumerate() {
+        if let Some((level, text)) = heading(line) {
+            if Some(index) != document_title {
+                builder.open(level, normalize_text(&strip_links(text)));
+            }
+            continue;
+        }
+
+        let links = markdown_links(line);
+        if links.is_empty() {
+            if let Some(item) = line_bookmark(line) {
+                builder.push_item(item);
+            }
+        } else {
+            for (title, url) in links {
+                builder.push_item(BookmarkItem::new(&title, url));
+            }
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+/// 按行拆分文档，并去掉围栏代码块中的行。
+fn content_lines(content: &str) -> Vec<&str> {
+    let mut in_code_block = false;
+    content
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code_block = !in_code_block;
+                return false;
+            }
+            !in_code_block
+        })
+        .collect()
+}
+
+/// 识别 ATX 风格的标题行（`## 标题`），返回其级别和文本。
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let text = &trimmed[level..];
+    if !text.is_empty() && !text.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some((level, text.trim().trim_end_matches('#').trim_end()))
+}
+
+/// 提取一行中所有的 `[标题](链接)` 和 `<链接>`，忽略图片和相对链接。
+fn markdown_links(line: &str) -> Vec<(String, String)> {
+    let mut links = Vec::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find(['[', '<']) {
+        let is_image = rest[..start].ends_with('!');
+        let tail = &rest[start..];
+
+        if tail.starts_with('<') {
+            match tail.find('>') {
+                Some(end) => {
+                    let url = &tail[1..end];
+                    if is_absolute_url(url) {
+                        links.push((String::new(), url.to_string()));
+                    }
+                    rest = &tail[end + 1..];
+                }
+                None => break,
+            }
+            continue;
+        }
+
+        let Some((text, url, consumed)) = inline_link(tail) else {
+            rest = &tail[1..];
+            continue;
+        };
+        if !is_image && is_absolute_url(url) {
+            links.push((strip_links(text), url.to_string()));
+        }
+        rest = &tail[consumed..];
+    }
+    links
+}
+
+/// 解析以 `[` 开头的内联链接，返回链接文本、URL 和消耗的字节数。
+///
+/// 链接文本中允许嵌套一层方括号（如徽章图片），URL 后的可选标题（`"title"`）会被忽略。
+fn inline_link(tail: &str) -> Option<(&str, &str, usize)> {
+    let mut depth = 0usize;
+    let mut text_end = None;
+    for (index, c) in tail.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    text_end = Some(index);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let text_end = text_end?;
+    let after = &tail[text_end + 1..];
+    let target = after.strip_prefix('(')?;
+
+    let mut depth = 1usize;
+    let mut target_end = None;
+    for (index, c) in target.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    target_end = Some(index);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let target_end = target_end?;
+    let url = target[..target_end]
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('<')
+        .trim_end_matches('>');
+    let consumed = text_end + 2 + target_end + 1;
+    Some((&tail[1..text_end], url, consumed))
+}
+
+/// 把文本中的 `[标题](链接)` 替换为标题本身，并去掉图片。
+fn strip_links(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        match inline_link(&rest[start..]) {
+            Some((inner, _, consumed)) => {
+                match rest[..start].strip_suffix('!') {
+                    Some(prefix) => result.push_str(prefix),
+                    None => {
+                        result.push_str(&rest[..start]);
+                        result.push_str(&strip_links(inner));
+                    }
+                }
+                rest = &rest[start + consumed..];
+            }
+            None => {
+                result.push_str(&rest[..=start]);
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// 是否为带有主机名的绝对 URL，相对路径和页内锚点不会被导入。
+fn is_absolute_url(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|u| u.host_str().is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{names, parse_fixture, titles};
+    use super::*;
+
+    fn urls(group: &BookmarkGroup) -> Vec<&str> {
+        group.items.iter().map(|item| item.url.as_str()).collect()
+    }
+
+    #[test]
+    fn headings_and_links() {
+        let groups = parse_fixture("links.md", parse_file);
+        // 唯一的一级标题是文档标题
+        assert_eq!(
+            names(&groups),
+            ["Awesome Self-Hosted", "Monitoring", "Media"]
+        );
+        assert_eq!(titles(&groups[0]), ["Project site"]);
+
+        let monitoring = &groups[1];
+        assert_eq!(
+            urls(monitoring),
+            [
+                "https://grafana.com/",
+                "https://prometheus.io/",
+                "https://uptime.kuma.pet/"
+            ]
+        );
+        assert_eq!(titles(monitoring)[..2], ["Grafana", "Prometheus"]);
+
+        let alerting = &monitoring.children[0];
+        assert_eq!(alerting.path, ["Monitoring", "Alerting"]);
+        assert_eq!(titles(alerting), ["Alertmanager"]);
+        assert_eq!(urls(alerting), ["https://prometheus.io/docs/alerting/"]);
+
+        // 代码块中的链接被忽略，裸链接所在行的其余文字作为标题
+        assert_eq!(titles(&groups[2]), ["Jellyfin"]);
+        assert_eq!(urls(&groups[2]), ["https://jellyfin.org/"]);
+    }
+
+    #[test]
+    fn heading_levels() {
+        assert_eq!(heading("### Alerting ###"), Some((3, "Alerting")));
+        assert_eq!(heading("#hashtag"), None);
+        assert_eq!(heading("####### too deep"), None);
+    }
+}
@@ -0,0 +1,609 @@
+//! 该模块负责解析浏览器导出的书签文件。
+//!
+//! 各种来源的书签都会被解析成统一的、由 `BookmarkGroup` 构成的文件夹树，
+//! 再根据 `BookmarkImportMode` 整理成前端导入时需要的分组结构。
+
+mod chromium;
+mod dashboard;
+mod firefox;
+mod markdown;
+mod netscape;
+mod opml;
+mod outline;
+mod plain_text;
+mod proxy;
+mod read_later_csv;
+mod read_later_html;
+mod safari;
+mod title;
+
+pub use dashboard::DashboardKind;
+pub use proxy::ProxyKind;
+
+use crate::utils::app_icons_dir_path;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, State};
+
+/// 使用完整路径作为分组名时，各级文件夹之间的分隔符。
+const PATH_SEPARATOR: &str = " / ";
+
+/// 书签导入的共享状态，用于取消正在进行的解析。
+#[derive(Default)]
+pub struct BookmarkImportState {
+    cancelled: Arc<AtomicBool>,
+}
+
+/// 通过 `bookmark-import-progress` 事件发送给前端的解析进度。
+#[derive(Serialize, Clone, Debug)]
+pub struct BookmarkImportProgress {
+    /// 已处理的字节数。
+    processed: u64,
+    /// 文件总字节数。
+    total: u64,
+}
+
+/// 代表一个单独的书签项目。
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BookmarkItem {
+    /// 书签的标题。
+    pub(crate) title: String,
+    /// 书签指向的 URL。
+    pub(crate) url: String,
+    /// 书签的添加时间（Unix 时间戳，单位秒）。
+    pub(crate) add_date: Option<i64>,
+    /// 书签的最后修改时间（Unix 时间戳，单位秒）。
+    pub(crate) last_modified: Option<i64>,
+    /// 书签内嵌的图标保存到图标目录后的文件名。
+    pub(crate) local_icon_path: Option<String>,
+    /// 尚未保存的内嵌图标 `data:` URI，在导入时才写入图标目录。
+    #[serde(default)]
+    pub(crate) icon_data_uri: Option<String>,
+    /// 书签的关键字（如 Firefox 中在地址栏输入的快捷关键字）。
+    pub(crate) keyword: Option<String>,
+    /// 书签的标签。
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+    /// 内网地址，来自区分内外网地址的自托管导航页配置。
+    pub(crate) url_lan: Option<String>,
+    /// 网站的描述。
+    pub(crate) description: Option<String>,
+    /// Iconify 图标名称（如 `mdi:server`），作为网站的默认图标。
+    pub(crate) default_icon: Option<String>,
+}
+
+/// 代表一个书签文件夹（或分组），包含一个名称、多个书签项以及子文件夹。
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BookmarkGroup {
+    /// 书签文件夹的名称。
+    pub(crate) name: String,
+    /// 从顶层分组到当前文件夹的完整路径（包含自身名称）。
+    #[serde(default)]
+    pub(crate) path: Vec<String>,
+    /// 该文件夹下直接包含的书签项。
+    pub(crate) items: Vec<BookmarkItem>,
+    /// 该文件夹下的子文件夹。
+    #[serde(default)]
+    pub(crate) children: Vec<BookmarkGroup>,
+}
+
+/// 书签导入时分组的整理方式。
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum BookmarkImportMode {
+    /// 每个顶层文件夹一个分组，子文件夹中的书签全部合并到顶层分组中。
+    #[default]
+    Flatten,
+    /// 保留完整的文件夹树，子文件夹放在 `children` 中。
+    Tree,
+    /// 每个文件夹（包括子文件夹）各自成为一个分组，分组名为文件夹名。
+    PerFolder,
+    /// 每个文件夹各自成为一个分组，分组名为完整路径，如 `Work / Infra / Grafana`。
+    JoinedPath,
+}
+
+/// 可以导入的书签文件格式。
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum BookmarkFormat {
+    /// 浏览器导出的 Netscape 书签 HTML。
+    Netscape,
+    /// Chromium 系浏览器的 `Bookmarks` JSON。
+    Chromium,
+    /// Firefox 的 `places.sqlite`。
+    Firefox,
+    /// Safari 的 `Bookmarks.plist`。
+    Safari,
+    /// RSS 阅读器等工具导出的 OPML。
+    Opml,
+    /// 以标题分节的 Markdown 链接列表。
+    Markdown,
+    /// 每行一个链接的纯文本。
+    PlainText,
+    /// Homer、Homepage、Dashy 或 Heimdall 等自托管导航页的配置。
+    Dashboard,
+    /// Caddy、nginx 或 Traefik 等反向代理的配置。
+    Proxy,
+    /// Raindrop.io、Pocket 或 Instapaper 导出的 CSV。
+    ReadLaterCsv,
+    /// Pocket 或 Instapaper 导出的 HTML。
+    ReadLaterHtml,
+}
+
+impl BookmarkItem {
+    /// 创建一个只有标题和 URL 的书签，标题会经过统一的规范化处理。
+    fn new(title: &str, url: String) -> Self {
+        BookmarkItem {
+            title: title::normalize_title(title, &url),
+            url,
+            add_date: None,
+            last_modified: None,
+            local_icon_path: None,
+            icon_data_uri: None,
+            keyword: None,
+            tags: Vec::new(),
+            url_lan: None,
+            description: None,
+            default_icon: None,
+        }
+    }
+}
+
+impl BookmarkGroup {
+    fn new(name: String, path: Vec<String>) -> Self {
+        BookmarkGroup {
+            name,
+            path,
+            items: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// 当前文件夹及其所有子文件夹中是否都没有任何书签。
+    fn is_empty(&self) -> bool {
+        self.items.is_empty() && self.children.iter().all(BookmarkGroup::is_empty)
+    }
+
+    /// 当前文件夹及其所有子文件夹中的书签总数。
+    fn bookmark_count(&self) -> usize {
+        self.items.len()
+            + self
+                .children
+                .iter()
+                .map(BookmarkGroup::bookmark_count)
+                .sum::<usize>()
+    }
+
+    /// 移除整棵子树中不包含任何书签的文件夹。
+    fn prune(&mut self) {
+        self.children.retain(|child| !child.is_empty());
+        for child in &mut self.children {
+            child.prune();
+        }
+    }
+
+    /// 将所有子文件夹中的书签按深度优先的顺序合并到当前分组中。
+    fn flatten(mut self) -> Self {
+        let children = std::mem::take(&mut self.children);
+        for child in children {
+            let child = child.flatten();
+            self.items.extend(child.items);
+        }
+        self
+    }
+
+    /// 将当前文件夹及其所有子文件夹展开为一个分组列表（先父后子）。
+    fn into_folders(mut self, folders: &mut Vec<BookmarkGroup>) {
+        let children = std::mem::take(&mut self.children);
+        if !self.items.is_empty() {
+            folders.push(self);
+        }
+        for child in children {
+            child.into_folders(folders);
+        }
+    }
+}
+
+/// 按照给定的导入方式整理解析出的文件夹树。
+fn arrange_groups(groups: Vec<BookmarkGroup>, mode: BookmarkImportMode) -> Vec<BookmarkGroup> {
+    let mut groups: Vec<BookmarkGroup> = groups
+        .into_iter()
+        .filter(|group| !group.is_empty())
+        .collect();
+    for group in &mut groups {
+        group.prune();
+    }
+
+    match mode {
+        BookmarkImportMode::Tree => groups,
+        BookmarkImportMode::Flatten => groups.into_iter().map(BookmarkGroup::flatten).collect(),
+        BookmarkImportMode::PerFolder | BookmarkImportMode::JoinedPath => {
+            let mut folders = Vec::new();
+            for group in groups {
+                group.into_folders(&mut folders);
+            }
+            if mode == BookmarkImportMode::JoinedPath {
+                for folder in &mut folders {
+                    folder.name = folder.path.join(PATH_SEPARATOR);
+                }
+            }
+            folders
+        }
+    }
+}
+
+/// 解析指定的 Netscape 书签 HTML 文件。
+///
+/// 文件以流式方式分块解析，解析过程中会向主窗口发送 `bookmark-import-progress` 事件，
+/// 并可通过 `cancel_bookmark_import` 中途取消。
+/// 书签中以 `ICON=` 内嵌的图标会被解码并保存到图标目录中。
+///
+/// # Arguments
+/// * `path` - 指向书签 HTML 文件的路径 (`PathBuf`)。
+/// * `mode` - 分组的整理方式，缺省时为 `BookmarkImportMode::Flatten`。
+///
+/// # Returns
+/// * `Ok(Vec<BookmarkGroup>)` - 如果解析成功，返回一个包含所有书签组的向量。
+/// * `Err(String)` - 如果文件读取或解析过程中发生错误，或导入被取消，返回一个描述错误的字符串。
+#[tauri::command]
+pub async fn bookmark_parser(
+    app: AppHandle,
+    state: State<'_, BookmarkImportState>,
+    path: PathBuf,
+    mode: Option<BookmarkImportMode>,
+) -> Result<Vec<BookmarkGroup>, String> {
+    let groups = parse_netscape(app, &state, path).await?;
+    Ok(arrange_groups(groups, mode.unwrap_or_default()))
+}
+
+/// 自动识别书签文件的格式并解析。
+///
+/// 除了浏览器的 Netscape HTML、Chromium `Bookmarks` JSON 和 Firefox `places.sqlite` 之外，
+/// 还支持 OPML、Markdown 链接列表、每行一个链接的纯文本，
+/// 以及 Raindrop.io、Pocket 和 Instapaper 导出的 CSV 和 HTML。
+/// OPML 中的 `<outline>` 文件夹和 Markdown 中的标题会成为分组。
+///
+/// # Arguments
+/// * `path` - 指向书签文件的路径 (`PathBuf`)。
+/// * `mode` - 分组的整理方式，缺省时为 `BookmarkImportMode::Flatten`。
+///
+/// # Returns
+/// * `Ok(Vec<BookmarkGroup>)` - 如果解析成功，返回一个包含所有书签组的向量。
+/// * `Err(String)` - 如果文件读取或解析过程中发生错误，返回一个描述错误的字符串。
+#[tauri::command]
+pub async fn auto_bookmark_parser(
+    app: AppHandle,
+    state: State<'_, BookmarkImportState>,
+    path: PathBuf,
+    mode: Option<BookmarkImportMode>,
+) -> Result<Vec<BookmarkGroup>, String> {
+    let format = detect_format(&path)?;
+    info!("识别到书签文件格式 {format:?}: {path:?}");
+
+    let groups = match format {
+        BookmarkFormat::Netscape => parse_netscape(app, &state, path).await?,
+        BookmarkFormat::Chromium => chromium::parse_file(&path)?,
+        BookmarkFormat::Firefox => firefox::parse_file(&path).await?,
+        BookmarkFormat::Safari => safari::parse_file(&path)?,
+        BookmarkFormat::Opml => opml::parse_file(&path)?,
+        BookmarkFormat::Markdown => markdown::parse_file(&path)?,
+        BookmarkFormat::PlainText => plain_text::parse_file(&path)?,
+        BookmarkFormat::Dashboard => {
+            dashboard::parse_file(&path, None, &app_icons_dir_path(&app)).await?
+        }
+        BookmarkFormat::Proxy => proxy::parse_file(&path, None)?,
+        BookmarkFormat::ReadLaterCsv => read_later_csv::parse_file(&path)?,
+        BookmarkFormat::ReadLaterHtml => read_later_html::parse_file(&path)?,
+    };
+    Ok(arrange_groups(groups, mode.unwrap_or_default()))
+}
+
+/// 在后台线程中流式解析 Netscape 书签 HTML，并向主窗口发送解析进度。
+async fn parse_netscape(
+    app: AppHandle,
+    state: &BookmarkImportState,
+    path: PathBuf,
+) -> Result<Vec<BookmarkGroup>, String> {
+    let cancelled = state.cancelled.clone();
+    cancelled.store(false, Ordering::Relaxed);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        // 只在进度百分比变化时发送事件，避免大文件导入时事件过多
+        let mut last_percent = None;
+        let on_progress = |processed: u64, total: u64| {
+            let percent = (processed * 100).checked_div(total).unwrap_or(100);
+            if last_percent == Some(percent) {
+                return;
+            }
+            last_percent = Some(percent);
+            let payload = BookmarkImportProgress { processed, total };
+            if let Err(e) = app.emit_to("main", "bookmark-import-progress", payload) {
+                warn!("发送书签导入进度失败: {e}");
+            }
+        };
+        netscape::parse_file(&path, on_progress, &cancelled)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 根据文件开头的内容和扩展名判断书签文件的格式。
+fn detect_format(path: &Path) -> Result<BookmarkFormat, String> {
+    let mut head = Vec::new();
+    File::open(path)
+        .and_then(|file| file.take(4096).read_to_end(&mut head))
+        .map_err(|e| e.to_string())?;
+
+    if head.starts_with(b"SQLite format 3\0") {
+        // Heimdall 的数据库固定命名为 `app.sqlite`
+        let is_heimdall = path.file_name().is_some_and(|name| name == "app.sqlite");
+        return Ok(if is_heimdall {
+            BookmarkFormat::Dashboard
+        } else {
+            BookmarkFormat::Firefox
+        });
+    }
+    if head.starts_with(b"bplist00") {
+        return Ok(BookmarkFormat::Safari);
+    }
+
+    let text = String::from_utf8_lossy(&head);
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    let lower = text.to_lowercase();
+    let extension = path
+        .extension()
+        .and_then(std::ffi::OsStr::to_str)
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+
+    let format = if text.starts_with('{') && lower.contains("\"roots\"") {
+        BookmarkFormat::Chromium
+    } else if lower.contains("<plist") || extension == "plist" {
+        BookmarkFormat::Safari
+    } else if lower.contains("<opml") || extension == "opml" {
+        BookmarkFormat::Opml
+    } else if lower.contains("<title>pocket export") || lower.contains("<title>instapaper") {
+        BookmarkFormat::ReadLaterHtml
+    } else if lower.contains("netscape-bookmark-file") || lower.contains("<dl") {
+        BookmarkFormat::Netscape
+    } else if extension == "csv" {
+        BookmarkFormat::ReadLaterCsv
+    } else if proxy::is_proxy_config(path, text) {
+        BookmarkFormat::Proxy
+    } else if matches!(extension.as_str(), "yml" | "yaml") {
+        BookmarkFormat::Dashboard
+    } else if matches!(extension.as_str(), "md" | "markdown")
+        || text.lines().any(|line| line.trim_start().starts_with('#')) && text.contains("](")
+    {
+        BookmarkFormat::Markdown
+    } else if matches!(extension.as_str(), "html" | "htm") {
+        BookmarkFormat::Netscape
+    } else {
+        BookmarkFormat::PlainText
+    };
+    Ok(format)
+}
+
+/// 取文件名（不含扩展名）作为默认的分组名称。
+fn file_stem_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Bookmarks".to_string())
+}
+
+/// 统计浏览器书签文件中的书签数量，支持 Chromium、Firefox 和 Safari 的书签文件。
+pub(crate) async fn count_browser_bookmarks(
+    format: BookmarkFormat,
+    path: &Path,
+) -> Result<usize, String> {
+    let groups = match format {
+        BookmarkFormat::Chromium => chromium::parse_file(path)?,
+        BookmarkFormat::Firefox => firefox::parse_file(path).await?,
+        BookmarkFormat::Safari => safari::parse_file(path)?,
+        _ => return Err(format!("不支持统计 {format:?} 格式的书签")),
+    };
+    Ok(groups.iter().map(BookmarkGroup::bookmark_count).sum())
+}
+
+/// 取消正在进行的书签文件解析，被取消的 `bookmark_parser` 会返回错误。
+#[tauri::command]
+pub fn cancel_bookmark_import(state: State<'_, BookmarkImportState>) {
+    state.cancelled.store(true, Ordering::Relaxed);
+}
+
+/// 解析 Chromium 系浏览器配置目录中的 `Bookmarks` JSON 文件。
+///
+/// 支持 Chrome、Edge、Brave、Vivaldi 等浏览器，用户无需先通过浏览器导出书签。
+///
+/// # Arguments
+/// * `path` - 指向 `Bookmarks` 文件的路径 (`PathBuf`)。
+/// * `mode` - 分组的整理方式，缺省时为 `BookmarkImportMode::Flatten`。
+#[tauri::command]
+pub async fn chromium_bookmark_parser(
+    path: PathBuf,
+    mode: Option<BookmarkImportMode>,
+) -> Result<Vec<BookmarkGroup>, String> {
+    let groups = chromium::parse_file(&path)?;
+    Ok(arrange_groups(groups, mode.unwrap_or_default()))
+}
+
+/// 解析 Firefox 配置目录中的 `places.sqlite` 书签数据库。
+///
+/// 数据库会先被复制到临时目录再以只读方式打开，因此 Firefox 正在运行时也可以导入。
+///
+/// # Arguments
+/// * `path` - 指向 `places.sqlite` 文件的路径 (`PathBuf`)。
+/// * `mode` - 分组的整理方式，缺省时为 `BookmarkImportMode::Flatten`。
+#[tauri::command]
+pub async fn firefox_bookmark_parser(
+    path: PathBuf,
+    mode: Option<BookmarkImportMode>,
+) -> Result<Vec<BookmarkGroup>, String> {
+    let groups = firefox::parse_file(&path).await?;
+    Ok(arrange_groups(groups, mode.unwrap_or_default()))
+}
+
+/// 解析 Safari 的 `Bookmarks.plist`，支持二进制和 XML 两种 plist 格式。
+///
+/// 阅读列表中的条目会单独成为一个名为“Reading List”的分组。
+/// 该文件位于 macOS 的 `~/Library/Safari/Bookmarks.plist`，复制到其他系统后同样可以导入。
+///
+/// # Arguments
+/// * `path` - 指向 `Bookmarks.plist` 文件的路径 (`PathBuf`)。
+/// * `mode` - 分组的整理方式，缺省时为 `BookmarkImportMode::Flatten`。
+#[tauri::command]
+pub async fn safari_bookmark_parser(
+    path: PathBuf,
+    mode: Option<BookmarkImportMode>,
+) -> Result<Vec<BookmarkGroup>, String> {
+    let groups = safari::parse_file(&path)?;
+    Ok(arrange_groups(groups, mode.unwrap_or_default()))
+}
+
+/// 解析自托管导航页的配置，支持 Homer、Homepage、Dashy 和 Heimdall。
+///
+/// 导航页中的分区成为分组，服务成为书签，并保留描述、图标以及单独配置的内网地址。
+///
+/// # Arguments
+/// * `path` - 指向 `config.yml`、`services.yaml`、`bookmarks.yaml`、`conf.yml` 或 `app.sqlite` 的路径。
+/// * `kind` - 导航页类型，缺省时根据文件内容自动判断。
+/// * `mode` - 分组的整理方式，缺省时为 `BookmarkImportMode::Flatten`。
+#[tauri::command]
+pub async fn dashboard_config_parser(
+    app: AppHandle,
+    path: PathBuf,
+    kind: Option<DashboardKind>,
+    mode: Option<BookmarkImportMode>,
+) -> Result<Vec<BookmarkGroup>, String> {
+    let groups = dashboard::parse_file(&path, kind, &app_icons_dir_path(&app)).await?;
+    Ok(arrange_groups(groups, mode.unwrap_or_default()))
+}
+
+/// 从反向代理配置中生成网站，支持 Caddyfile、nginx 站点配置和 Traefik 动态配置（YAML/TOML）。
+///
+/// 代理对外暴露的域名作为网站地址，转发到的上游地址作为内网地址（`url_lan`）。
+///
+/// # Arguments
+/// * `path` - 指向配置文件的路径。
+/// * `kind` - 代理类型，缺省时根据文件名和内容自动判断。
+/// * `mode` - 分组的整理方式，缺省时为 `BookmarkImportMode::Flatten`。
+#[tauri::command]
+pub async fn proxy_config_parser(
+    path: PathBuf,
+    kind: Option<ProxyKind>,
+    mode: Option<BookmarkImportMode>,
+) -> Result<Vec<BookmarkGroup>, String> {
+    let groups = proxy::parse_file(&path, kind)?;
+    Ok(arrange_groups(groups, mode.unwrap_or_default()))
+}
+
+/// 各解析器测试共用的辅助函数。
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::BookmarkGroup;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::AtomicBool;
+
+    /// `tests/fixtures/bookmarks` 中的测试文件路径。
+    pub(in crate::invokes) fn fixture_path(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/bookmarks")
+            .join(name)
+    }
+
+    /// 用给定的解析函数解析测试文件，解析失败时直接 panic。
+    pub(in crate::invokes) fn parse_fixture<T>(
+        name: &str,
+        parse: impl FnOnce(&Path) -> Result<T, String>,
+    ) -> T {
+        parse(&fixture_path(name)).unwrap()
+    }
+
+    /// 不报告进度、不可取消地解析 Netscape 书签文件。
+    pub(in crate::invokes) fn parse_netscape(path: &Path) -> Result<Vec<BookmarkGroup>, String> {
+        super::netscape::parse_file(path, |_, _| {}, &AtomicBool::new(false))
+    }
+
+    pub(in crate::invokes) fn titles(group: &BookmarkGroup) -> Vec<&str> {
+        group.items.iter().map(|item| item.title.as_str()).collect()
+    }
+
+    pub(in crate::invokes) fn names(groups: &[BookmarkGroup]) -> Vec<&str> {
+        groups.iter().map(|group| group.name.as_str()).collect()
+    }
+
+    /// 写在独立临时目录中的测试文件，drop 时删除整个目录。
+    pub(in crate::invokes) struct TempFile {
+        dir: PathBuf,
+        pub(in crate::invokes) path: PathBuf,
+    }
+
+    impl TempFile {
+        pub(in crate::invokes) fn new(file_name: &str, contents: impl AsRef<[u8]>) -> Self {
+            let dir = std::env::temp_dir().join(format!("vust-test-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let path = dir.join(file_name);
+            std::fs::write(&path, contents).unwrap();
+            TempFile { dir, path }
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::{TempFile, fixture_path};
+    use super::*;
+
+    /// 把内容写入没有扩展名的文件后判断格式。
+    fn detect(contents: impl AsRef<[u8]>) -> BookmarkFormat {
+        let file = TempFile::new("bookmarks", contents);
+        detect_format(&file.path).unwrap()
+    }
+
+    fn detect_fixture(name: &str) -> BookmarkFormat {
+        detect(std::fs::read(fixture_path(name)).unwrap())
+    }
+
+    #[test]
+    fn detects_format_without_extension() {
+        assert_eq!(detect_fixture("chrome.html"), BookmarkFormat::Netscape);
+        assert_eq!(detect_fixture("safari_xml.plist"), BookmarkFormat::Safari);
+        assert_eq!(
+            detect_fixture("safari_binary.plist"),
+            BookmarkFormat::Safari
+        );
+        assert_eq!(detect_fixture("feeds.opml"), BookmarkFormat::Opml);
+        assert_eq!(detect_fixture("pocket.html"), BookmarkFormat::ReadLaterHtml);
+        assert_eq!(detect_fixture("links.md"), BookmarkFormat::Markdown);
+        assert_eq!(detect_fixture("links.txt"), BookmarkFormat::PlainText);
+        assert_eq!(
+            detect("\u{feff}{\"roots\": {\"bookmark_bar\": {}}}"),
+            BookmarkFormat::Chromium
+        );
+        assert_eq!(detect(b"SQLite format 3\0"), BookmarkFormat::Firefox);
+        assert_eq!(
+            detect("server {\n    server_name grafana.example.com;\n}"),
+            BookmarkFormat::Proxy
+        );
+    }
+
+    #[test]
+    fn detects_heimdall_database_by_name() {
+        let file = TempFile::new("app.sqlite", b"SQLite format 3\0");
+        assert_eq!(
+            detect_format(&file.path).unwrap(),
+            BookmarkFormat::Dashboard
+        );
+    }
+}
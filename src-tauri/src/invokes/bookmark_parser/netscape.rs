@@ -0,0 +1,555 @@
+//! 解析浏览器导出的 Netscape 书签格式的 HTML 文件。
+//!
+//! 书签文件可能有几十 MB，因此这里不构建完整的 DOM，而是分块读取文件，
+//! 交给 `html5ever` 的流式分词器逐个产生标签，再用一个栈还原 `<DL>` 嵌套出的文件夹树。
+//! 书签上的 `ADD_DATE`、`LAST_MODIFIED`、`ICON`、`SHORTCUTURL` 和 `TAGS` 属性，
+//! 以及紧随书签之后的 `<DD>` 描述也会一并解析。
+//! 分词器已经解码了 `&amp;`、`&#39;` 等实体，标题中嵌套的标签也只保留其文本。
+
+use super::title::normalize_text;
+use super::{BookmarkGroup, BookmarkItem};
+use html5ever::tendril::StrTendril;
+use html5ever::tokenizer::{
+    BufferQueue, TagKind, Token, TokenSink, TokenSinkResult, Tokenizer, TokenizerOpts,
+};
+use log::debug;
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 每次从文件中读取的字节数。
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// 读取并解析 Netscape 书签 HTML 文件，返回顶层分组构成的文件夹树。
+///
+/// 直接位于“收藏夹栏”下的书签会被整理成一个以“收藏夹栏”命名的分组，
+/// 放在结果的最前面；其余每个顶层文件夹各自成为一个分组，子文件夹保存在 `children` 中。
+/// Safari 导出的文件没有标记“收藏夹栏”，此时文件中的每个顶层文件夹各自成为一个分组。
+/// 书签内嵌的图标只保留 `data:` URI，导入时才保存到图标目录，取消或跳过的书签不会留下文件。
+///
+/// # Arguments
+/// * `on_progress` - 每读取一块数据后调用，参数为已处理字节数和文件总字节数。
+/// * `cancelled` - 每读取一块数据前检查，为 `true` 时中止解析并返回错误。
+pub(super) fn parse_file(
+    path: &Path,
+    mut on_progress: impl FnMut(u64, u64),
+    cancelled: &AtomicBool,
+) -> Result<Vec<BookmarkGroup>, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let total = file.metadata().map(|m| m.len()).unwrap_or_default();
+    let mut reader = BufReader::with_capacity(CHUNK_SIZE, file);
+
+    let tokenizer = Tokenizer::new(
+        NetscapeSink {
+            state: RefCell::new(SinkState::default()),
+        },
+        TokenizerOpts::default(),
+    );
+    let queue = BufferQueue::default();
+
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut pending: Vec<u8> = Vec::new();
+    let mut processed = 0u64;
+
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err("书签导入已取消".to_string());
+        }
+
+        let read = reader.read(&mut buffer).map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        processed += read as u64;
+
+        pending.extend_from_slice(&buffer[..read]);
+        let text = take_utf8(&mut pending);
+        if !text.is_empty() {
+            queue.push_back(StrTendril::from(text));
+            let _ = tokenizer.feed(&queue);
+        }
+        on_progress(processed, total);
+    }
+
+    if !pending.is_empty() {
+        queue.push_back(StrTendril::from(
+            String::from_utf8_lossy(&pending).into_owned(),
+        ));
+        let _ = tokenizer.feed(&queue);
+    }
+    tokenizer.end();
+
+    let state = tokenizer.sink.state.take();
+    let mut groups: Vec<BookmarkGroup> = Vec::new();
+
+    // 有“收藏夹栏”时只导入其中的内容
+    if let Some(toolbar) = state.toolbar {
+        debug!("找到根目录: {:?}", toolbar.name);
+
+        // 直接位于“收藏夹栏”下的书签整理成一个特殊的组，插入到列表的最前面
+        if !toolbar.items.is_empty() {
+            let mut top_level = BookmarkGroup::new(toolbar.name.clone(), vec![toolbar.name]);
+            top_level.items = toolbar.items;
+            groups.push(top_level);
+        }
+        groups.extend(toolbar.children);
+    } else {
+        // 不属于任何文件夹的书签会被丢弃，与有“收藏夹栏”时的处理一致
+        groups.extend(state.roots.into_iter().flat_map(|root| root.children));
+    }
+    Ok(groups)
+}
+
+/// 从缓冲区头部取出所有完整的 UTF-8 字符，末尾不完整的多字节字符留在缓冲区中等待下一块数据。
+///
+/// 无效的字节序列会被替换为 `U+FFFD`。
+fn take_utf8(pending: &mut Vec<u8>) -> String {
+    let mut text = String::new();
+    loop {
+        match std::str::from_utf8(pending) {
+            Ok(valid) => {
+                text.push_str(valid);
+                pending.clear();
+                return text;
+            }
+            Err(e) => {
+                let valid_up_to = e.valid_up_to();
+                text.push_str(std::str::from_utf8(&pending[..valid_up_to]).unwrap_or_default());
+                match e.error_len() {
+                    Some(len) => {
+                        text.push(char::REPLACEMENT_CHARACTER);
+                        pending.drain(..valid_up_to + len);
+                    }
+                    None => {
+                        pending.drain(..valid_up_to);
+                        return text;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 一个正在解析中的 `<DL>` 文件夹。
+struct FolderFrame {
+    group: BookmarkGroup,
+    /// 是否为带有 `PERSONAL_TOOLBAR_FOLDER` 标记的“收藏夹栏”。
+    is_toolbar: bool,
+    /// 是否为没有 `<H3>` 标题的 `<DL>`（如文件最外层的列表）。
+    anonymous: bool,
+}
+
+/// 正在收集文本的标签。
+enum Capture {
+    /// `<H3>` 文件夹标题。
+    Folder { text: String, is_toolbar: bool },
+    /// `<A>` 书签，保存其属性。
+    ///
+    /// `</A>` 之后书签并不立即结束，而是等到下一个标签，以便收集紧随其后的 `<DD>` 描述。
+    Link {
+        text: String,
+        attrs: Vec<(String, String)>,
+        /// 是否已经读到 `</A>`，之后的文本不再属于标题。
+        closed: bool,
+        description: Option<String>,
+    },
+}
+
+/// 分词过程中的解析状态。
+#[derive(Default)]
+struct SinkState {
+    /// 从外到内依次打开的文件夹。
+    stack: Vec<FolderFrame>,
+    /// 已经读完 `<H3>`、正在等待其 `<DL>` 的文件夹。
+    pending_folder: Option<(String, bool)>,
+    capture: Option<Capture>,
+    /// 解析完成的“收藏夹栏”。
+    toolbar: Option<BookmarkGroup>,
+    /// 解析完成的最外层列表，没有“收藏夹栏”时从中取出顶层文件夹。
+    roots: Vec<BookmarkGroup>,
+}
+
+/// 接收分词器产生的标签，并把它们还原成文件夹树。
+struct NetscapeSink {
+    state: RefCell<SinkState>,
+}
+
+impl TokenSink for NetscapeSink {
+    type Handle = ();
+
+    fn process_token(&self, token: Token, _line_number: u64) -> TokenSinkResult<()> {
+        let mut state = self.state.borrow_mut();
+        match token {
+            Token::TagToken(tag) => match (tag.kind, &*tag.name) {
+                (TagKind::StartTag, "dt") => {
+                    self.finish_link(&mut state);
+                    state.flush_pending_folder();
+                }
+                (TagKind::StartTag, "h3") => {
+                    self.finish_link(&mut state);
+                    state.flush_pending_folder();
+                    let is_toolbar = tag.attrs.iter().any(|attr| {
+                        &*attr.name.local == "personal_toolbar_folder"
+                            && attr.value.eq_ignore_ascii_case("true")
+                    });
+                    state.capture = Some(Capture::Folder {
+                        text: String::new(),
+                        is_toolbar,
+                    });
+                }
+                (TagKind::EndTag, "h3") => {
+                    if let Some(Capture::Folder { text, is_toolbar }) = state.capture.take() {
+                        state.pending_folder = Some((normalize_text(&text), is_toolbar));
+                    }
+                }
+                (TagKind::StartTag, "a") => {
+                    self.finish_link(&mut state);
+                    let attrs = tag
+                        .attrs
+                        .iter()
+                        .map(|attr| (attr.name.local.to_string(), attr.value.to_string()))
+                        .collect();
+                    state.capture = Some(Capture::Link {
+                        text: String::new(),
+                        attrs,
+                        closed: false,
+                        description: None,
+                    });
+                }
+                (TagKind::EndTag, "a") => {
+                    if let Some(Capture::Link { closed, .. }) = state.capture.as_mut() {
+                        *closed = true;
+                    }
+                }
+                (TagKind::StartTag, "dd") => {
+                    // 文件夹的 `<DD>` 描述不会被导入
+                    if let Some(Capture::Link {
+                        closed,
+                        description,
+                        ..
+                    }) = state.capture.as_mut()
+                    {
+                        *closed = true;
+                        *description = Some(String::new());
+                    }
+                }
+                (TagKind::StartTag, "dl") => {
+                    self.finish_link(&mut state);
+                    state.open_folder();
+                }
+                (TagKind::EndTag, "dl") => {
+                    self.finish_link(&mut state);
+                    state.flush_pending_folder();
+                    state.close_folder();
+                }
+                _ => {}
+            },
+            Token::CharacterTokens(text) => match state.capture.as_mut() {
+                Some(Capture::Folder { text: buffer, .. }) => buffer.push_str(&text),
+                Some(Capture::Link {
+                    description: Some(buffer),
+                    ..
+                }) => buffer.push_str(&text),
+                Some(Capture::Link {
+                    text: buffer,
+                    closed: false,
+                    ..
+                }) => buffer.push_str(&text),
+                _ => {}
+            },
+            _ => {}
+        }
+        TokenSinkResult::Continue
+    }
+}
+
+impl NetscapeSink {
+    /// 结束正在收集的 `<A>` 书签及其描述，并将其加入当前文件夹。
+    fn finish_link(&self, state: &mut SinkState) {
+        if !matches!(state.capture, Some(Capture::Link { .. })) {
+            return;
+        }
+        let Some(Capture::Link {
+            text,
+            attrs,
+            description,
+            ..
+        }) = state.capture.take()
+        else {
+            return;
+        };
+        let Some(frame) = state.stack.last_mut() else {
+            return;
+        };
+        if let Some(mut item) = build_item(text, &attrs) {
+            item.description = description
+                .map(|description| description.trim().to_string())
+                .filter(|description| !description.is_empty());
+            frame.group.items.push(item);
+        }
+    }
+}
+
+impl SinkState {
+    /// 遇到 `<DL>` 时打开一个新的文件夹。
+    fn open_folder(&mut self) {
+        let parent_path = self
+            .stack
+            .last()
+            .map(|frame| frame.group.path.clone())
+            .unwrap_or_default();
+
+        let frame = match self.pending_folder.take() {
+            Some((name, true)) => FolderFrame {
+                // 顶层文件夹的路径不包含“收藏夹栏”本身
+                group: BookmarkGroup::new(name, Vec::new()),
+                is_toolbar: true,
+                anonymous: false,
+            },
+            Some((name, false)) => {
+                let mut path = parent_path;
+                path.push(name.clone());
+                FolderFrame {
+                    group: BookmarkGroup::new(name, path),
+                    is_toolbar: false,
+                    anonymous: false,
+                }
+            }
+            None => FolderFrame {
+                group: BookmarkGroup::new(String::new(), parent_path),
+                is_toolbar: false,
+                anonymous: true,
+            },
+        };
+        self.stack.push(frame);
+    }
+
+    /// 遇到 `</DL>` 时关闭当前文件夹，并将其挂到父级文件夹下。
+    fn close_folder(&mut self) {
+        let Some(frame) = self.stack.pop() else {
+            return;
+        };
+        if frame.is_toolbar {
+            if self.toolbar.is_none() {
+                self.toolbar = Some(frame.group);
+            }
+            return;
+        }
+        let Some(parent) = self.stack.last_mut() else {
+            // Safari 导出的顶层文件夹没有外层的 `<DL>`，为其补上一个匿名的根
+            let root = if frame.anonymous {
+                frame.group
+            } else {
+                let mut root = BookmarkGroup::new(String::new(), Vec::new());
+                root.children.push(frame.group);
+                root
+            };
+            self.roots.push(root);
+            return;
+        };
+        if frame.anonymous {
+            parent.group.items.extend(frame.group.items);
+            parent.group.children.extend(frame.group.children);
+        } else {
+            parent.group.children.push(frame.group);
+        }
+    }
+
+    /// 处理只有 `<H3>` 而没有 `<DL>` 的空文件夹。
+    fn flush_pending_folder(&mut self) {
+        let Some((name, _)) = self.pending_folder.take() else {
+            return;
+        };
+        if let Some(parent) = self.stack.last_mut() {
+            let mut path = parent.group.path.clone();
+            path.push(name.clone());
+            parent.group.children.push(BookmarkGroup::new(name, path));
+        }
+    }
+}
+
+/// 根据 `<A>` 的文本和属性构建书签项，没有 `HREF` 时返回 `None`。
+fn build_item(title: String, attrs: &[(String, String)]) -> Option<BookmarkItem> {
+    let attr = |name: &str| {
+        attrs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    let url = attr("href").filter(|url| !url.is_empty())?;
+
+    Some(BookmarkItem {
+        add_date: attr("add_date").and_then(parse_timestamp),
+        last_modified: attr("last_modified").and_then(parse_timestamp),
+        icon_data_uri: attr("icon")
+            .map(str::trim)
+            .filter(|icon| icon.starts_with("data:"))
+            .map(String::from),
+        keyword: attr("shortcuturl")
+            .map(str::trim)
+            .filter(|keyword| !keyword.is_empty())
+            .map(String::from),
+        tags: attr("tags")
+            .map(|tags| {
+                tags.split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ..BookmarkItem::new(&title, url.to_string())
+    })
+}
+
+/// 解析 `ADD_DATE`/`LAST_MODIFIED` 属性中的时间戳，统一换算为 Unix 秒。
+///
+/// 标准格式以秒为单位，但部分工具导出的是毫秒或微秒，这里按数量级进行换算。
+pub(super) fn parse_timestamp(value: &str) -> Option<i64> {
+    let value: i64 = value.trim().parse().ok()?;
+    match value {
+        v if v <= 0 => None,
+        v if v >= 100_000_000_000_000 => Some(v / 1_000_000),
+        v if v >= 100_000_000_000 => Some(v / 1_000),
+        v => Some(v),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{TempFile, names, parse_fixture, parse_netscape, titles};
+    use super::*;
+
+    /// 在 `<DL>` 之前用注释填充，使 `title` 的第一个字符跨越第一块数据的末尾。
+    fn html_with_title_at_chunk_boundary(title: &str) -> String {
+        let head = "<!DOCTYPE NETSCAPE-Bookmark-file-1>\n<!--";
+        let tail = "-->\n<DL><p>\n<DT><H3 PERSONAL_TOOLBAR_FOLDER=\"true\">Bar</H3>\n<DL><p>\n<DT><A HREF=\"https://example.cn/\">";
+        let padding = CHUNK_SIZE - 1 - head.len() - tail.len();
+        format!(
+            "{head}{}{tail}{title}</A>\n</DL><p>\n</DL><p>\n",
+            " ".repeat(padding)
+        )
+    }
+
+    #[test]
+    fn chrome_export() {
+        let groups = parse_fixture("chrome.html", parse_netscape);
+        assert_eq!(names(&groups), ["Bookmarks bar", "Dev & Ops"]);
+        assert_eq!(titles(&groups[0]), ["Use <div> tags", "example.org"]);
+        assert_eq!(groups[0].items[0].add_date, Some(1_700_000_001));
+        assert_eq!(
+            titles(&groups[1]),
+            ["Vec<T> in std::vec - Rust", "Grafana Dashboards"]
+        );
+        assert_eq!(groups[1].path, ["Dev & Ops"]);
+    }
+
+    #[test]
+    fn firefox_export() {
+        let groups = parse_fixture("firefox.html", parse_netscape);
+        assert_eq!(names(&groups), ["Bookmarks Toolbar", "Reading & Research"]);
+        let mdn = &groups[0].items[0];
+        assert_eq!(mdn.title, "MDN – Tom's \"favourite\" docs");
+        assert_eq!(mdn.keyword.as_deref(), Some("mdn"));
+        assert_eq!(mdn.tags, ["dev", "docs"]);
+        assert_eq!(mdn.description.as_deref(), Some("Web platform reference"));
+        assert_eq!(groups[1].items[0].description, None);
+        assert_eq!(titles(&groups[1]), ["arXiv preprints"]);
+    }
+
+    #[test]
+    fn icon_keyword_and_timestamps() {
+        let groups = parse_fixture("attributes.html", parse_netscape);
+        assert_eq!(titles(&groups[0]), ["GitHub", "crates.io"]);
+        let github = &groups[0].items[0];
+        assert_eq!(
+            github.icon_data_uri.as_deref(),
+            Some("data:image/png;base64,iVBORw0KGgo=")
+        );
+        // 图标在导入时才保存
+        assert_eq!(github.local_icon_path, None);
+        assert_eq!(github.keyword.as_deref(), Some("gh"));
+        assert_eq!(github.add_date, Some(1_700_000_302));
+        assert_eq!(github.last_modified, Some(1_700_000_303));
+
+        let crates = &groups[0].items[1];
+        assert_eq!(crates.icon_data_uri, None);
+        assert_eq!(crates.keyword, None);
+        assert_eq!(crates.add_date, Some(1_700_000_304));
+        assert_eq!(crates.last_modified, Some(1_700_000_305));
+    }
+
+    #[test]
+    fn edge_export() {
+        let groups = parse_fixture("edge.html", parse_netscape);
+        assert_eq!(names(&groups), ["Favorites bar", "Work"]);
+        // 实体只解码一次
+        assert_eq!(titles(&groups[0]), ["Tom &amp; Jerry"]);
+        let infra = &groups[1].children[0];
+        assert_eq!(infra.path, ["Work", "Infra"]);
+        assert_eq!(titles(infra), ["Grafana"]);
+    }
+
+    #[test]
+    fn safari_export_without_toolbar_marker() {
+        let groups = parse_fixture("safari.html", parse_netscape);
+        assert_eq!(
+            names(&groups),
+            ["Favourites", "Bookmarks Menu", "Reading List"]
+        );
+        assert_eq!(titles(&groups[0]), ["Apple & Co"]);
+        assert_eq!(names(&groups[0].children), ["News"]);
+        assert_eq!(groups[0].children[0].path, ["Favourites", "News"]);
+        assert_eq!(titles(&groups[2]), ["A <long> read"]);
+    }
+
+    #[test]
+    fn cjk_title_across_chunk_boundary() {
+        let html = html_with_title_at_chunk_boundary("中文书签");
+        // 第一个汉字的 3 个字节被拆到两块数据中
+        assert_eq!(html.find("中"), Some(CHUNK_SIZE - 1));
+        let file = TempFile::new("bookmarks.html", &html);
+
+        let groups = parse_netscape(&file.path).unwrap();
+        assert_eq!(titles(&groups[0]), ["中文书签"]);
+    }
+
+    #[test]
+    fn take_utf8_keeps_incomplete_tail() {
+        let mut pending = "书签".as_bytes().to_vec();
+        pending.truncate(4);
+        assert_eq!(take_utf8(&mut pending), "书");
+        assert_eq!(pending, "签".as_bytes()[..1]);
+
+        pending.extend_from_slice(&"签".as_bytes()[1..]);
+        assert_eq!(take_utf8(&mut pending), "签");
+        assert!(pending.is_empty());
+
+        pending.extend_from_slice(b"a\xffb");
+        assert_eq!(take_utf8(&mut pending), "a\u{FFFD}b");
+    }
+
+    #[test]
+    fn cancelled_mid_parse() {
+        let file = TempFile::new(
+            "bookmarks.html",
+            html_with_title_at_chunk_boundary("Example"),
+        );
+        let cancelled = AtomicBool::new(false);
+        let mut chunks = 0;
+
+        let result = parse_file(
+            &file.path,
+            |_, _| {
+                chunks += 1;
+                cancelled.store(true, Ordering::Relaxed);
+            },
+            &cancelled,
+        );
+        assert_eq!(result.unwrap_err(), "书签导入已取消");
+        assert_eq!(chunks, 1);
+    }
+}
@@ -0,0 +1,160 @@
+//! 解析 RSS 阅读器等工具导出的 OPML 文件。
+//!
+//! 带有链接的 `<outline>` 被视为书签，优先使用 `htmlUrl`（网站主页），其次是 `url` 和 `xmlUrl`；
+//! 没有链接的 `<outline>` 被视为文件夹，其子节点放在该文件夹下。
+
+use super::BookmarkItem;
+use super::outline::OutlineBuilder;
+use super::title::normalize_text;
+use super::{BookmarkGroup, file_stem_name};
+use quick_xml::Reader;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use std::fs;
+use std::path::Path;
+
+/// 读取并解析 OPML 文件，返回顶层分组构成的文件夹树。
+///
+/// 不属于任何文件夹的链接会被整理成一个分组，以 `<head><title>` 或文件名命名。
+pub(super) fn parse_file(path: &Path) -> Result<Vec<BookmarkGroup>, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut reader = Reader::from_str(&content);
+
+    let mut head_title: Option<String> = None;
+    let mut in_title = false;
+    let mut title_text = String::new();
+    // 每个尚未关闭的 `<outline>` 是否为文件夹
+    let mut open_outlines: Vec<bool> = Vec::new();
+    let mut builder: Option<OutlineBuilder> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"title" if builder.is_none() => in_title = true,
+                b"body" => {
+                    let root_name = head_title.take().unwrap_or_else(|| file_stem_name(path));
+                    builder = Some(OutlineBuilder::new(root_name));
+                }
+                b"outline" => {
+                    if let Some(builder) = builder.as_mut() {
+                        let is_folder = open_outline(builder, &e, open_outlines.len() + 1);
+                        open_outlines.push(is_folder);
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Empty(e)) => {
+                if e.local_name().as_ref() == b"outline"
+                    && let Some(builder) = builder.as_mut()
+                {
+                    let depth = open_outlines.len() + 1;
+                    if open_outline(builder, &e, depth) {
+                        builder.close_to(depth);
+                    }
+                }
+            }
+            Ok(Event::Text(e)) if in_title => {
+                let text = e.decode().map_err(|e| format!("解析 OPML 失败: {e}"))?;
+                title_text.push_str(&text);
+            }
+            // 文本中的 `&amp;` 等实体会被单独作为一个事件返回
+            Ok(Event::GeneralRef(e)) if in_title => {
+                if let Ok(Some(c)) = e.resolve_char_ref() {
+                    title_text.push(c);
+                } else if let Ok(name) = e.decode()
+                    && let Some(value) = resolve_predefined_entity(&name)
+                {
+                    title_text.push_str(value);
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"title" => {
+                    in_title = false;
+                    let title = normalize_text(&title_text);
+                    if !title.is_empty() {
+                        head_title = Some(title);
+                    }
+                }
+                b"outline" => {
+                    let depth = open_outlines.len();
+                    if open_outlines.pop() == Some(true)
+                        && let Some(builder) = builder.as_mut()
+                    {
+                        builder.close_to(depth);
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("解析 OPML 失败: {e}")),
+            _ => {}
+        }
+    }
+
+    Ok(builder.map(OutlineBuilder::finish).unwrap_or_default())
+}
+
+/// 处理一个 `<outline>` 节点：有链接时作为书签加入当前文件夹，否则打开一个新文件夹。
+///
+/// 返回该节点是否为文件夹。
+fn open_outline(builder: &mut OutlineBuilder, outline: &BytesStart, depth: usize) -> bool {
+    let mut text = String::new();
+    let mut title = String::new();
+    let mut html_url = None;
+    let mut url = None;
+    let mut xml_url = None;
+
+    for attr in outline.attributes().flatten() {
+        let Ok(value) = attr.unescape_value() else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match attr.key.local_name().as_ref() {
+            b"text" => text = value,
+            b"title" => title = value,
+            b"htmlUrl" => html_url = Some(value),
+            b"url" => url = Some(value),
+            b"xmlUrl" => xml_url = Some(value),
+            _ => {}
+        }
+    }
+
+    let name = if text.is_empty() { title } else { text };
+    match [html_url, url, xml_url]
+        .into_iter()
+        .flatten()
+        .find(|url| !url.is_empty())
+    {
+        Some(url) => {
+            builder.push_item(BookmarkItem::new(&name, url));
+            false
+        }
+        None => {
+            builder.open(depth, normalize_text(&name));
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{names, parse_fixture, titles};
+    use super::*;
+
+    #[test]
+    fn nested_outlines() {
+        let groups = parse_fixture("feeds.opml", parse_file);
+        assert_eq!(names(&groups), ["My Feeds & Sites", "Tech", "Empty"]);
+        // 优先使用网站主页而不是订阅地址
+        assert_eq!(groups[0].items[0].url, "https://blog.rust-lang.org/");
+        assert_eq!(titles(&groups[0]), ["Rust Blog"]);
+
+        let tech = &groups[1];
+        assert_eq!(titles(tech), ["Hacker News"]);
+        assert_eq!(tech.items[0].url, "https://news.ycombinator.com/rss");
+        let linux = &tech.children[0];
+        assert_eq!(linux.path, ["Tech", "Linux"]);
+        assert_eq!(linux.items[0].url, "https://lwn.net/");
+        assert!(groups[2].items.is_empty());
+    }
+}
@@ -0,0 +1,74 @@
+//! 按层级逐步构建文件夹树的辅助结构，供 OPML、Markdown 等以“标题/大纲层级”组织链接的格式使用。
+
+use super::{BookmarkGroup, BookmarkItem};
+
+/// 维护一个从根到当前文件夹的栈，每个文件夹都带有其层级（根为 0）。
+pub(super) struct OutlineBuilder {
+    stack: Vec<(usize, BookmarkGroup)>,
+}
+
+impl OutlineBuilder {
+    /// 创建构建器，`root_name` 用于收纳位于任何文件夹之外的链接。
+    pub(super) fn new(root_name: String) -> Self {
+        let root = BookmarkGroup::new(root_name.clone(), vec![root_name]);
+        OutlineBuilder {
+            stack: vec![(0, root)],
+        }
+    }
+
+    /// 关闭层级不低于 `level` 的文件夹，再在剩下的文件夹下打开一个新的子文件夹。
+    pub(super) fn open(&mut self, level: usize, name: String) {
+        self.close_to(level.max(1));
+        let path = match self.stack.last() {
+            Some((0, _)) | None => vec![name.clone()],
+            Some((_, parent)) => {
+                let mut path = parent.path.clone();
+                path.push(name.clone());
+                path
+            }
+        };
+        self.stack
+            .push((level.max(1), BookmarkGroup::new(name, path)));
+    }
+
+    /// 关闭所有层级不低于 `level` 的文件夹，并把它们挂到各自的父级下。
+    pub(super) fn close_to(&mut self, level: usize) {
+        while self.stack.len() > 1
+            && let Some((current, _)) = self.stack.last()
+            && *current >= level
+        {
+            let Some((_, folder)) = self.stack.pop() else {
+                break;
+            };
+            if let Some((_, parent)) = self.stack.last_mut() {
+                parent.children.push(folder);
+            }
+        }
+    }
+
+    /// 把书签加入当前打开的文件夹。
+    pub(super) fn push_item(&mut self, item: BookmarkItem) {
+        if let Some((_, folder)) = self.stack.last_mut() {
+            folder.items.push(item);
+        }
+    }
+
+    /// 结束构建，返回顶层分组。
+    ///
+    /// 位于任何文件夹之外的链接会被整理成一个以根名称命名的分组，放在结果的最前面。
+    pub(super) fn finish(mut self) -> Vec<BookmarkGroup> {
+        self.close_to(1);
+        let Some((_, root)) = self.stack.pop() else {
+            return Vec::new();
+        };
+
+        let mut groups: Vec<BookmarkGroup> = Vec::new();
+        if !root.items.is_empty() {
+            let mut top_level = BookmarkGroup::new(root.name, root.path);
+            top_level.items = root.items;
+            groups.push(top_level);
+        }
+        groups.extend(root.children);
+        groups
+    }
+}
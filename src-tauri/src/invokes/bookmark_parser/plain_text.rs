@@ -0,0 +1,96 @@
+//! 解析每行一个链接的纯文本文件。
+//!
+//! 每行中第一个 `http://` 或 `https://` 链接被视为书签，行内其余文字作为标题；
+//! 空行和以 `#` 开头的注释行会被忽略。
+
+use super::outline::OutlineBuilder;
+use super::{BookmarkGroup, BookmarkItem, file_stem_name};
+use std::fs;
+use std::path::Path;
+use url::Url;
+
+/// 读取并解析纯文本链接列表，所有链接放在一个以文件名命名的分组中。
+pub(super) fn parse_file(path: &Path) -> Result<Vec<BookmarkGroup>, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut builder = OutlineBuilder::new(file_stem_name(path));
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(item) = line_bookmark(line) {
+            builder.push_item(item);
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+/// 从一行文本中提取第一个裸链接作为书签，行内其余文字作为标题。
+pub(super) fn line_bookmark(line: &str) -> Option<BookmarkItem> {
+    let (start, end) = find_bare_url(line)?;
+    let url = &line[start..end];
+    // 紧跟在链接后的标点属于句子而不是标题
+    let after = line[end..].trim_start_matches(['.', ',', ';', ':', '!', '?', ')', ']']);
+    let title = format!("{} {after}", &line[..start]);
+    let title = title
+        .trim()
+        .trim_start_matches(['-', '*', '+'])
+        .trim_matches(|c: char| c.is_whitespace() || matches!(c, '-' | '|' | ':' | '—'));
+    Some(BookmarkItem::new(title, url.to_string()))
+}
+
+/// 查找行内第一个可以解析的 `http(s)://` 链接，返回其字节范围。
+///
+/// 链接末尾的标点（如句号、逗号、右括号）不属于链接本身，会被排除在外。
+fn find_bare_url(line: &str) -> Option<(usize, usize)> {
+    let mut offset = 0;
+    while let Some(found) = line[offset..].find("http") {
+        let start = offset + found;
+        let rest = &line[start..];
+        let len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '`'))
+            .unwrap_or(rest.len());
+        let candidate = rest[..len].trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']']);
+
+        if (candidate.starts_with("http://") || candidate.starts_with("https://"))
+            && Url::parse(candidate).is_ok_and(|u| u.host_str().is_some())
+        {
+            return Some((start, start + candidate.len()));
+        }
+        offset = start + "http".len();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{names, parse_fixture};
+    use super::*;
+
+    #[test]
+    fn bare_url_lines() {
+        let groups = parse_fixture("links.txt", parse_file);
+        assert_eq!(names(&groups), ["links"]);
+        let items = &groups[0].items;
+        let urls: Vec<&str> = items.iter().map(|item| item.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://www.rust-lang.org/",
+                "https://tauri.app/docs",
+                "https://example.com/path"
+            ]
+        );
+        assert_eq!(items[0].title, "Rust");
+        assert_eq!(items[1].title, "Tauri");
+    }
+
+    #[test]
+    fn trailing_punctuation_is_not_part_of_url() {
+        let item = line_bookmark("Docs (https://example.com/docs), updated daily.").unwrap();
+        assert_eq!(item.url, "https://example.com/docs");
+        assert!(line_bookmark("httpx://example.com").is_none());
+    }
+}
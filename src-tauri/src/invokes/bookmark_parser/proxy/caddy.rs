@@ -0,0 +1,168 @@
+//! 解析 Caddy 的 `Caddyfile`。
+//!
+//! 每个站点块的地址（如 `grafana.example.com`）成为一个网站，块中 `reverse_proxy` 的上游地址作为内网地址。
+//! 对于使用通配符证书的站点（`*.example.com`），通过 `@name host ...` 匹配器和
+//! `handle @name { reverse_proxy ... }` 为每个子域名各生成一个网站。
+
+use super::{Directive, ProxyRoute, parse_directives, upstream_url};
+use std::collections::HashMap;
+
+/// 把 Caddyfile 转换为站点列表。
+pub(super) fn parse_config(content: &str) -> Vec<ProxyRoute> {
+    let mut routes = Vec::new();
+
+    for site in parse_directives(content, true) {
+        let Some(body) = site.block.as_deref() else {
+            continue;
+        };
+        // 没有地址的是全局选项块，`(name)` 是代码片段
+        if site.args.is_empty() || site.name().starts_with('(') {
+            continue;
+        }
+
+        let addresses: Vec<&str> = site
+            .args
+            .iter()
+            .flat_map(|arg| arg.split(','))
+            .map(str::trim)
+            .filter(|address| !address.is_empty())
+            .collect();
+        let scheme = if addresses.iter().all(|a| a.starts_with("http://")) {
+            "http"
+        } else {
+            "https"
+        };
+
+        let matchers = host_matchers(body);
+        let mut default_upstream = None;
+        for directive in body {
+            match directive.name() {
+                "handle" | "handle_path" | "route" => {
+                    let upstream = find_upstream(directive.children());
+                    match directive.args.get(1).and_then(|name| matchers.get(name)) {
+                        Some(hosts) => {
+                            for host in hosts {
+                                routes.push(ProxyRoute {
+                                    public_url: format!("{scheme}://{host}"),
+                                    upstream: upstream.clone(),
+                                });
+                            }
+                        }
+                        None if directive.args.len() == 1 && default_upstream.is_none() => {
+                            default_upstream = upstream;
+                        }
+                        None => {}
+                    }
+                }
+                "reverse_proxy" if default_upstream.is_none() && !has_path_matcher(directive) => {
+                    default_upstream = reverse_proxy_upstream(directive);
+                }
+                _ => {}
+            }
+        }
+
+        // 只有带路径匹配器的 `reverse_proxy` 时退而使用其中第一个
+        let default_upstream = default_upstream.or_else(|| {
+            body.iter()
+                .filter(|directive| directive.name() == "reverse_proxy")
+                .find_map(reverse_proxy_upstream)
+        });
+        for address in addresses {
+            if let Some(public_url) = site_url(address) {
+                routes.push(ProxyRoute {
+                    public_url,
+                    upstream: default_upstream.clone(),
+                });
+            }
+        }
+    }
+    routes
+}
+
+/// 收集站点块中 `@name host a b` 或 `@name { host a b }` 形式的主机名匹配器。
+fn host_matchers(body: &[Directive]) -> HashMap<String, Vec<String>> {
+    let mut matchers = HashMap::new();
+    for directive in body.iter().filter(|d| d.name().starts_with('@')) {
+        let hosts: Vec<String> = if directive.args.get(1).map(String::as_str) == Some("host") {
+            directive.args[2..].to_vec()
+        } else {
+            directive
+                .children()
+                .iter()
+                .filter(|child| child.name() == "host")
+                .flat_map(|child| child.args[1..].iter().cloned())
+                .collect()
+        };
+        if !hosts.is_empty() {
+            matchers.insert(directive.name().to_string(), hosts);
+        }
+    }
+    matchers
+}
+
+/// 在指令块中递归查找第一个 `reverse_proxy` 的上游地址，优先使用没有路径匹配器的那一个。
+fn find_upstream(directives: &[Directive]) -> Option<String> {
+    let mut fallback = None;
+    for directive in directives {
+        if directive.name() == "reverse_proxy" {
+            let upstream = reverse_proxy_upstream(directive);
+            if !has_path_matcher(directive) && upstream.is_some() {
+                return upstream;
+            }
+            fallback = fallback.or(upstream);
+        } else if let Some(upstream) = find_upstream(directive.children()) {
+            return Some(upstream);
+        }
+    }
+    fallback
+}
+
+/// 判断指令的第一个参数是否为路径或命名匹配器，如 `reverse_proxy /api/* ...`。
+fn has_path_matcher(directive: &Directive) -> bool {
+    directive
+        .args
+        .get(1)
+        .is_some_and(|arg| arg.starts_with(['/', '@', '*']))
+}
+
+/// 读取 `reverse_proxy [matcher] upstream...` 或块中 `to upstream...` 的第一个上游地址。
+fn reverse_proxy_upstream(directive: &Directive) -> Option<String> {
+    let inline = directive.args[1..]
+        .iter()
+        .filter(|arg| !arg.starts_with(['/', '@', '*']));
+    let from_block = directive
+        .children()
+        .iter()
+        .filter(|child| child.name() == "to")
+        .flat_map(|child| child.args[1..].iter());
+    inline.chain(from_block).find_map(|arg| upstream_url(arg))
+}
+
+/// 把站点地址转换为访问地址；没有主机名（`:8080`）或带通配符的地址返回 `None`。
+///
+/// 没有指定协议时 Caddy 会自动启用 HTTPS，只有显式的 `http://` 或 80 端口才使用 HTTP。
+fn site_url(address: &str) -> Option<String> {
+    let (scheme, rest) = match address.split_once("://") {
+        Some((scheme, rest)) => (scheme.to_string(), rest),
+        None => (String::new(), address),
+    };
+    let authority = rest.split('/').next().unwrap_or_default();
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => (host, Some(port)),
+        _ => (authority, None),
+    };
+    if host.is_empty() || host.contains('*') || host.contains('{') {
+        return None;
+    }
+
+    let scheme = match (scheme.as_str(), port) {
+        ("", Some("80")) => "http",
+        ("", _) => "https",
+        (scheme, _) => scheme,
+    };
+    let port = match (scheme, port) {
+        ("http", Some("80")) | ("https", Some("443")) | (_, None) => String::new(),
+        (_, Some(port)) => format!(":{port}"),
+    };
+    Some(format!("{scheme}://{host}{port}"))
+}
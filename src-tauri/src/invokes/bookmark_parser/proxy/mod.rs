@@ -0,0 +1,357 @@
+//! 从反向代理（Caddy、nginx、Traefik）的配置中生成启动台网站。
+//!
+//! 代理对外暴露的域名作为网站的 `url`，转发到的上游地址作为 `url_lan`，
+//! 每种代理的结果放在一个以代理名称命名的分组中，之后可以和书签一样预览并导入。
+
+mod caddy;
+mod nginx;
+mod traefik;
+
+use super::{BookmarkGroup, BookmarkItem};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use url::Url;
+
+/// 支持导入的反向代理类型。
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ProxyKind {
+    /// Caddy 的 `Caddyfile`。
+    Caddy,
+    /// nginx 的站点配置。
+    Nginx,
+    /// Traefik 的动态配置（YAML 或 TOML）。
+    Traefik,
+}
+
+impl ProxyKind {
+    /// 作为分组名称的显示名称。
+    fn display_name(self) -> &'static str {
+        match self {
+            ProxyKind::Caddy => "Caddy",
+            ProxyKind::Nginx => "nginx",
+            ProxyKind::Traefik => "Traefik",
+        }
+    }
+}
+
+/// 从配置中识别出的一个被代理的站点。
+#[derive(Debug)]
+struct ProxyRoute {
+    /// 对外访问地址。
+    public_url: String,
+    /// 上游地址。
+    upstream: Option<String>,
+}
+
+/// 读取并解析反向代理配置，返回只包含一个分组的列表。
+///
+/// 未指定 `kind` 时根据文件名和内容自动判断。
+pub(super) fn parse_file(
+    path: &Path,
+    kind: Option<ProxyKind>,
+) -> Result<Vec<BookmarkGroup>, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let kind = kind.unwrap_or_else(|| detect_kind(path, &content));
+
+    let routes = match kind {
+        ProxyKind::Caddy => caddy::parse_config(&content),
+        ProxyKind::Nginx => nginx::parse_config(&content),
+        ProxyKind::Traefik => traefik::parse_config(path, &content)?,
+    };
+
+    let name = kind.display_name().to_string();
+    let mut group = BookmarkGroup::new(name.clone(), vec![name]);
+    let mut seen = HashSet::new();
+    for route in routes {
+        if !seen.insert(route.public_url.clone()) {
+            continue;
+        }
+        let Ok(url) = Url::parse(&route.public_url) else {
+            continue;
+        };
+        let title = url.host_str().map(host_title).unwrap_or_default();
+        group.items.push(BookmarkItem {
+            url_lan: route.upstream,
+            ..BookmarkItem::new(&title, route.public_url)
+        });
+    }
+    Ok(vec![group])
+}
+
+/// 根据文件名和内容判断代理类型。
+fn detect_kind(path: &Path, content: &str) -> ProxyKind {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if file_name.starts_with("caddyfile") || file_name.ends_with(".caddy") {
+        ProxyKind::Caddy
+    } else if file_name.ends_with(".toml")
+        || file_name.ends_with(".yml")
+        || file_name.ends_with(".yaml")
+    {
+        ProxyKind::Traefik
+    } else if content.contains("server_name") || content.contains("proxy_pass") {
+        ProxyKind::Nginx
+    } else {
+        ProxyKind::Caddy
+    }
+}
+
+/// 判断文件是否像反向代理配置，供自动识别格式时使用。
+///
+/// Caddyfile 和 nginx 配置依据文件名与指令判断；YAML/TOML 只有包含 Traefik 的
+/// `routers` 时才视为代理配置，以免和导航页的 YAML 配置混淆。
+pub(super) fn is_proxy_config(path: &Path, head: &str) -> bool {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if file_name.starts_with("caddyfile") || file_name.ends_with(".caddy") {
+        return true;
+    }
+    if file_name.ends_with(".toml") || file_name.ends_with(".yml") || file_name.ends_with(".yaml") {
+        return head.contains("routers");
+    }
+    head.contains("server_name") || head.contains("proxy_pass") || head.contains("reverse_proxy")
+}
+
+/// 以主机名的第一段作为标题，如 `grafana.example.com` 的标题为 `grafana`；
+/// 二级域名（`example.com`）和 IP 地址保持原样。
+fn host_title(host: &str) -> String {
+    let labels: Vec<&str> = host.split('.').collect();
+    if labels.len() > 2 && host.parse::<std::net::IpAddr>().is_err() {
+        labels[0].to_string()
+    } else {
+        host.to_string()
+    }
+}
+
+/// 把上游地址规范化为完整的 URL，如 `10.0.0.2:8080` 转为 `http://10.0.0.2:8080`。
+///
+/// 包含变量或占位符、指向 Unix 套接字的地址无法在浏览器中访问，返回 `None`。
+fn upstream_url(upstream: &str) -> Option<String> {
+    let upstream = upstream.trim().trim_end_matches(';');
+    if upstream.is_empty() || upstream.contains(['$', '{']) || upstream.starts_with("unix") {
+        return None;
+    }
+    let url = if upstream.contains("://") {
+        upstream.to_string()
+    } else {
+        format!("http://{upstream}")
+    };
+    let parsed = Url::parse(&url).ok()?;
+    matches!(parsed.scheme(), "http" | "https").then_some(url)
+}
+
+/// 配置文件中的一条指令及其可选的块。
+#[derive(Debug, Default)]
+struct Directive {
+    /// 指令名及其参数。
+    args: Vec<String>,
+    /// `{ ... }` 中的子指令。
+    block: Option<Vec<Directive>>,
+}
+
+impl Directive {
+    /// 指令名称。
+    fn name(&self) -> &str {
+        self.args.first().map(String::as_str).unwrap_or_default()
+    }
+
+    /// 指令块中的子指令，没有块时为空。
+    fn children(&self) -> &[Directive] {
+        self.block.as_deref().unwrap_or_default()
+    }
+}
+
+/// 把使用 `{ }` 分块、`#` 注释的配置文本解析为指令树。
+///
+/// Caddyfile 以换行结束一条指令，且只有单独的 `{`、`}` 才表示块（`{host}` 之类是占位符）；
+/// nginx 以 `;` 结束一条指令，`{`、`}` 总是表示块。
+fn parse_directives(content: &str, newline_terminates: bool) -> Vec<Directive> {
+    let mut stack: Vec<Vec<Directive>> = vec![Vec::new()];
+    let mut pending: Vec<Directive> = Vec::new();
+    let mut args: Vec<String> = Vec::new();
+
+    let flush = |args: &mut Vec<String>, stack: &mut Vec<Vec<Directive>>| {
+        if !args.is_empty()
+            && let Some(current) = stack.last_mut()
+        {
+            current.push(Directive {
+                args: std::mem::take(args),
+                block: None,
+            });
+        }
+    };
+
+    for token in tokenize(content, newline_terminates) {
+        match token {
+            Token::Word(word) => args.push(word),
+            Token::End => flush(&mut args, &mut stack),
+            Token::Open => {
+                pending.push(Directive {
+                    args: std::mem::take(&mut args),
+                    block: None,
+                });
+                stack.push(Vec::new());
+            }
+            Token::Close => {
+                flush(&mut args, &mut stack);
+                if stack.len() > 1
+                    && let (Some(children), Some(mut directive)) = (stack.pop(), pending.pop())
+                {
+                    directive.block = Some(children);
+                    if let Some(parent) = stack.last_mut() {
+                        parent.push(directive);
+                    }
+                }
+            }
+        }
+    }
+    flush(&mut args, &mut stack);
+    stack.into_iter().next().unwrap_or_default()
+}
+
+/// 配置文本中的词法单元。
+enum Token {
+    Word(String),
+    /// 指令结束（`;` 或换行）。
+    End,
+    Open,
+    Close,
+}
+
+/// 把配置文本切分为词法单元，支持单双引号和 `#` 注释。
+fn tokenize(content: &str, newline_terminates: bool) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    for line in content.lines() {
+        let mut word = String::new();
+        let mut quote: Option<char> = None;
+        let mut chars = line.chars().peekable();
+
+        let push_word = |word: &mut String, tokens: &mut Vec<Token>| {
+            if word.is_empty() {
+                return;
+            }
+            let word = std::mem::take(word);
+            // Caddyfile 中只有单独的 `{`、`}` 才表示块
+            match word.as_str() {
+                "{" if newline_terminates => tokens.push(Token::Open),
+                "}" if newline_terminates => tokens.push(Token::Close),
+                _ => tokens.push(Token::Word(word)),
+            }
+        };
+
+        while let Some(c) = chars.next() {
+            if let Some(q) = quote {
+                if c == q {
+                    quote = None;
+                } else if c == '\\'
+                    && let Some(next) = chars.next()
+                {
+                    word.push(next);
+                } else {
+                    word.push(c);
+                }
+                continue;
+            }
+            match c {
+                '"' | '\'' | '`' => quote = Some(c),
+                '#' if word.is_empty() => break,
+                c if c.is_whitespace() => push_word(&mut word, &mut tokens),
+                ';' if !newline_terminates => {
+                    push_word(&mut word, &mut tokens);
+                    tokens.push(Token::End);
+                }
+                '{' if !newline_terminates => {
+                    push_word(&mut word, &mut tokens);
+                    tokens.push(Token::Open);
+                }
+                '}' if !newline_terminates => {
+                    push_word(&mut word, &mut tokens);
+                    tokens.push(Token::Close);
+                }
+                _ => word.push(c),
+            }
+        }
+        push_word(&mut word, &mut tokens);
+        if newline_terminates {
+            tokens.push(Token::End);
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{fixture_path, names, titles};
+    use super::*;
+
+    /// 自动判断类型并解析测试配置，返回各网站的访问地址和内网地址。
+    fn parse_fixture(name: &str) -> (Vec<BookmarkGroup>, Vec<(String, Option<String>)>) {
+        let groups = parse_file(&fixture_path(name), None).unwrap();
+        let routes = groups[0]
+            .items
+            .iter()
+            .map(|item| (item.url.clone(), item.url_lan.clone()))
+            .collect();
+        (groups, routes)
+    }
+
+    fn route(url: &str, url_lan: &str) -> (String, Option<String>) {
+        (url.to_string(), Some(url_lan.to_string()))
+    }
+
+    #[test]
+    fn caddyfile() {
+        let (groups, routes) = parse_fixture("Caddyfile");
+        assert_eq!(names(&groups), ["Caddy"]);
+        assert_eq!(
+            routes,
+            [
+                route("https://grafana.example.com", "http://10.0.0.5:3000"),
+                route("http://wiki.lan", "http://10.0.0.6:8080"),
+                route("https://wiki.example.com:8443", "http://10.0.0.6:8080"),
+                route("https://jellyfin.home.example.com", "http://10.0.0.7:8096"),
+                route("https://nas.home.example.com", "https://10.0.0.8:5001"),
+            ]
+        );
+        assert_eq!(
+            titles(&groups[0]),
+            ["grafana", "wiki.lan", "wiki", "jellyfin", "nas"]
+        );
+    }
+
+    #[test]
+    fn nginx_config() {
+        let (groups, routes) = parse_fixture("nginx.conf");
+        assert_eq!(names(&groups), ["nginx"]);
+        assert_eq!(
+            routes,
+            [
+                route("https://grafana.example.com", "http://10.0.0.5:3000"),
+                route("https://www.grafana.example.com", "http://10.0.0.5:3000"),
+                route("http://wiki.lan:8080", "http://10.0.0.6:8080/"),
+            ]
+        );
+    }
+
+    #[test]
+    fn traefik_config() {
+        let (groups, routes) = parse_fixture("traefik.yml");
+        assert_eq!(names(&groups), ["Traefik"]);
+        assert_eq!(
+            routes,
+            [
+                route("https://grafana.example.com", "http://10.0.0.5:3000"),
+                route("http://wiki.lan", "http://10.0.0.6:8080/"),
+                route("http://wiki.home", "http://10.0.0.6:8080/"),
+            ]
+        );
+        assert_eq!(titles(&groups[0]), ["grafana", "wiki.lan", "wiki.home"]);
+    }
+}
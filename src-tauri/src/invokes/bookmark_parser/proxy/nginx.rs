@@ -0,0 +1,129 @@
+//! 解析 nginx 的站点配置。
+//!
+//! 每个 `server` 块中的 `server_name` 成为网站，`location` 中 `proxy_pass` 的上游地址作为内网地址；
+//! 上游指向 `upstream` 块时取其中的第一个 `server`。是否使用 HTTPS 及端口取自 `listen` 指令。
+
+use super::{Directive, ProxyRoute, parse_directives, upstream_url};
+use std::collections::HashMap;
+
+/// 把 nginx 配置转换为站点列表。
+pub(super) fn parse_config(content: &str) -> Vec<ProxyRoute> {
+    let directives = parse_directives(content, false);
+
+    let mut upstreams: HashMap<String, String> = HashMap::new();
+    let mut servers: Vec<&Directive> = Vec::new();
+    collect(&directives, &mut upstreams, &mut servers);
+
+    let mut routes = Vec::new();
+    for server in servers {
+        let (scheme, port) = listen_scheme(server.children());
+        let upstream = find_proxy_pass(server.children()).and_then(|target| {
+            // `proxy_pass http://backend;` 引用的是 `upstream backend { ... }`
+            let name = target
+                .split_once("://")
+                .map_or(target.as_str(), |(_, rest)| rest);
+            let name = name.split('/').next().unwrap_or_default();
+            match upstreams.get(name) {
+                Some(address) => upstream_url(&format!("{}://{address}", scheme_of(&target))),
+                None => upstream_url(&target),
+            }
+        });
+
+        let names = server
+            .children()
+            .iter()
+            .filter(|d| d.name() == "server_name")
+            .flat_map(|d| d.args[1..].iter());
+        for name in names {
+            // `_` 是默认站点，`~` 开头的是正则，带通配符的无法直接访问
+            if name == "_" || name.starts_with('~') || name.contains('*') || name.is_empty() {
+                continue;
+            }
+            routes.push(ProxyRoute {
+                public_url: format!("{scheme}://{name}{port}"),
+                upstream: upstream.clone(),
+            });
+        }
+    }
+    routes
+}
+
+/// 递归收集所有 `upstream` 块（名称到第一个服务器地址）和 `server` 块。
+fn collect<'a>(
+    directives: &'a [Directive],
+    upstreams: &mut HashMap<String, String>,
+    servers: &mut Vec<&'a Directive>,
+) {
+    for directive in directives {
+        match directive.name() {
+            "server" if directive.block.is_some() => servers.push(directive),
+            "upstream" => {
+                if let Some(name) = directive.args.get(1)
+                    && let Some(address) = directive
+                        .children()
+                        .iter()
+                        .find(|child| child.name() == "server")
+                        .and_then(|child| child.args.get(1))
+                {
+                    upstreams.insert(name.clone(), address.clone());
+                }
+            }
+            _ => collect(directive.children(), upstreams, servers),
+        }
+    }
+}
+
+/// 根据 `listen` 指令判断站点的协议和需要在 URL 中保留的端口。
+fn listen_scheme(directives: &[Directive]) -> (&'static str, String) {
+    let listens: Vec<&Directive> = directives.iter().filter(|d| d.name() == "listen").collect();
+    let https = listens.iter().find(|d| {
+        d.args[1..]
+            .iter()
+            .any(|arg| arg == "ssl" || arg == "quic" || arg.ends_with("443"))
+    });
+    let (scheme, listen) = match https {
+        Some(listen) => ("https", Some(*listen)),
+        None => ("http", listens.first().copied()),
+    };
+
+    let port = listen
+        .and_then(|d| d.args.get(1))
+        .map(|address| address.rsplit(':').next().unwrap_or(address))
+        .filter(|port| port.chars().all(|c| c.is_ascii_digit()))
+        .filter(|port| !matches!((scheme, *port), ("http", "80") | ("https", "443")))
+        .map(|port| format!(":{port}"))
+        .unwrap_or_default();
+    (scheme, port)
+}
+
+/// 查找 `proxy_pass` 的目标，优先使用 `location /` 中的那一个。
+fn find_proxy_pass(directives: &[Directive]) -> Option<String> {
+    let proxy_pass = |directives: &[Directive]| {
+        directives
+            .iter()
+            .find(|d| d.name() == "proxy_pass")
+            .and_then(|d| d.args.get(1).cloned())
+    };
+
+    let locations: Vec<&Directive> = directives
+        .iter()
+        .filter(|d| d.name() == "location")
+        .collect();
+    locations
+        .iter()
+        .find(|location| location.args.get(1).map(String::as_str) == Some("/"))
+        .and_then(|location| proxy_pass(location.children()))
+        .or_else(|| proxy_pass(directives))
+        .or_else(|| {
+            locations
+                .iter()
+                .find_map(|location| proxy_pass(location.children()))
+        })
+}
+
+/// 取 `proxy_pass` 目标的协议，缺省为 `http`。
+fn scheme_of(target: &str) -> &str {
+    target
+        .split_once("://")
+        .map_or("http", |(scheme, _)| scheme)
+}
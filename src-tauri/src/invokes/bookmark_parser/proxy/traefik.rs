@@ -0,0 +1,123 @@
+//! 解析 Traefik 文件提供者（file provider）的动态配置，支持 YAML 和 TOML。
+//!
+//! `http.routers` 中每个路由规则里的 `Host(...)` 成为网站，
+//! 路由引用的 `http.services` 中负载均衡的第一个服务器地址作为内网地址。
+
+use super::{ProxyRoute, upstream_url};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// 动态配置中与 HTTP 路由相关的部分。
+#[derive(Deserialize, Default, Debug)]
+struct DynamicConfig {
+    #[serde(default)]
+    http: HttpConfig,
+}
+
+#[derive(Deserialize, Default, Debug)]
+struct HttpConfig {
+    #[serde(default)]
+    routers: BTreeMap<String, Router>,
+    #[serde(default)]
+    services: BTreeMap<String, Service>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Router {
+    #[serde(default)]
+    rule: String,
+    service: Option<String>,
+    #[serde(rename = "entryPoints", default)]
+    entry_points: Vec<String>,
+    tls: Option<serde::de::IgnoredAny>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Service {
+    #[serde(rename = "loadBalancer")]
+    load_balancer: Option<LoadBalancer>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LoadBalancer {
+    #[serde(default)]
+    servers: Vec<Server>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Server {
+    url: Option<String>,
+}
+
+/// 把 Traefik 动态配置转换为站点列表，扩展名为 `.toml` 时按 TOML 解析，否则按 YAML 解析。
+pub(super) fn parse_config(path: &Path, content: &str) -> Result<Vec<ProxyRoute>, String> {
+    let is_toml = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("toml"));
+    let config: DynamicConfig = if is_toml {
+        toml::from_str(content).map_err(|e| format!("解析 Traefik TOML 配置失败: {e}"))?
+    } else {
+        serde_yaml::from_str::<Option<DynamicConfig>>(content)
+            .map_err(|e| format!("解析 Traefik YAML 配置失败: {e}"))?
+            .unwrap_or_default()
+    };
+
+    let mut routes = Vec::new();
+    for router in config.http.routers.values() {
+        let upstream = router
+            .service
+            .as_deref()
+            // 引用其他提供者的服务时带有 `@file` 之类的后缀
+            .map(|service| service.split('@').next().unwrap_or(service))
+            .and_then(|service| config.http.services.get(service))
+            .and_then(|service| service.load_balancer.as_ref())
+            .and_then(|balancer| balancer.servers.iter().find_map(|s| s.url.as_deref()))
+            .and_then(upstream_url);
+
+        // 只在 `web` 之类的明文入口上监听且没有启用 TLS 时使用 HTTP
+        let plain_http = router.tls.is_none()
+            && !router.entry_points.is_empty()
+            && router
+                .entry_points
+                .iter()
+                .all(|entry| matches!(entry.as_str(), "web" | "http"));
+        let scheme = if plain_http { "http" } else { "https" };
+
+        for host in rule_hosts(&router.rule) {
+            routes.push(ProxyRoute {
+                public_url: format!("{scheme}://{host}"),
+                upstream: upstream.clone(),
+            });
+        }
+    }
+    Ok(routes)
+}
+
+/// 提取路由规则中所有 `Host(...)` 的主机名，如 ``Host(`a.com`) || Host(`b.com`)``。
+///
+/// `HostRegexp` 等无法直接访问的规则会被忽略。
+fn rule_hosts(rule: &str) -> Vec<String> {
+    let mut hosts = Vec::new();
+    let mut rest = rule;
+    while let Some(start) = rest.find("Host(") {
+        let preceded_by_ident = rest[..start]
+            .chars()
+            .last()
+            .is_some_and(|c| c.is_ascii_alphanumeric());
+        let after = &rest[start + "Host(".len()..];
+        let Some(end) = after.find(')') else {
+            break;
+        };
+        if !preceded_by_ident {
+            hosts.extend(
+                after[..end]
+                    .split(',')
+                    .map(|host| host.trim().trim_matches(['`', '"', '\'']).to_string())
+                    .filter(|host| !host.is_empty()),
+            );
+        }
+        rest = &after[end..];
+    }
+    hosts
+}
@@ -0,0 +1,234 @@
+//! 解析 Raindrop.io、Pocket 和 Instapaper 等稍后读服务导出的 CSV。
+//!
+//! 各家的列名不尽相同，这里按表头识别各列：
+//! - Raindrop.io：`title,note,excerpt,url,folder,tags,created,...`，`folder` 为收藏集，
+//!   嵌套的收藏集以 `/` 分隔；
+//! - Pocket：`title,url,time_added,tags,status`，标签以 `|` 分隔，`status` 为“未读/已归档”；
+//! - Instapaper：`URL,Title,Selection,Folder,Timestamp,Tags`，标签为 JSON 数组。
+//!
+//! 收藏集（文件夹）成为分组，不属于任何收藏集的链接放在以文件名命名的分组中。
+
+use super::netscape::parse_timestamp;
+use super::title::normalize_text;
+use super::{BookmarkGroup, BookmarkItem, file_stem_name};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use std::path::Path;
+
+/// 各列可能使用的列名，按优先级排列。
+const URL_COLUMNS: &[&str] = &["url", "link", "href"];
+const TITLE_COLUMNS: &[&str] = &["title", "name"];
+const FOLDER_COLUMNS: &[&str] = &["folder", "collection", "status"];
+const TAG_COLUMNS: &[&str] = &["tags", "tag"];
+const NOTE_COLUMNS: &[&str] = &["note", "selection", "excerpt", "description"];
+const CREATED_COLUMNS: &[&str] = &["created", "time_added", "timestamp", "date_added"];
+
+/// 读取并解析 CSV 导出文件，返回以收藏集划分的文件夹树。
+pub(super) fn parse_file(path: &Path) -> Result<Vec<BookmarkGroup>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .map_err(|e| e.to_string())?;
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("读取 CSV 表头失败: {e}"))?
+        .iter()
+        .map(|header| header.trim_start_matches('\u{feff}').to_lowercase())
+        .collect();
+    let column = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| headers.iter().position(|header| header == name))
+    };
+    let Some(url_column) = column(URL_COLUMNS) else {
+        return Err("CSV 中没有 URL 列".to_string());
+    };
+    let title_column = column(TITLE_COLUMNS);
+    let folder_column = column(FOLDER_COLUMNS);
+    let tag_column = column(TAG_COLUMNS);
+    // Raindrop.io 同时有 `note`（用户笔记）和 `excerpt`（网页摘要），优先使用笔记
+    let note_columns: Vec<usize> = NOTE_COLUMNS
+        .iter()
+        .filter_map(|name| headers.iter().position(|header| header == name))
+        .collect();
+    let created_column = column(CREATED_COLUMNS);
+
+    let root_name = file_stem_name(path);
+    let mut root = BookmarkGroup::new(root_name.clone(), vec![root_name]);
+
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("读取 CSV 记录失败: {e}"))?;
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|index| record.get(index))
+                .filter(|value| !value.is_empty())
+        };
+
+        let Some(url) = field(Some(url_column)) else {
+            continue;
+        };
+        let item = BookmarkItem {
+            add_date: field(created_column).and_then(parse_date),
+            tags: field(tag_column).map(parse_tags).unwrap_or_default(),
+            description: note_columns
+                .iter()
+                .find_map(|&index| field(Some(index)))
+                .map(String::from),
+            ..BookmarkItem::new(field(title_column).unwrap_or_default(), url.to_string())
+        };
+
+        let folders: Vec<String> = field(folder_column)
+            .map(|folder| {
+                folder
+                    .split('/')
+                    .map(normalize_text)
+                    .filter(|name| !name.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        folder_mut(&mut root, &folders).items.push(item);
+    }
+
+    let mut groups: Vec<BookmarkGroup> = Vec::new();
+    if !root.items.is_empty() {
+        let mut top_level = BookmarkGroup::new(root.name, root.path);
+        top_level.items = root.items;
+        groups.push(top_level);
+    }
+    groups.extend(root.children);
+    Ok(groups)
+}
+
+/// 按路径查找文件夹，不存在的文件夹会被依次创建。
+///
+/// 顶层文件夹的路径不包含根分组本身。
+fn folder_mut<'a>(root: &'a mut BookmarkGroup, folders: &[String]) -> &'a mut BookmarkGroup {
+    let mut current = root;
+    let mut path: Vec<String> = Vec::new();
+    for name in folders {
+        path.push(name.clone());
+        let index = match current
+            .children
+            .iter()
+            .position(|child| &child.name == name)
+        {
+            Some(index) => index,
+            None => {
+                current
+                    .children
+                    .push(BookmarkGroup::new(name.clone(), path.clone()));
+                current.children.len() - 1
+            }
+        };
+        current = &mut current.children[index];
+    }
+    current
+}
+
+/// 拆分标签列，支持 `a, b`、`a|b` 和 Instapaper 的 `["a","b"]` 三种写法。
+fn parse_tags(value: &str) -> Vec<String> {
+    value
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split([',', '|'])
+        .map(|tag| tag.trim().trim_matches('"').trim())
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// 解析创建时间，支持 Unix 时间戳和 Raindrop.io 使用的 ISO 8601 格式，统一换算为 Unix 秒。
+fn parse_date(value: &str) -> Option<i64> {
+    if value.bytes().all(|b| b.is_ascii_digit()) {
+        parse_timestamp(value)
+    } else {
+        parse_iso8601(value)
+    }
+}
+
+/// 解析 `2024-03-01T08:30:00.000Z`、`2024-03-01 08:30:00+08:00` 或 `2024-03-01` 形式的时间。
+///
+/// 没有时区时按 UTC 处理。
+fn parse_iso8601(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let seconds = match DateTime::parse_from_rfc3339(value) {
+        Ok(time) => time.timestamp(),
+        Err(_) => NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+            .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f"))
+            .or_else(|_| {
+                NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map(|date| date.and_time(NaiveTime::MIN))
+            })
+            .ok()?
+            .and_utc()
+            .timestamp(),
+    };
+    (seconds > 0).then_some(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{names, parse_fixture, titles};
+    use super::*;
+
+    #[test]
+    fn raindrop_export() {
+        let groups = parse_fixture("raindrop.csv", parse_file);
+        assert_eq!(names(&groups), ["raindrop", "Dev"]);
+        assert_eq!(titles(&groups[0]), ["Unsorted link"]);
+        assert_eq!(titles(&groups[1]), ["Tauri"]);
+        let rust = &groups[1].children[0];
+        assert_eq!(rust.path, ["Dev", "Rust"]);
+
+        let book = &rust.items[0];
+        assert_eq!(book.url, "https://doc.rust-lang.org/book/");
+        assert_eq!(book.description.as_deref(), Some("My notes"));
+        assert_eq!(book.tags, ["rust", "books"]);
+        assert_eq!(book.add_date, Some(1_709_281_800));
+        // 笔记为空时使用网页摘要
+        assert_eq!(
+            groups[1].items[0].description.as_deref(),
+            Some("Build apps")
+        );
+        assert_eq!(groups[1].items[0].add_date, Some(1_709_281_800));
+        assert_eq!(groups[0].items[0].add_date, Some(1_709_251_200));
+    }
+
+    #[test]
+    fn pocket_export() {
+        let groups = parse_fixture("pocket.csv", parse_file);
+        assert_eq!(names(&groups), ["unread", "archive"]);
+        let article = &groups[0].items[0];
+        assert_eq!(article.title, "Example Article");
+        assert_eq!(article.tags, ["news", "tech"]);
+        assert_eq!(article.add_date, Some(1_700_000_400));
+        assert_eq!(titles(&groups[1]), ["Archived Post"]);
+    }
+
+    #[test]
+    fn instapaper_export() {
+        let groups = parse_fixture("instapaper.csv", parse_file);
+        assert_eq!(names(&groups), ["Unread", "Research"]);
+        let long_read = &groups[0].items[0];
+        assert_eq!(long_read.url, "https://example.com/long-read");
+        assert_eq!(
+            long_read.description.as_deref(),
+            Some("A highlighted sentence")
+        );
+        assert_eq!(long_read.tags, ["essays", "later"]);
+        assert_eq!(long_read.add_date, Some(1_700_000_600));
+        assert!(groups[1].items[0].tags.is_empty());
+    }
+
+    #[test]
+    fn iso8601_dates() {
+        assert_eq!(parse_date("2024-03-01T08:30:00.000Z"), Some(1_709_281_800));
+        assert_eq!(parse_date("2024-03-01T08:30:00"), Some(1_709_281_800));
+        assert_eq!(parse_date("2024-03-01 16:30:00+08:00"), Some(1_709_281_800));
+        assert_eq!(parse_date("2024-03-01"), Some(1_709_251_200));
+        assert_eq!(parse_date("1700000000"), Some(1_700_000_000));
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("yesterday"), None);
+    }
+}
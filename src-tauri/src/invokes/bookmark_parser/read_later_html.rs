@@ -0,0 +1,160 @@
+//! 解析 Pocket 和 Instapaper 导出的 HTML。
+//!
+//! 两者的结构相同：每个 `<h1>`（如“Unread”“Read Archive”或 Instapaper 的文件夹）后面
+//! 跟着一个由 `<li><a href="...">标题</a></li>` 构成的列表。
+//! Pocket 还会在 `<a>` 上写入 `time_added` 和以逗号分隔的 `tags` 属性。
+
+use super::netscape::parse_timestamp;
+use super::outline::OutlineBuilder;
+use super::title::normalize_text;
+use super::{BookmarkGroup, BookmarkItem, file_stem_name};
+use html5ever::tendril::StrTendril;
+use html5ever::tokenizer::{
+    BufferQueue, TagKind, Token, TokenSink, TokenSinkResult, Tokenizer, TokenizerOpts,
+};
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+
+/// 读取并解析导出的 HTML，每个 `<h1>` 成为一个分组。
+pub(super) fn parse_file(path: &Path) -> Result<Vec<BookmarkGroup>, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+
+    let tokenizer = Tokenizer::new(
+        ReadLaterSink {
+            state: RefCell::new(SinkState {
+                builder: Some(OutlineBuilder::new(file_stem_name(path))),
+                capture: None,
+            }),
+        },
+        TokenizerOpts::default(),
+    );
+    let queue = BufferQueue::default();
+    queue.push_back(StrTendril::from(content));
+    let _ = tokenizer.feed(&queue);
+    tokenizer.end();
+
+    let state = tokenizer.sink.state.take();
+    Ok(state
+        .builder
+        .map(OutlineBuilder::finish)
+        .unwrap_or_default())
+}
+
+/// 正在收集文本的标签。
+enum Capture {
+    /// `<h1>` 分组标题。
+    Heading(String),
+    /// `<a>` 链接，保存其属性。
+    Link {
+        text: String,
+        attrs: Vec<(String, String)>,
+    },
+}
+
+/// 分词过程中的解析状态。
+#[derive(Default)]
+struct SinkState {
+    builder: Option<OutlineBuilder>,
+    capture: Option<Capture>,
+}
+
+/// 接收分词器产生的标签，按 `<h1>` 划分分组。
+struct ReadLaterSink {
+    state: RefCell<SinkState>,
+}
+
+impl TokenSink for ReadLaterSink {
+    type Handle = ();
+
+    fn process_token(&self, token: Token, _line_number: u64) -> TokenSinkResult<()> {
+        let mut state = self.state.borrow_mut();
+        match token {
+            Token::TagToken(tag) => match (tag.kind, &*tag.name) {
+                (TagKind::StartTag, "h1") => state.capture = Some(Capture::Heading(String::new())),
+                (TagKind::EndTag, "h1") => {
+                    if let Some(Capture::Heading(text)) = state.capture.take()
+                        && let Some(builder) = state.builder.as_mut()
+                    {
+                        builder.open(1, normalize_text(&text));
+                    }
+                }
+                (TagKind::StartTag, "a") => {
+                    let attrs = tag
+                        .attrs
+                        .iter()
+                        .map(|attr| (attr.name.local.to_string(), attr.value.to_string()))
+                        .collect();
+                    state.capture = Some(Capture::Link {
+                        text: String::new(),
+                        attrs,
+                    });
+                }
+                (TagKind::EndTag, "a") => {
+                    if let Some(Capture::Link { text, attrs }) = state.capture.take()
+                        && let Some(item) = build_item(&text, &attrs)
+                        && let Some(builder) = state.builder.as_mut()
+                    {
+                        builder.push_item(item);
+                    }
+                }
+                _ => {}
+            },
+            Token::CharacterTokens(text) => match state.capture.as_mut() {
+                Some(Capture::Heading(buffer)) | Some(Capture::Link { text: buffer, .. }) => {
+                    buffer.push_str(&text)
+                }
+                None => {}
+            },
+            _ => {}
+        }
+        TokenSinkResult::Continue
+    }
+}
+
+/// 根据 `<a>` 的文本和属性构建书签项，只接受 `http(s)` 链接。
+fn build_item(title: &str, attrs: &[(String, String)]) -> Option<BookmarkItem> {
+    let attr = |name: &str| {
+        attrs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    let url = attr("href")
+        .map(str::trim)
+        .filter(|url| url.starts_with("http://") || url.starts_with("https://"))?;
+
+    Some(BookmarkItem {
+        add_date: attr("time_added").and_then(parse_timestamp),
+        tags: attr("tags")
+            .map(|tags| {
+                tags.split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ..BookmarkItem::new(title, url.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{names, parse_fixture, titles};
+    use super::*;
+
+    #[test]
+    fn pocket_export() {
+        let groups = parse_fixture("pocket.html", parse_file);
+        assert_eq!(names(&groups), ["Unread", "Read Archive"]);
+        // 非 http(s) 链接被忽略
+        assert_eq!(titles(&groups[0]), ["Example & Article"]);
+        let article = &groups[0].items[0];
+        assert_eq!(article.add_date, Some(1_700_000_400));
+        assert_eq!(article.tags, ["news", "tech"]);
+        assert!(groups[1].items[0].tags.is_empty());
+        assert_eq!(groups[1].items[0].add_date, Some(1_700_000_500));
+    }
+}
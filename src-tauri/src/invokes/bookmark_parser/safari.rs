@@ -0,0 +1,182 @@
+//! 解析 Safari 的 `Bookmarks.plist`（二进制或 XML 格式均可）。
+//!
+//! 文件的根节点是一个 `WebBookmarkTypeList`，其下依次是“个人收藏”（`BookmarksBar`）、
+//! “书签菜单”（`BookmarksMenu`）、“阅读列表”（`com.apple.ReadingList`）以及用户创建的文件夹；
+//! 书签为 `WebBookmarkTypeLeaf`，标题保存在 `URIDictionary.title` 中。
+
+use super::title::normalize_text;
+use super::{BookmarkGroup, BookmarkItem};
+use serde::Deserialize;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// `WebBookmarkType` 中表示文件夹的值。
+const TYPE_LIST: &str = "WebBookmarkTypeList";
+/// `WebBookmarkType` 中表示书签的值。
+const TYPE_LEAF: &str = "WebBookmarkTypeLeaf";
+
+/// 各个内置根文件夹的标题。
+const BOOKMARKS_BAR: &str = "BookmarksBar";
+const BOOKMARKS_MENU: &str = "BookmarksMenu";
+const READING_LIST: &str = "com.apple.ReadingList";
+
+/// 书签树中的一个节点，可能是文件夹、书签或“历史记录”等代理节点。
+#[derive(Deserialize, Debug)]
+struct Node {
+    #[serde(rename = "WebBookmarkType")]
+    kind: String,
+    #[serde(rename = "Title", default)]
+    title: String,
+    #[serde(rename = "URLString")]
+    url: Option<String>,
+    #[serde(rename = "URIDictionary")]
+    uri_dictionary: Option<UriDictionary>,
+    /// 仅阅读列表中的书签带有该字段。
+    #[serde(rename = "ReadingList")]
+    reading_list: Option<ReadingListInfo>,
+    #[serde(rename = "Children", default)]
+    children: Vec<Node>,
+}
+
+#[derive(Deserialize, Debug)]
+struct UriDictionary {
+    title: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ReadingListInfo {
+    #[serde(rename = "DateAdded")]
+    date_added: Option<plist::Date>,
+}
+
+/// 读取并解析 Safari `Bookmarks.plist`，返回与其他解析器相同结构的文件夹树。
+///
+/// “个人收藏”下的书签整理成一个分组放在最前面，其中的每个文件夹各自成为一个顶层分组；
+/// “书签菜单”、“阅读列表”和其他顶层文件夹则分别作为一个顶层分组。
+pub(super) fn parse_file(path: &Path) -> Result<Vec<BookmarkGroup>, String> {
+    let root: Node =
+        plist::from_file(path).map_err(|e| format!("解析 Bookmarks.plist 失败: {e}"))?;
+
+    let mut groups: Vec<BookmarkGroup> = Vec::new();
+    for node in root.children {
+        if node.kind != TYPE_LIST {
+            continue;
+        }
+        let name = root_display_name(&node.title);
+
+        if node.title == BOOKMARKS_BAR {
+            let bar = build_folder(node, BookmarkGroup::new(name.clone(), Vec::new()));
+            if !bar.items.is_empty() {
+                let mut top_level = BookmarkGroup::new(name.clone(), vec![name]);
+                top_level.items = bar.items;
+                groups.push(top_level);
+            }
+            groups.extend(bar.children);
+        } else {
+            groups.push(build_folder(
+                node,
+                BookmarkGroup::new(name.clone(), vec![name]),
+            ));
+        }
+    }
+
+    Ok(groups)
+}
+
+/// 递归地把文件夹节点中的子节点填充到 `folder` 中。
+fn build_folder(node: Node, mut folder: BookmarkGroup) -> BookmarkGroup {
+    for child in node.children {
+        match child.kind.as_str() {
+            TYPE_LIST => {
+                let name = normalize_text(&child.title);
+                let mut path = folder.path.clone();
+                path.push(name.clone());
+                folder
+                    .children
+                    .push(build_folder(child, BookmarkGroup::new(name, path)));
+            }
+            TYPE_LEAF => {
+                let Some(url) = child.url.filter(|url| !url.is_empty()) else {
+                    continue;
+                };
+                let title = child
+                    .uri_dictionary
+                    .and_then(|dict| dict.title)
+                    .unwrap_or_default();
+                let add_date = child
+                    .reading_list
+                    .and_then(|info| info.date_added)
+                    .and_then(|date| SystemTime::from(date).duration_since(UNIX_EPOCH).ok())
+                    .map(|duration| duration.as_secs() as i64);
+                folder.items.push(BookmarkItem {
+                    add_date,
+                    ..BookmarkItem::new(&title, url)
+                });
+            }
+            // “历史记录”等代理节点直接忽略
+            _ => {}
+        }
+    }
+    folder
+}
+
+/// 内置根文件夹在文件中的标题是 `BookmarksBar` 这类内部名称，这里换成可读的名称。
+fn root_display_name(title: &str) -> String {
+    match title {
+        BOOKMARKS_BAR => "Favorites".to_string(),
+        BOOKMARKS_MENU => "Bookmarks Menu".to_string(),
+        READING_LIST => "Reading List".to_string(),
+        _ => normalize_text(title),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{names, parse_fixture, titles};
+    use super::*;
+
+    fn assert_bookmarks(groups: &[BookmarkGroup]) {
+        assert_eq!(
+            names(groups),
+            [
+                "Favorites",
+                "Dev",
+                "Bookmarks Menu",
+                "Reading List",
+                "Recipes"
+            ]
+        );
+
+        assert_eq!(titles(&groups[0]), ["Apple"]);
+        assert_eq!(groups[0].path, ["Favorites"]);
+
+        let dev = &groups[1];
+        assert_eq!(dev.path, ["Dev"]);
+        assert_eq!(titles(dev), ["Rust"]);
+        assert_eq!(names(&dev.children), ["Infra"]);
+        assert_eq!(dev.children[0].path, ["Dev", "Infra"]);
+        assert_eq!(titles(&dev.children[0]), ["Grafana Dashboards"]);
+
+        // 标题为空时使用域名
+        assert_eq!(titles(&groups[2]), ["example.org"]);
+
+        let reading_list = &groups[3];
+        assert_eq!(reading_list.path, ["Reading List"]);
+        assert_eq!(titles(reading_list), ["Vec<T> & friends"]);
+        assert_eq!(reading_list.items[0].add_date, Some(1_700_000_000));
+        assert_eq!(groups[0].items[0].add_date, None);
+
+        // 没有 URL 的书签被忽略
+        assert_eq!(titles(&groups[4]), ["Bread"]);
+    }
+
+    #[test]
+    fn binary_plist() {
+        assert_bookmarks(&parse_fixture("safari_binary.plist", parse_file));
+    }
+
+    #[test]
+    fn xml_plist() {
+        assert_bookmarks(&parse_fixture("safari_xml.plist", parse_file));
+    }
+}
@@ -0,0 +1,77 @@
+//! 书签标题和文件夹名称的统一规范化。
+//!
+//! 这里只处理与格式无关的部分：合并换行和连续空白、截断过长的标题，以及标题为空时的默认值。
+//! HTML 实体和内嵌标签由 Netscape、Pocket 等基于 HTML 的解析器在分词时处理，
+//! JSON、SQLite、plist 和 CSV 中的标题本身就是纯文本，`Vector<T>`、`Tom &amp; Jerry`
+//! 这样的标题应当原样保留，不能再解码一次。
+
+use url::Url;
+
+/// 标题保留的最大字符数，超出部分以省略号代替。
+const MAX_TITLE_CHARS: usize = 120;
+
+/// 无法从 URL 中提取域名时使用的标题。
+const UNTITLED: &str = "Untitled";
+
+/// 规范化书签标题。
+///
+/// 合并连续空白并截断过长的标题；
+/// 处理后为空时，使用 URL 中的域名作为标题，URL 无法解析时返回 `Untitled`。
+pub(super) fn normalize_title(title: &str, url: &str) -> String {
+    let title = normalize_text(title);
+    if !title.is_empty() {
+        return title;
+    }
+    Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(String::from))
+        .unwrap_or_else(|| UNTITLED.to_string())
+}
+
+/// 规范化文件夹名称等普通文本，规则与 `normalize_title` 相同，但不提供默认值。
+pub(super) fn normalize_text(text: &str) -> String {
+    truncate(&text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// 截断超过 `MAX_TITLE_CHARS` 个字符的文本。
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(MAX_TITLE_CHARS) {
+        Some((end, _)) => format!("{}…", text[..end].trim_end()),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_plain_text_markup_characters() {
+        assert_eq!(normalize_text("Vector<T> docs"), "Vector<T> docs");
+        assert_eq!(normalize_text("Tom &amp; Jerry"), "Tom &amp; Jerry");
+    }
+
+    #[test]
+    fn collapses_whitespace() {
+        assert_eq!(
+            normalize_text("  Grafana\n\t  Dashboards  "),
+            "Grafana Dashboards"
+        );
+    }
+
+    #[test]
+    fn truncates_long_titles() {
+        let title = normalize_text(&"a".repeat(MAX_TITLE_CHARS + 10));
+        assert_eq!(title.chars().count(), MAX_TITLE_CHARS + 1);
+        assert!(title.ends_with('…'));
+    }
+
+    #[test]
+    fn falls_back_to_host() {
+        assert_eq!(
+            normalize_title(" \n ", "https://example.com/a"),
+            "example.com"
+        );
+        assert_eq!(normalize_title("", "not a url"), UNTITLED);
+    }
+}
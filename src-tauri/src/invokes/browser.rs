@@ -1,7 +1,12 @@
-//! 该模块提供与系统浏览器交互相关的功能，检测已安装的浏览器。
+//! 该模块提供与系统浏览器交互相关的功能，检测已安装的浏览器，并查找各浏览器配置目录中的书签文件。
 
-#[cfg(any(target_os = "windows", target_os = "macos"))]
-use std::path::PathBuf;
+use crate::invokes::bookmark_parser::{BookmarkFormat, count_browser_bookmarks};
+use log::{debug, warn};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
 
 #[cfg(target_os = "linux")]
 use std::process::Command;
@@ -131,3 +136,304 @@ async fn check_linux_installations(id: &str) -> bool {
         Err(_) => false,
     }
 }
+
+/// 浏览器中一个包含书签的用户配置。
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct BrowserProfile {
+    /// 浏览器的唯一标识符，如 `chrome`、`firefox`
+    browser_id: &'static str,
+    /// 浏览器的显示名称
+    browser_name: &'static str,
+    /// 用户配置的显示名称，如 `Work`
+    profile_name: String,
+    /// 书签文件的完整路径，可直接传给 `auto_bookmark_parser` 导入
+    bookmarks_path: PathBuf,
+    /// 书签文件的格式
+    format: BookmarkFormat,
+    /// 书签数量
+    bookmark_count: usize,
+    /// 同一配置中保存搜索引擎的文件，可直接传给 `auto_search_engine_parser` 导入
+    search_engines_path: Option<PathBuf>,
+}
+
+/// Chromium 系浏览器 `Local State` 文件中与用户配置相关的部分。
+#[derive(Deserialize, Default, Debug)]
+struct LocalState {
+    #[serde(default)]
+    profile: LocalStateProfiles,
+}
+
+#[derive(Deserialize, Default, Debug)]
+struct LocalStateProfiles {
+    /// 以配置目录名（如 `Default`、`Profile 1`）为键的配置信息。
+    #[serde(default)]
+    info_cache: HashMap<String, LocalStateProfile>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LocalStateProfile {
+    #[serde(default)]
+    name: String,
+}
+
+/// [Tauri Command] 列出本机所有浏览器用户配置及其书签文件。
+///
+/// ## 查找范围:
+/// - **Chromium 系**（Chrome、Edge、Chromium、Brave、Vivaldi）: 用户数据目录下每个包含 `Bookmarks`
+///   文件的子目录都是一个配置，显示名称取自 `Local State` 中的 `profile.info_cache`。
+/// - **Firefox**: 读取 `profiles.ini` 中的每个 `[Profile*]`，书签文件为配置目录下的 `places.sqlite`。
+/// - **Safari**: macOS 上的 `~/Library/Safari/Bookmarks.plist`。
+///
+/// 无法读取书签数量的配置会被跳过。
+///
+/// # Returns
+/// * `Vec<BrowserProfile>` - 找到的所有浏览器配置。
+#[tauri::command]
+pub async fn detect_browser_profiles(app: AppHandle) -> Vec<BrowserProfile> {
+    let Ok(home) = app.path().home_dir() else {
+        return Vec::new();
+    };
+
+    let mut candidates: Vec<(&'static str, &'static str, String, PathBuf, BookmarkFormat)> =
+        Vec::new();
+    for (id, name, user_data_dir) in chromium_user_data_dirs(&home) {
+        for (profile_name, bookmarks_path) in chromium_profiles(&user_data_dir) {
+            candidates.push((
+                id,
+                name,
+                profile_name,
+                bookmarks_path,
+                BookmarkFormat::Chromium,
+            ));
+        }
+    }
+    for firefox_dir in firefox_dirs(&home) {
+        for (profile_name, places_path) in firefox_profiles(&firefox_dir) {
+            candidates.push((
+                "firefox",
+                "Mozilla Firefox",
+                profile_name,
+                places_path,
+                BookmarkFormat::Firefox,
+            ));
+        }
+    }
+
+    let safari_path = home.join("Library/Safari/Bookmarks.plist");
+    if safari_path.is_file() {
+        candidates.push((
+            "safari",
+            "Safari",
+            "Default".to_string(),
+            safari_path,
+            BookmarkFormat::Safari,
+        ));
+    }
+
+    let mut profiles = Vec::new();
+    for (browser_id, browser_name, profile_name, bookmarks_path, format) in candidates {
+        match count_browser_bookmarks(format, &bookmarks_path).await {
+            Ok(bookmark_count) => profiles.push(BrowserProfile {
+                browser_id,
+                browser_name,
+                profile_name,
+                search_engines_path: search_engines_path(format, &bookmarks_path),
+                bookmarks_path,
+                format,
+                bookmark_count,
+            }),
+            Err(e) => warn!("读取浏览器书签失败 {bookmarks_path:?}: {e}"),
+        }
+    }
+    profiles
+}
+
+/// 查找与书签文件位于同一配置目录中的搜索引擎文件：Chromium 的 `Web Data`、Firefox 的 `search.json.mozlz4`。
+fn search_engines_path(format: BookmarkFormat, bookmarks_path: &Path) -> Option<PathBuf> {
+    let file_name = match format {
+        BookmarkFormat::Chromium => "Web Data",
+        BookmarkFormat::Firefox => "search.json.mozlz4",
+        _ => return None,
+    };
+    let path = bookmarks_path.parent()?.join(file_name);
+    path.is_file().then_some(path)
+}
+
+/// 各 Chromium 系浏览器可能的用户数据目录。
+fn chromium_user_data_dirs(home: &Path) -> Vec<(&'static str, &'static str, PathBuf)> {
+    #[cfg(target_os = "linux")]
+    let dirs = {
+        let config = home.join(".config");
+        let flatpak = home.join(".var/app");
+        vec![
+            ("chrome", "Google Chrome", config.join("google-chrome")),
+            ("chrome", "Google Chrome", config.join("google-chrome-beta")),
+            (
+                "chrome",
+                "Google Chrome",
+                flatpak.join("com.google.Chrome/config/google-chrome"),
+            ),
+            ("msedge", "Microsoft Edge", config.join("microsoft-edge")),
+            ("chromium", "Chromium", config.join("chromium")),
+            (
+                "chromium",
+                "Chromium",
+                home.join("snap/chromium/common/chromium"),
+            ),
+            ("brave", "Brave", config.join("BraveSoftware/Brave-Browser")),
+            ("vivaldi", "Vivaldi", config.join("vivaldi")),
+        ]
+    };
+    #[cfg(target_os = "macos")]
+    let dirs = {
+        let support = home.join("Library/Application Support");
+        vec![
+            ("chrome", "Google Chrome", support.join("Google/Chrome")),
+            ("msedge", "Microsoft Edge", support.join("Microsoft Edge")),
+            ("chromium", "Chromium", support.join("Chromium")),
+            (
+                "brave",
+                "Brave",
+                support.join("BraveSoftware/Brave-Browser"),
+            ),
+            ("vivaldi", "Vivaldi", support.join("Vivaldi")),
+        ]
+    };
+    #[cfg(target_os = "windows")]
+    let dirs = {
+        let local = std::env::var("LOCALAPPDATA")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| home.join("AppData\\Local"));
+        vec![
+            (
+                "chrome",
+                "Google Chrome",
+                local.join("Google\\Chrome\\User Data"),
+            ),
+            (
+                "msedge",
+                "Microsoft Edge",
+                local.join("Microsoft\\Edge\\User Data"),
+            ),
+            ("chromium", "Chromium", local.join("Chromium\\User Data")),
+            (
+                "brave",
+                "Brave",
+                local.join("BraveSoftware\\Brave-Browser\\User Data"),
+            ),
+            ("vivaldi", "Vivaldi", local.join("Vivaldi\\User Data")),
+        ]
+    };
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    let dirs = {
+        let _ = home;
+        Vec::new()
+    };
+
+    dirs.into_iter()
+        .filter(|(_, _, dir)| dir.is_dir())
+        .collect()
+}
+
+/// 列出 Chromium 用户数据目录下所有包含 `Bookmarks` 文件的配置，返回显示名称和书签文件路径。
+fn chromium_profiles(user_data_dir: &Path) -> Vec<(String, PathBuf)> {
+    let local_state: LocalState = fs::read_to_string(user_data_dir.join("Local State"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    let Ok(entries) = fs::read_dir(user_data_dir) else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<(String, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let bookmarks_path = entry.path().join("Bookmarks");
+            if !bookmarks_path.is_file() {
+                return None;
+            }
+            let dir_name = entry.file_name().to_string_lossy().into_owned();
+            let profile_name = local_state
+                .profile
+                .info_cache
+                .get(&dir_name)
+                .map(|info| info.name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .unwrap_or(dir_name);
+            Some((profile_name, bookmarks_path))
+        })
+        .collect();
+    profiles.sort();
+    debug!("在 {user_data_dir:?} 中找到 {} 个配置", profiles.len());
+    profiles
+}
+
+/// Firefox 可能的配置根目录（包含 `profiles.ini` 的目录）。
+fn firefox_dirs(home: &Path) -> Vec<PathBuf> {
+    #[cfg(target_os = "linux")]
+    let dirs = vec![
+        home.join(".mozilla/firefox"),
+        home.join("snap/firefox/common/.mozilla/firefox"),
+        home.join(".var/app/org.mozilla.firefox/.mozilla/firefox"),
+    ];
+    #[cfg(target_os = "macos")]
+    let dirs = vec![home.join("Library/Application Support/Firefox")];
+    #[cfg(target_os = "windows")]
+    let dirs = vec![
+        std::env::var("APPDATA")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| home.join("AppData\\Roaming"))
+            .join("Mozilla\\Firefox"),
+    ];
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    let dirs: Vec<PathBuf> = {
+        let _ = home;
+        Vec::new()
+    };
+
+    dirs.into_iter()
+        .filter(|dir| dir.join("profiles.ini").is_file())
+        .collect()
+}
+
+/// 解析 `profiles.ini`，返回每个配置的名称和其 `places.sqlite` 的路径。
+fn firefox_profiles(firefox_dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(content) = fs::read_to_string(firefox_dir.join("profiles.ini")) else {
+        return Vec::new();
+    };
+
+    // 每个 `[Profile*]` 小节中的键值对
+    let mut sections: Vec<HashMap<String, String>> = Vec::new();
+    let mut in_profile = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_profile = line.starts_with("[Profile");
+            if in_profile {
+                sections.push(HashMap::new());
+            }
+        } else if in_profile
+            && let Some((key, value)) = line.split_once('=')
+            && let Some(section) = sections.last_mut()
+        {
+            section.insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    sections
+        .into_iter()
+        .filter_map(|section| {
+            let path = section.get("Path")?;
+            let profile_dir = if section.get("IsRelative").map(String::as_str) == Some("0") {
+                PathBuf::from(path)
+            } else {
+                firefox_dir.join(path)
+            };
+            let places_path = profile_dir.join("places.sqlite");
+            if !places_path.is_file() {
+                return None;
+            }
+            let name = section.get("Name").cloned().unwrap_or_else(|| path.clone());
+            Some((name, places_path))
+        })
+        .collect()
+}
@@ -0,0 +1,345 @@
+//! 该模块负责从网页中收集图标候选并按质量排序。
+//!
+//! 页面往往同时声明多个图标：16×16 的 ICO、高清的 PNG、SVG 以及只在深色模式下使用的变体。
+//! 按声明的尺寸、格式以及 `media` 中的 `prefers-color-scheme` 为每个候选打分，
+//! 得分最高的优先下载。Web App Manifest 中的图标（见 [`web_manifest`]）与页面中的图标一起排序，
+//! 站点根目录下的 `/favicon.ico` 始终作为最后一个候选。
+//!
+//! [`web_manifest`]: crate::invokes::web_manifest
+
+use scraper::{Html, Selector};
+use url::Url;
+
+/// 没有声明尺寸时 `apple-touch-icon` 的默认尺寸。
+const APPLE_TOUCH_ICON_SIZE: u32 = 180;
+
+/// 没有声明尺寸的普通图标按此尺寸估计。
+const UNKNOWN_ICON_SIZE: u32 = 32;
+
+/// 超过该尺寸后不再加分，避免为了更大的图片放弃更合适的格式。
+const MAX_SCORED_SIZE: u32 = 256;
+
+/// 与当前主题不匹配的 `prefers-color-scheme` 图标的扣分，足以让它排在所有匹配的图标之后。
+const SCHEME_MISMATCH_PENALTY: i64 = 10_000;
+
+/// 界面的配色方案，用于挑选 `media="(prefers-color-scheme: ...)"` 的图标。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum ColorScheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    /// 解析前端传入的主题名称，无法识别时使用浅色。
+    pub(crate) fn parse(name: Option<&str>) -> Self {
+        match name.map(str::trim) {
+            Some(name) if name.eq_ignore_ascii_case("dark") => Self::Dark,
+            _ => Self::Light,
+        }
+    }
+}
+
+/// 图标的格式，按显示效果从好到差排列。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IconFormat {
+    Svg,
+    Png,
+    /// JPEG、GIF、WebP 等其他位图。
+    Raster,
+    Ico,
+    Unknown,
+}
+
+impl IconFormat {
+    /// 根据 `type` 属性判断格式，没有时根据 URL 的扩展名判断。
+    fn detect(mime: Option<&str>, url: &Url) -> Self {
+        if let Some(mime) = mime.map(|mime| mime.trim().to_lowercase())
+            && !mime.is_empty()
+        {
+            return match mime.as_str() {
+                "image/svg+xml" => Self::Svg,
+                "image/png" => Self::Png,
+                "image/x-icon" | "image/vnd.microsoft.icon" | "image/ico" => Self::Ico,
+                mime if mime.starts_with("image/") => Self::Raster,
+                _ => Self::Unknown,
+            };
+        }
+        let path = url.path().to_lowercase();
+        match path.rsplit_once('.').map(|(_, extension)| extension) {
+            Some("svg") => Self::Svg,
+            Some("png") => Self::Png,
+            Some("ico") => Self::Ico,
+            Some("jpg" | "jpeg" | "gif" | "webp") => Self::Raster,
+            _ => Self::Unknown,
+        }
+    }
+
+    fn score(self) -> i64 {
+        match self {
+            Self::Svg => 100,
+            Self::Png => 60,
+            Self::Raster => 20,
+            Self::Unknown => 10,
+            Self::Ico => 0,
+        }
+    }
+}
+
+/// 一个图标候选。
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IconCandidate {
+    pub(crate) url: String,
+    score: i64,
+}
+
+/// 页面或 Manifest 中声明图标时附带的属性。
+#[derive(Debug, Default)]
+pub(crate) struct IconDeclaration<'a> {
+    pub(crate) mime: Option<&'a str>,
+    pub(crate) sizes: Option<&'a str>,
+    pub(crate) media: Option<&'a str>,
+    /// 没有声明尺寸时使用的估计值。
+    pub(crate) default_size: Option<u32>,
+    /// 额外的扣分，例如只能用作遮罩的 Manifest 图标。
+    pub(crate) penalty: i64,
+}
+
+impl IconCandidate {
+    /// 根据图标的声明计算得分。
+    pub(crate) fn new(url: Url, declaration: &IconDeclaration, scheme: ColorScheme) -> Self {
+        let format = IconFormat::detect(declaration.mime, &url);
+        let size = declared_size(declaration.sizes, format)
+            .or(declaration.default_size)
+            .unwrap_or(UNKNOWN_ICON_SIZE);
+        let score = score_icon(size, format, declaration.media, scheme) - declaration.penalty;
+        Self {
+            url: url.to_string(),
+            score,
+        }
+    }
+}
+
+/// 收集页面中 `<link>` 声明的所有图标，尚未排序。
+pub(crate) fn collect_link_icons(
+    document: &Html,
+    base_url: &str,
+    scheme: ColorScheme,
+) -> Vec<IconCandidate> {
+    let Ok(base_url) = Url::parse(base_url) else {
+        return Vec::new();
+    };
+
+    let selector = Selector::parse("link[rel][href]").unwrap();
+    document
+        .select(&selector)
+        .filter_map(|element| {
+            let element = element.value();
+            let rel = element.attr("rel")?.to_lowercase();
+            let is_apple_touch = rel
+                .split_whitespace()
+                .any(|token| token.starts_with("apple-touch-icon"));
+            // `mask-icon` 是 Safari 的单色图标，不适合用作磁贴图标
+            if !is_apple_touch && !rel.split_whitespace().any(|token| token == "icon") {
+                return None;
+            }
+            let href = element.attr("href")?.trim();
+            if href.is_empty() {
+                return None;
+            }
+            let url = base_url.join(href).ok()?;
+
+            let declaration = IconDeclaration {
+                mime: element.attr("type"),
+                sizes: element.attr("sizes"),
+                media: element.attr("media"),
+                default_size: is_apple_touch.then_some(APPLE_TOUCH_ICON_SIZE),
+                penalty: 0,
+            };
+            Some(IconCandidate::new(url, &declaration, scheme))
+        })
+        .collect()
+}
+
+/// 按得分从高到低排序，并在最后追加站点的 `/favicon.ico`。
+///
+/// 得分相同的候选保持原有顺序，即页面中的图标在 Manifest 的图标之前。
+pub(crate) fn rank_favicon_candidates(
+    mut candidates: Vec<IconCandidate>,
+    base_url: &Url,
+) -> Vec<IconCandidate> {
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.score));
+    push_default_favicon(&mut candidates, base_url);
+    candidates
+}
+
+/// 在末尾追加 `/favicon.ico`，页面已经声明了同一地址时不重复添加。
+fn push_default_favicon(candidates: &mut Vec<IconCandidate>, base_url: &Url) {
+    if let Ok(url) = base_url.join("/favicon.ico") {
+        let url = url.to_string();
+        if !candidates.iter().any(|candidate| candidate.url == url) {
+            candidates.push(IconCandidate { url, score: 0 });
+        }
+    }
+}
+
+/// 解析 `sizes` 属性，返回其中最大的边长；`any` 视为可任意缩放。
+///
+/// 没有 `sizes` 的 SVG 同样可以任意缩放。
+fn declared_size(sizes: Option<&str>, format: IconFormat) -> Option<u32> {
+    let sizes = sizes.unwrap_or("").to_lowercase();
+    if sizes.split_whitespace().any(|size| size == "any")
+        || (sizes.trim().is_empty() && format == IconFormat::Svg)
+    {
+        return Some(MAX_SCORED_SIZE);
+    }
+    sizes
+        .split_whitespace()
+        .filter_map(|size| {
+            let (width, height) = size.split_once('x')?;
+            Some(width.parse::<u32>().ok()?.min(height.parse().ok()?))
+        })
+        .max()
+}
+
+/// 计算候选图标的得分：尺寸越大越好（超过 [`MAX_SCORED_SIZE`] 不再加分），
+/// SVG 和 PNG 优于 ICO，`prefers-color-scheme` 与当前主题不一致时排到最后。
+fn score_icon(size: u32, format: IconFormat, media: Option<&str>, scheme: ColorScheme) -> i64 {
+    let mut score = i64::from(size.min(MAX_SCORED_SIZE)) * 2 + format.score();
+    if let Some(media_scheme) = media.and_then(media_color_scheme) {
+        if media_scheme == scheme {
+            // 页面专门为当前主题准备的图标优先于通用图标
+            score += 50;
+        } else {
+            score -= SCHEME_MISMATCH_PENALTY;
+        }
+    }
+    score
+}
+
+/// 从 `media` 属性中读取 `prefers-color-scheme` 的取值。
+fn media_color_scheme(media: &str) -> Option<ColorScheme> {
+    let media = media.to_lowercase();
+    let (_, value) = media.split_once("prefers-color-scheme")?;
+    let value = value.trim_start().strip_prefix(':')?.trim_start();
+    if value.starts_with("dark") {
+        Some(ColorScheme::Dark)
+    } else if value.starts_with("light") {
+        Some(ColorScheme::Light)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_URL: &str = "https://example.com/app/";
+
+    fn ranked_urls(head: &str, scheme: ColorScheme) -> Vec<String> {
+        let document = Html::parse_document(&format!("<html><head>{head}</head></html>"));
+        let candidates = collect_link_icons(&document, BASE_URL, scheme);
+        rank_favicon_candidates(candidates, &Url::parse(BASE_URL).unwrap())
+            .into_iter()
+            .map(|candidate| candidate.url)
+            .collect()
+    }
+
+    #[test]
+    fn declared_sizes() {
+        assert_eq!(
+            declared_size(Some("16x16 32X32"), IconFormat::Png),
+            Some(32)
+        );
+        assert_eq!(
+            declared_size(Some("any"), IconFormat::Png),
+            Some(MAX_SCORED_SIZE)
+        );
+        assert_eq!(declared_size(None, IconFormat::Svg), Some(MAX_SCORED_SIZE));
+        assert_eq!(declared_size(None, IconFormat::Png), None);
+        assert_eq!(declared_size(Some("big"), IconFormat::Ico), None);
+    }
+
+    #[test]
+    fn larger_png_beats_favicon_ico() {
+        let urls = ranked_urls(
+            r#"<link rel="icon" href="/favicon.ico" sizes="16x16">
+               <link rel="icon" type="image/png" href="icon-192.png" sizes="192x192">"#,
+            ColorScheme::Light,
+        );
+        // 页面已经声明了 `/favicon.ico`，不会重复追加
+        assert_eq!(
+            urls,
+            [
+                "https://example.com/app/icon-192.png",
+                "https://example.com/favicon.ico"
+            ]
+        );
+    }
+
+    #[test]
+    fn svg_beats_small_raster() {
+        assert!(
+            score_icon(MAX_SCORED_SIZE, IconFormat::Svg, None, ColorScheme::Light)
+                > score_icon(32, IconFormat::Png, None, ColorScheme::Light)
+        );
+        let urls = ranked_urls(
+            r#"<link rel="icon" href="/icon-32.png" sizes="32x32">
+               <link rel="icon" href="/icon.svg">"#,
+            ColorScheme::Light,
+        );
+        assert_eq!(urls[0], "https://example.com/icon.svg");
+    }
+
+    #[test]
+    fn media_color_schemes() {
+        assert_eq!(
+            media_color_scheme("(prefers-color-scheme: dark)"),
+            Some(ColorScheme::Dark)
+        );
+        assert_eq!(
+            media_color_scheme("screen and (PREFERS-COLOR-SCHEME:light)"),
+            Some(ColorScheme::Light)
+        );
+        assert_eq!(media_color_scheme("(max-width: 600px)"), None);
+    }
+
+    #[test]
+    fn dark_icon_wins_only_in_dark_scheme() {
+        let head = r#"<link rel="icon" href="/light.svg">
+                      <link rel="icon" href="/dark.svg" media="(prefers-color-scheme: dark)">"#;
+        assert_eq!(
+            ranked_urls(head, ColorScheme::Dark),
+            [
+                "https://example.com/dark.svg",
+                "https://example.com/light.svg",
+                "https://example.com/favicon.ico"
+            ]
+        );
+        assert_eq!(
+            ranked_urls(head, ColorScheme::Light),
+            [
+                "https://example.com/light.svg",
+                "https://example.com/dark.svg",
+                "https://example.com/favicon.ico"
+            ]
+        );
+    }
+
+    #[test]
+    fn favicon_ico_appended_last() {
+        let urls = ranked_urls(
+            r#"<link rel="apple-touch-icon" href="touch.png">
+               <link rel="mask-icon" href="mask.svg">"#,
+            ColorScheme::Light,
+        );
+        assert_eq!(
+            urls,
+            [
+                "https://example.com/app/touch.png",
+                "https://example.com/favicon.ico"
+            ]
+        );
+    }
+}
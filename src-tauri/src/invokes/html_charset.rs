@@ -0,0 +1,142 @@
+//! 该模块负责确定网页的字符编码并解码。
+//!
+//! 不少内网页面和路由器管理页面只在 HTML 中声明 `<meta charset="gbk">`，
+//! HTTP 头里没有 `charset`，直接按 UTF-8 解码会得到乱码。
+//! 按照 HTML 规范的顺序确定编码：BOM、`Content-Type` 头中的 `charset`、
+//! 前 1024 字节中的 `<meta>` 声明，都没有时使用 UTF-8。
+
+use encoding_rs::{Encoding, UTF_8, UTF_16BE, UTF_16LE, WINDOWS_1252, X_USER_DEFINED};
+use log::info;
+
+/// 只在前 1024 字节中查找 `<meta>` 声明的编码。
+const META_PRESCAN_LIMIT: usize = 1024;
+
+/// 按 BOM、`Content-Type` 头和 `<meta>` 声明确定编码并解码网页内容。
+pub(crate) fn decode_html(bytes: &[u8], content_type: Option<&str>) -> String {
+    let encoding = Encoding::for_bom(bytes)
+        .map(|(encoding, _)| encoding)
+        .or_else(|| content_type.and_then(charset_from_content_type))
+        .or_else(|| prescan_meta_charset(bytes))
+        .unwrap_or(UTF_8);
+    if encoding != UTF_8 {
+        info!("Decoding page as {}", encoding.name());
+    }
+    // `decode` 会识别并去掉 BOM
+    let (body, _, _) = encoding.decode(bytes);
+    body.into_owned()
+}
+
+/// 读取 `Content-Type` 中的 `charset` 参数。
+fn charset_from_content_type(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|parameter| {
+        let (name, value) = parameter.split_once('=')?;
+        if !name.trim().eq_ignore_ascii_case("charset") {
+            return None;
+        }
+        Encoding::for_label(value.trim().trim_matches(['"', '\'']).as_bytes())
+    })
+}
+
+/// 在前 1024 字节中查找 `<meta charset="...">` 或
+/// `<meta http-equiv="Content-Type" content="text/html; charset=...">` 声明的编码。
+fn prescan_meta_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = &bytes[..bytes.len().min(META_PRESCAN_LIMIT)];
+    // 编码声明只包含 ASCII 字符，其他字节替换掉也不影响查找
+    let head = String::from_utf8_lossy(head).to_ascii_lowercase();
+
+    let mut rest = head.as_str();
+    while let Some(start) = rest.find("<meta") {
+        rest = &rest[start + "<meta".len()..];
+        let tag = &rest[..rest.find('>').unwrap_or(rest.len())];
+        if let Some(encoding) = charset_in_meta_tag(tag) {
+            return Some(adjust_meta_encoding(encoding));
+        }
+    }
+    None
+}
+
+/// 从 `<meta` 之后、`>` 之前的属性中读取 `charset=` 的取值。
+fn charset_in_meta_tag(tag: &str) -> Option<&'static Encoding> {
+    let (_, value) = tag.split_once("charset")?;
+    let value = value.trim_start().strip_prefix('=')?.trim_start();
+    let value = value.trim_start_matches(['"', '\'']);
+    let end = value
+        .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | ';' | '/' | '>'))
+        .unwrap_or(value.len());
+    Encoding::for_label(&value.as_bytes()[..end])
+}
+
+/// HTML 规范要求：`<meta>` 中声明的 UTF-16 按 UTF-8 处理，`x-user-defined` 按 windows-1252 处理。
+fn adjust_meta_encoding(encoding: &'static Encoding) -> &'static Encoding {
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        UTF_8
+    } else if encoding == X_USER_DEFINED {
+        WINDOWS_1252
+    } else {
+        encoding
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::GBK;
+
+    /// “中文”的 GBK 编码。
+    const ZHONGWEN_GBK: &[u8] = b"\xd6\xd0\xce\xc4";
+
+    fn page(head: &str, body: &[u8]) -> Vec<u8> {
+        let mut bytes = format!("<html><head>{head}</head><body>").into_bytes();
+        bytes.extend_from_slice(body);
+        bytes.extend_from_slice(b"</body></html>");
+        bytes
+    }
+
+    #[test]
+    fn bom_wins_over_header() {
+        let mut bytes = b"\xef\xbb\xbf".to_vec();
+        bytes.extend(page("", "中文".as_bytes()));
+        let html = decode_html(&bytes, Some("text/html; charset=gbk"));
+        assert!(html.starts_with("<html>"));
+        assert!(html.contains("中文"));
+    }
+
+    #[test]
+    fn header_wins_over_meta() {
+        let bytes = page(r#"<meta charset="utf-8">"#, ZHONGWEN_GBK);
+        let html = decode_html(&bytes, Some(r#"text/html; Charset="GBK""#));
+        assert!(html.contains("中文"));
+    }
+
+    #[test]
+    fn meta_charset() {
+        let bytes = page(r#"<meta charset="gbk">"#, ZHONGWEN_GBK);
+        assert_eq!(prescan_meta_charset(&bytes), Some(GBK));
+        assert!(decode_html(&bytes, Some("text/html")).contains("中文"));
+    }
+
+    #[test]
+    fn meta_http_equiv_content_type() {
+        let bytes = page(
+            r#"<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=gb2312">"#,
+            ZHONGWEN_GBK,
+        );
+        assert_eq!(prescan_meta_charset(&bytes), Some(GBK));
+        assert!(decode_html(&bytes, None).contains("中文"));
+    }
+
+    #[test]
+    fn meta_after_prescan_limit_is_ignored() {
+        let padding = format!("<!--{}-->", " ".repeat(META_PRESCAN_LIMIT));
+        let bytes = page(&format!(r#"{padding}<meta charset="gbk">"#), ZHONGWEN_GBK);
+        assert_eq!(prescan_meta_charset(&bytes), None);
+        assert!(!decode_html(&bytes, None).contains("中文"));
+    }
+
+    #[test]
+    fn utf16_in_meta_means_utf8() {
+        let bytes = page(r#"<meta charset="utf-16">"#, "中文".as_bytes());
+        assert_eq!(prescan_meta_charset(&bytes), Some(UTF_8));
+        assert!(decode_html(&bytes, None).contains("中文"));
+    }
+}
@@ -0,0 +1,205 @@
+//! 该模块负责校验并规范化要保存到图标目录的图片。
+//!
+//! 响应头和扩展名都不可靠：不少网站对 `/favicon.ico` 返回 HTML 格式的 404 页面或空文件。
+//! 因此按文件头的魔数判断格式，拒绝非图片内容；SVG 原样保存，
+//! 其他位图统一转换为 [`ICON_EDGE`] × [`ICON_EDGE`] 的 PNG（ICO 取其中最大的一帧）。
+
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, ImageFormat, ImageReader, Limits, RgbaImage};
+use std::io::Cursor;
+
+/// 图标文件的大小上限。
+pub(crate) const MAX_ICON_BYTES: usize = 4 * 1024 * 1024;
+
+/// 保存的位图图标的边长。
+const ICON_EDGE: u32 = 256;
+
+/// 解码时允许的最大边长，防止体积很小但尺寸巨大的图片耗尽内存。
+const MAX_DECODE_EDGE: u32 = 8192;
+
+/// 只在文件开头的这段范围内查找 `<svg` 标签。
+const SVG_SNIFF_LIMIT: usize = 1024;
+
+/// 规范化后的图标。
+pub(crate) struct NormalizedIcon {
+    pub(crate) bytes: Vec<u8>,
+    pub(crate) extension: &'static str,
+}
+
+/// 按内容判断出的图标格式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SniffedFormat {
+    Svg,
+    Raster(ImageFormat),
+}
+
+/// 校验图标内容并转换为统一的格式。
+///
+/// 内容为空、超过 [`MAX_ICON_BYTES`]、不是图片或无法解码时返回 `Err`。
+pub(crate) fn normalize_icon(bytes: &[u8]) -> Result<NormalizedIcon, String> {
+    if bytes.is_empty() {
+        return Err("Icon is empty".to_string());
+    }
+    if bytes.len() > MAX_ICON_BYTES {
+        return Err(format!(
+            "Icon is too large ({} bytes, limit {MAX_ICON_BYTES})",
+            bytes.len()
+        ));
+    }
+
+    let format = sniff_format(bytes).ok_or_else(|| {
+        if looks_like_html(bytes) {
+            "Response is an HTML page, not an image".to_string()
+        } else {
+            "Unrecognized image format".to_string()
+        }
+    })?;
+    let format = match format {
+        SniffedFormat::Svg => {
+            return Ok(NormalizedIcon {
+                bytes: bytes.to_vec(),
+                extension: "svg",
+            });
+        }
+        SniffedFormat::Raster(format) => format,
+    };
+
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DECODE_EDGE);
+    limits.max_image_height = Some(MAX_DECODE_EDGE);
+    reader.limits(limits);
+    // ICO 解码器会选择其中尺寸最大的一帧，GIF 取第一帧
+    let image = reader
+        .decode()
+        .map_err(|e| format!("Invalid {format:?} image: {e}"))?;
+
+    if format == ImageFormat::Png && image.width() == ICON_EDGE && image.height() == ICON_EDGE {
+        // 已经是目标尺寸的 PNG，原样保存以免重复编码
+        return Ok(NormalizedIcon {
+            bytes: bytes.to_vec(),
+            extension: "png",
+        });
+    }
+
+    let mut png = Vec::new();
+    fit_to_square(&image)
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode icon as PNG: {e}"))?;
+    Ok(NormalizedIcon {
+        bytes: png,
+        extension: "png",
+    })
+}
+
+/// 将图片等比缩放到 [`ICON_EDGE`] 以内，并居中放到同样大小的透明画布上。
+fn fit_to_square(image: &DynamicImage) -> DynamicImage {
+    let resized = image
+        .resize(ICON_EDGE, ICON_EDGE, FilterType::Lanczos3)
+        .into_rgba8();
+    let mut canvas = RgbaImage::new(ICON_EDGE, ICON_EDGE);
+    let x = (ICON_EDGE - resized.width()) / 2;
+    let y = (ICON_EDGE - resized.height()) / 2;
+    imageops::overlay(&mut canvas, &resized, i64::from(x), i64::from(y));
+    DynamicImage::ImageRgba8(canvas)
+}
+
+/// 按文件头的魔数判断格式。
+fn sniff_format(bytes: &[u8]) -> Option<SniffedFormat> {
+    let raster = match bytes {
+        [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => Some(ImageFormat::Png),
+        // ICO 的保留字段为 0，类型为 1（CUR 为 2，不作为图标使用），且至少包含一帧
+        [0x00, 0x00, 0x01, 0x00, count_low, count_high, ..]
+            if (*count_low, *count_high) != (0, 0) =>
+        {
+            Some(ImageFormat::Ico)
+        }
+        [0xFF, 0xD8, 0xFF, ..] => Some(ImageFormat::Jpeg),
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some(ImageFormat::Gif),
+        [b'R', b'I', b'F', b'F', ..] if bytes.get(8..12) == Some(b"WEBP") => {
+            Some(ImageFormat::WebP)
+        }
+        [b'B', b'M', ..] => Some(ImageFormat::Bmp),
+        _ => None,
+    };
+    raster
+        .map(SniffedFormat::Raster)
+        .or_else(|| is_svg(bytes).then_some(SniffedFormat::Svg))
+}
+
+/// 文件开头的 1 KB 中是否有 `<svg` 标签，且内容不是 HTML 页面。
+fn is_svg(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(SVG_SNIFF_LIMIT)];
+    let head = String::from_utf8_lossy(head).to_ascii_lowercase();
+    head.contains("<svg") && !looks_like_html(bytes)
+}
+
+/// 内容是否为 HTML 页面，例如伪装成图标的 404 页面。
+fn looks_like_html(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(SVG_SNIFF_LIMIT)];
+    let head = String::from_utf8_lossy(head).to_ascii_lowercase();
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    head.starts_with("<!doctype html") || head.starts_with("<html") || head.contains("<body")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    /// 生成指定尺寸、指定格式的纯色图片。
+    fn encode(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+        let image = RgbaImage::from_pixel(width, height, Rgba([200, 30, 30, 255]));
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .unwrap();
+        bytes
+    }
+
+    fn decoded_size(bytes: &[u8]) -> (u32, u32) {
+        let image = image::load_from_memory_with_format(bytes, ImageFormat::Png).unwrap();
+        (image.width(), image.height())
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert_eq!(normalize_icon(&[]).err().as_deref(), Some("Icon is empty"));
+    }
+
+    #[test]
+    fn rejects_html_page() {
+        let page = b"<!DOCTYPE html>\n<html><body><svg></svg>Not Found</body></html>";
+        assert_eq!(
+            normalize_icon(page).err().as_deref(),
+            Some("Response is an HTML page, not an image")
+        );
+    }
+
+    #[test]
+    fn svg_is_kept_as_is() {
+        let svg = br#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg"/>"#;
+        let icon = normalize_icon(svg).unwrap();
+        assert_eq!(icon.extension, "svg");
+        assert_eq!(icon.bytes, svg);
+    }
+
+    #[test]
+    fn small_png_becomes_square_png() {
+        let icon = normalize_icon(&encode(32, 16, ImageFormat::Png)).unwrap();
+        assert_eq!(icon.extension, "png");
+        assert_eq!(decoded_size(&icon.bytes), (ICON_EDGE, ICON_EDGE));
+    }
+
+    #[test]
+    fn ico_becomes_square_png() {
+        let ico = encode(16, 16, ImageFormat::Ico);
+        assert_eq!(
+            sniff_format(&ico),
+            Some(SniffedFormat::Raster(ImageFormat::Ico))
+        );
+        let icon = normalize_icon(&ico).unwrap();
+        assert_eq!(icon.extension, "png");
+        assert_eq!(decoded_size(&icon.bytes), (ICON_EDGE, ICON_EDGE));
+    }
+}
@@ -1,20 +1,137 @@
-use crate::types::{APP_CONFIG_DIR, HOME_VUST_DIR};
+use crate::invokes::favicon::{
+    ColorScheme, IconCandidate, collect_link_icons, rank_favicon_candidates,
+};
+use crate::invokes::html_charset::decode_html;
+use crate::invokes::icon_image::{MAX_ICON_BYTES, NormalizedIcon, normalize_icon};
+use crate::invokes::web_manifest::{fetch_manifest, find_manifest_url, normalize_hex_color};
+use crate::utils::app_icons_dir_path;
+use base64::{Engine, engine::general_purpose::STANDARD};
 use log::{error, info, warn};
 use scraper::{Html, Selector};
 use sha2::{Digest, Sha256};
 use std::fs::{self, create_dir_all};
-use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
 use tauri_plugin_http::reqwest::{Client, StatusCode, header};
 use url::Url;
 
+/// 单个字段的抓取结果。
+#[derive(serde::Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum FieldOutcome<T> {
+    /// 成功获取到该字段。
+    Found { value: T },
+    /// 页面中没有该字段。
+    Missing,
+    /// 获取该字段时出错，例如页面或图标请求失败。
+    Failed { error: String },
+}
+
+impl<T> FieldOutcome<T> {
+    /// 由可选值构造：有值时为 `Found`，否则为 `Missing`。
+    pub(crate) fn from_option(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Self::Found { value },
+            None => Self::Missing,
+        }
+    }
+
+    pub(crate) fn into_value(self) -> Option<T> {
+        match self {
+            Self::Found { value } => Some(value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn error(&self) -> Option<&str> {
+        match self {
+            Self::Failed { error } => Some(error),
+            _ => None,
+        }
+    }
+}
+
 /// 定义了要返回给前端的网站元数据结构
-#[derive(serde::Serialize)]
+///
+/// 每个字段单独记录结果，图标下载失败时仍然返回已经解析到的标题。
+#[derive(serde::Serialize, Debug)]
 pub struct WebsiteMetadata {
+    /// 跟随重定向之后的最终地址。
+    pub(crate) final_url: FieldOutcome<String>,
+    pub(crate) title: FieldOutcome<String>,
+    /// `<meta name="description">`，没有时使用 `og:description`。
+    pub(crate) description: FieldOutcome<String>,
+    /// 站点名称，来自 `application-name`、`og:site_name` 或 Web App Manifest。
+    pub(crate) site_name: FieldOutcome<String>,
+    /// `og:image` 的绝对地址，没有可用的图标时作为备用图片下载。
+    pub(crate) image_url: FieldOutcome<String>,
+    /// 规范化为 `#rrggbb` 的主题色，来自 Web App Manifest 或 `<meta name="theme-color">`。
+    pub(crate) theme_color: FieldOutcome<String>,
+    pub(crate) local_icon_path: FieldOutcome<String>,
+    /// 不影响结果但值得提示的情况，例如标题为空时改用了主机名。
+    pub(crate) warnings: Vec<String>,
+}
+
+impl WebsiteMetadata {
+    /// 所有失败字段的错误信息，形如 `icon: ...`。
+    ///
+    /// 页面请求失败时多个字段的错误相同，只保留第一个。
+    pub(crate) fn errors(&self) -> Vec<String> {
+        let mut seen = Vec::new();
+        let mut errors = Vec::new();
+        for (field, error) in [
+            ("page", self.final_url.error()),
+            ("title", self.title.error()),
+            ("description", self.description.error()),
+            ("icon", self.local_icon_path.error()),
+        ] {
+            if let Some(error) = error
+                && !seen.contains(&error)
+            {
+                seen.push(error);
+                errors.push(format!("{field}: {error}"));
+            }
+        }
+        errors
+    }
+}
+
+/// 从页面中解析出的数据。
+struct ParsedPage {
+    /// `<title>` 的内容，可能为空字符串。
     title: Option<String>,
-    local_icon_path: Option<String>,
+    /// `<meta name="description">` 或 `og:description`。
+    description: Option<String>,
+    og_title: Option<String>,
+    /// `application-name` 或 `og:site_name`。
+    site_name: Option<String>,
+    /// `og:image` 的绝对地址。
+    image_url: Option<String>,
+    /// `<link>` 声明的图标候选，尚未排序。
+    link_icons: Vec<IconCandidate>,
+    manifest_url: Option<String>,
+    /// 没有 `media` 限制的 `<meta name="theme-color">`。
+    theme_color: Option<String>,
 }
 
+/// 常见的占位标题：单页应用加载完成前的 `<title>`，或脚手架生成后没有修改的标题。
+const GENERIC_TITLES: &[&str] = &[
+    "loading",
+    "loading...",
+    "loading…",
+    "please wait",
+    "please wait...",
+    "untitled",
+    "index",
+    "home",
+    "react app",
+    "vite app",
+    "vite + react + ts",
+];
+
+/// 最多尝试下载的图标候选数量：得分最高的一个，以及一个备选。
+const MAX_ICON_ATTEMPTS: usize = 2;
+
 /// 这是一个同步函数，负责所有非线程安全的HTML解析工作。
 ///
 /// ## 为什么要这样做？
@@ -28,74 +145,245 @@ pub struct WebsiteMetadata {
 ///
 /// 接收一个 `String` 类型的 `body`（它是 `Send` 的），然后返回提取出的
 /// `Option<String>` 类型的数据（它们也是 `Send` 的），这些安全的数据可以在主异步函数中自由地跨越 `.await`。
-fn parse_metadata_from_body(body: &str, base_url: &str) -> (Option<String>, Option<String>) {
+fn parse_metadata_from_body(body: &str, base_url: &str, scheme: ColorScheme) -> ParsedPage {
     let document = Html::parse_document(body);
 
     // Get Title
     let title_selector = Selector::parse("title").unwrap();
-    let mut title = document
+    let title = document
         .select(&title_selector)
         .next()
         .map(|element| element.inner_html().trim().to_string());
-
     if let Some(t) = &title {
-        if t.is_empty() {
-            let url = Url::parse(base_url).expect("Invalid URL");
-            let host = url.host_str().unwrap().to_string();
-            warn!("Title is empty (length = 0), fallback to host: {host}");
-            title = Some(host);
-        } else {
-            info!("Found title: {t}");
-        }
+        info!("Found title: {t}");
     }
 
-    // Get Favicon URL
-    let favicon_url = find_favicon_url(&document, base_url);
+    // Get Description and Open Graph data
+    let description = meta_content(&document, "description")
+        .or_else(|| meta_content(&document, "og:description"));
+    let og_title = meta_content(&document, "og:title");
+    let site_name = meta_content(&document, "application-name")
+        .or_else(|| meta_content(&document, "og:site_name"));
+    let image_url = meta_content(&document, "og:image")
+        .and_then(|image| Url::parse(base_url).ok()?.join(&image).ok())
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .map(String::from);
+
+    // Get Theme Color
+    let theme_color_selector = Selector::parse("meta[name='theme-color']:not([media])").unwrap();
+    let theme_color = document
+        .select(&theme_color_selector)
+        .filter_map(|element| element.value().attr("content"))
+        .find_map(normalize_hex_color);
+
+    ParsedPage {
+        title,
+        description,
+        og_title,
+        site_name,
+        image_url,
+        link_icons: collect_link_icons(&document, base_url, scheme),
+        manifest_url: find_manifest_url(&document, base_url),
+        theme_color,
+    }
+}
 
-    (title, favicon_url)
+/// 读取第一个内容非空的 `<meta>`，`key` 与 `name` 或 `property` 属性比较时忽略大小写。
+///
+/// Open Graph 规范使用 `property`，但不少网站写成了 `name`。
+fn meta_content(document: &Html, key: &str) -> Option<String> {
+    let selector = Selector::parse("meta[content]").unwrap();
+    document
+        .select(&selector)
+        .filter(|element| {
+            let element = element.value();
+            [element.attr("name"), element.attr("property")]
+                .into_iter()
+                .flatten()
+                .any(|name| name.trim().eq_ignore_ascii_case(key))
+        })
+        .filter_map(|element| element.value().attr("content"))
+        .map(|content| content.trim().to_string())
+        .find(|content| !content.is_empty())
+}
+
+/// 标题是否为空或是常见的占位文字。
+fn is_generic_title(title: &str) -> bool {
+    let title = title.trim().to_lowercase();
+    title.is_empty() || GENERIC_TITLES.contains(&title.as_str())
 }
 
 /// 从给定的URL抓取网站的标题和图标
+///
+/// `color_scheme` 为当前界面主题（`light` 或 `dark`），用于挑选对应配色的图标。
 #[tauri::command]
 pub async fn fetch_website_metadata(
     app: AppHandle,
     url: String,
+    color_scheme: Option<String>,
     http_client: tauri::State<'_, Client>,
+) -> Result<WebsiteMetadata, String> {
+    let scheme = ColorScheme::parse(color_scheme.as_deref());
+    fetch_metadata(&app, &http_client, &url, scheme).await
+}
+
+/// 抓取网站的标题并下载图标，`fetch_website_metadata` 和批量抓取共用。
+///
+/// 只有 URL 本身无效时返回 `Err`；页面或图标请求失败会记录在对应字段中。
+/// 页面请求失败时仍会尝试下载站点根目录下的 `/favicon.ico`。
+/// 页面声明了 Web App Manifest 时，其中的图标参与排序；`<title>` 为空或是占位文字时使用其中的名称。
+pub(crate) async fn fetch_metadata(
+    app: &AppHandle,
+    http_client: &Client,
+    url: &str,
+    scheme: ColorScheme,
 ) -> Result<WebsiteMetadata, String> {
     info!("Fetching metadata for URL: {url}");
+    let parsed_url = Url::parse(url).map_err(|e| format!("Invalid URL {url}: {e}"))?;
+    let mut warnings = Vec::new();
+
+    let page = match fetch_body_with_redirects(http_client, url, &mut warnings).await {
+        Ok((final_url, body)) => {
+            // 调用同步函数来处理HTML解析，获取线程安全的数据。
+            let page = parse_metadata_from_body(&body, &final_url, scheme);
+            Ok((final_url, page))
+        }
+        Err(e) => {
+            error!("Failed to fetch page {url}: {e}");
+            Err(e)
+        }
+    };
+    let (final_url, page) = match page {
+        Ok((final_url, page)) => (final_url, page),
+        Err(e) => {
+            // 页面无法访问时仍然尝试站点的 `/favicon.ico`
+            let candidates = rank_favicon_candidates(Vec::new(), &parsed_url);
+            let failed = || FieldOutcome::Failed { error: e.clone() };
+            return Ok(WebsiteMetadata {
+                final_url: failed(),
+                title: failed(),
+                description: failed(),
+                site_name: failed(),
+                image_url: failed(),
+                theme_color: failed(),
+                local_icon_path: download_best_favicon(app, http_client, &candidates, None).await,
+                warnings,
+            });
+        }
+    };
+
+    let manifest = match &page.manifest_url {
+        Some(manifest_url) => match fetch_manifest(http_client, manifest_url).await {
+            Ok(manifest) => Some((manifest_url, manifest)),
+            Err(e) => {
+                warn!("Failed to read web app manifest {manifest_url}: {e}");
+                warnings.push(format!(
+                    "Failed to read web app manifest {manifest_url}: {e}"
+                ));
+                None
+            }
+        },
+        None => None,
+    };
+
+    let manifest_name = manifest
+        .as_ref()
+        .and_then(|(_, manifest)| manifest.display_name());
+    let site_name = page.site_name.or_else(|| manifest_name.clone());
+
+    // 占位标题依次改用 Manifest 中的名称、站点名称和 `og:title`
+    let mut title = page.title;
+    if title.as_deref().is_none_or(is_generic_title)
+        && let Some(name) = [manifest_name, site_name.clone(), page.og_title]
+            .into_iter()
+            .flatten()
+            .find(|name| !is_generic_title(name))
+    {
+        info!("Title {title:?} is generic, using {name}");
+        title = Some(name);
+    }
+    if title.as_deref().is_some_and(|title| title.is_empty()) {
+        let host = Url::parse(&final_url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from));
+        if let Some(host) = &host {
+            warn!("Title is empty (length = 0), fallback to host: {host}");
+            warnings.push(format!("Page title is empty, using host name {host}"));
+        }
+        title = host;
+    }
 
-    let (final_url, body) = fetch_body_with_redirects(&http_client, &url).await?;
+    let theme_color = manifest
+        .as_ref()
+        .and_then(|(_, manifest)| manifest.theme_color())
+        .or(page.theme_color);
 
-    // 调用同步函数来处理HTML解析，获取线程安全的数据。
-    let (title, favicon_url) = parse_metadata_from_body(&body, &final_url);
+    let mut candidates = page.link_icons;
+    if let Some((manifest_url, manifest)) = &manifest {
+        candidates.extend(manifest.icon_candidates(manifest_url, scheme));
+    }
+    let base_url = Url::parse(&final_url).unwrap_or(parsed_url);
+    let candidates = rank_favicon_candidates(candidates, &base_url);
+    let local_icon_path =
+        download_best_favicon(app, http_client, &candidates, page.image_url.as_deref()).await;
+
+    Ok(WebsiteMetadata {
+        final_url: FieldOutcome::Found { value: final_url },
+        title: FieldOutcome::from_option(title),
+        description: FieldOutcome::from_option(page.description),
+        site_name: FieldOutcome::from_option(site_name),
+        image_url: FieldOutcome::from_option(page.image_url),
+        theme_color: FieldOutcome::from_option(theme_color),
+        local_icon_path,
+        warnings,
+    })
+}
 
-    let local_icon_path = if let Some(fav_url) = favicon_url {
-        info!("Found favicon URL: {fav_url}");
-        match download_favicon(&app, &http_client, &fav_url).await {
+/// 按得分顺序下载图标，得分最高的失败时尝试下一个候选。
+///
+/// 所有候选都失败时下载 `fallback_image`（通常是 `og:image`）。
+async fn download_best_favicon(
+    app: &AppHandle,
+    http_client: &Client,
+    candidates: &[IconCandidate],
+    fallback_image: Option<&str>,
+) -> FieldOutcome<String> {
+    let mut errors = Vec::new();
+    let urls = candidates
+        .iter()
+        .take(MAX_ICON_ATTEMPTS)
+        .map(|candidate| candidate.url.as_str())
+        .chain(fallback_image);
+    for url in urls {
+        info!("Found favicon URL: {url}");
+        match download_favicon(app, http_client, url).await {
             Ok(file_name) => {
                 info!("Successfully downloaded favicon to: {file_name:?}");
-                Some(file_name)
+                return FieldOutcome::Found { value: file_name };
             }
             Err(e) => {
-                error!("Failed to download favicon from {fav_url}: {e}");
-                return Err(format!("Failed to download favicon from {fav_url}: {e}"));
+                error!("Failed to download favicon from {url}: {e}");
+                errors.push(format!("Failed to download favicon from {url}: {e}"));
             }
         }
+    }
+    if errors.is_empty() {
+        info!("No favicon URL found");
+        FieldOutcome::Missing
     } else {
-        info!("No favicon URL found for {url}");
-        return Err(format!("No favicon URL found for {url}"));
-    };
-
-    Ok(WebsiteMetadata {
-        title,
-        local_icon_path,
-    })
+        FieldOutcome::Failed {
+            error: errors.join("; "),
+        }
+    }
 }
 
 /// 请求URL并处理HTTP 3xx与HTML meta refresh重定向。
+///
+/// 允许的 403 等不影响结果的情况会追加到 `warnings` 中。
 async fn fetch_body_with_redirects(
     http_client: &Client,
     initial_url: &str,
+    warnings: &mut Vec<String>,
 ) -> Result<(String, String), String> {
     let mut current_url = initial_url.to_string();
     let mut redirect_count = 0usize;
@@ -142,11 +430,19 @@ async fn fetch_body_with_redirects(
         } else if status == StatusCode::FORBIDDEN && allow_403_for(&current_url) {
             // 特定 URL 允许 403
             warn!("Received 403 but allowed by policy {current_url}");
+            warnings.push(format!("{current_url} responded with 403 Forbidden"));
         } else {
             return Err(format!("Request failed with status: {}", status));
         }
 
-        let body = response.text().await.map_err(|e| e.to_string())?;
+        // 按 BOM、响应头和 `<meta>` 声明的编码解码，而不是只看响应头
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from);
+        let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+        let body = decode_html(&bytes, content_type.as_deref());
 
         if let Some(meta_refresh_url) = find_meta_refresh_url(&body, &current_url) {
             warn!(
@@ -218,40 +514,13 @@ fn allow_403_for(url: &str) -> bool {
     matches!(path, "/cgi-bin/luci/" | "/cgi-bin/luci/admin/status")
 }
 
-/// 在HTML文档中查找最合适的`favicon URL`
-fn find_favicon_url(document: &Html, base_url_str: &str) -> Option<String> {
-    let base_url = Url::parse(base_url_str).ok()?;
-
-    let selectors = [
-        "link[rel='icon']",
-        "link[rel='apple-touch-icon']",
-        "link[rel='shortcut icon']",
-    ];
-
-    for selector in selectors.iter() {
-        if let Some(element) = document.select(&Selector::parse(selector).unwrap()).next()
-            && let Some(href) = element.value().attr("href")
-            && !href.trim().is_empty()
-            && let Ok(absolute_url) = base_url.join(href.trim())
-        {
-            return Some(absolute_url.to_string());
-        }
-    }
-
-    // Fallback to /favicon.ico
-    base_url
-        .join("/favicon.ico")
-        .ok()
-        .map(|url| url.to_string())
-}
-
 /// 下载`favicon`并保存到本地
 async fn download_favicon(
     app: &AppHandle,
     http_client: &Client,
     url: &str,
 ) -> Result<String, String> {
-    let response = http_client
+    let mut response = http_client
         .get(url)
         .send()
         .await
@@ -264,32 +533,46 @@ async fn download_favicon(
         ));
     }
 
-    let headers = response.headers().clone();
-    let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+    // 响应头声明的大小超过上限时不再下载
+    if let Some(length) = response.content_length()
+        && length > MAX_ICON_BYTES as u64
+    {
+        return Err(format!(
+            "Icon is too large ({length} bytes, limit {MAX_ICON_BYTES})"
+        ));
+    }
+    // 分块读取，分块传输或没有 `Content-Length` 的响应超过上限时也会中止
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        if bytes.len() + chunk.len() > MAX_ICON_BYTES {
+            return Err(format!("Icon is too large (limit {MAX_ICON_BYTES} bytes)"));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    save_icon_to_dir(&app_icons_dir_path(app), &bytes)
+}
+
+/// 将图标内容保存到图标目录，返回保存后的文件名。
+///
+/// 保存前按内容校验并规范化图标（见 [`normalize_icon`]），不是图片的内容会被拒绝，
+/// 扩展名也由实际格式决定。
+///
+/// 文件名由图片内容的 SHA256 哈希值与扩展名组成，
+/// 这可以防止文件名冲突和重复存储相同内容的图片。
+pub(crate) fn save_icon_to_dir(icons_dir: &Path, bytes: &[u8]) -> Result<String, String> {
+    let NormalizedIcon { bytes, extension } = normalize_icon(bytes)?;
 
     // 基于图片内容进行哈希
     let mut hasher = Sha256::new();
-    hasher.update(&bytes); // 对下载的字节内容进行哈希
+    hasher.update(&bytes);
     let result = hasher.finalize();
     let hash_hex = hex::encode(result);
 
-    // 推断文件扩展名
-    let extension = get_extension_from_headers(headers)
-        .or_else(|| get_extension_from_url(url))
-        .unwrap_or("png");
-
     let file_name = format!("{}.{}", &hash_hex[..16], extension);
 
-    // 保存文件
-    let icons_dir = app
-        .path()
-        .home_dir()
-        .unwrap()
-        .join(HOME_VUST_DIR)
-        .join(APP_CONFIG_DIR)
-        .join("icons");
     if !icons_dir.exists() {
-        create_dir_all(&icons_dir).map_err(|e| e.to_string())?;
+        create_dir_all(icons_dir).map_err(|e| e.to_string())?;
     }
 
     let file_path = icons_dir.join(&file_name);
@@ -305,48 +588,39 @@ async fn download_favicon(
     Ok(file_name)
 }
 
-/// 从HTTP响应头中推断文件扩展名
-fn get_extension_from_headers(headers: header::HeaderMap) -> Option<&'static str> {
-    if let Some(content_type) = headers.get(header::CONTENT_TYPE)
-        && let Ok(ct) = content_type.to_str()
-    {
-        return match ct {
-            ct if ct.contains("image/png") => Some("png"),
-            ct if ct.contains("image/x-icon") | ct.contains("image/vnd.microsoft.icon") => {
-                Some("ico")
-            }
-            ct if ct.contains("image/svg+xml") => Some("svg"),
-            ct if ct.contains("image/jpeg") => Some("jpg"),
-            ct if ct.contains("image/gif") => Some("gif"),
-            _ => None,
-        };
+/// 解码 `data:image/...;base64,...` 形式的内嵌图标并保存到图标目录，返回文件名。
+///
+/// 书签的 `ICON` 属性和搜索引擎描述中的图标都使用这种形式。
+///
+/// 与 `save_uploaded_icon` 一样按内容哈希命名，相同的图标只会保存一次。
+pub(crate) fn save_data_uri_icon(data_uri: &str, icons_dir: &Path) -> Option<String> {
+    let (header, data) = data_uri.trim().strip_prefix("data:")?.split_once(',')?;
+    // 媒体类型之后可以有 `charset=utf-8` 等参数，`base64` 必须是最后一个
+    let mut params = header.split(';');
+    let mime = params.next()?.trim();
+    let is_base64 = params
+        .next_back()
+        .is_some_and(|encoding| encoding.trim().eq_ignore_ascii_case("base64"));
+    if !is_base64 || !mime.to_lowercase().starts_with("image/") {
+        return None;
+    }
+    let bytes = STANDARD.decode(data.trim()).ok()?;
+    if bytes.is_empty() {
+        return None;
     }
-    None
-}
 
-/// 从URL路径中推断文件扩展名
-fn get_extension_from_url(url: &str) -> Option<&'static str> {
-    let path = Url::parse(url).ok()?.path().to_lowercase();
-    if path.ends_with(".png") {
-        Some("png")
-    } else if path.ends_with(".ico") {
-        Some("ico")
-    } else if path.ends_with(".svg") {
-        Some("svg")
-    } else if path.ends_with(".jpg") || path.ends_with(".jpeg") {
-        Some("jpg")
-    } else if path.ends_with(".gif") {
-        Some("gif")
-    } else {
-        None
+    match save_icon_to_dir(icons_dir, &bytes) {
+        Ok(file_name) => Some(file_name),
+        Err(e) => {
+            warn!("保存内嵌图标失败: {e}");
+            None
+        }
     }
 }
 
 /// 保存用户上传的图标文件到应用的图标目录。
 ///
-/// 该函数会读取给定路径的文件内容，计算其 SHA256 哈希值，
-/// 并使用哈希值和原始扩展名重命名后保存。
-/// 这可以防止文件名冲突和重复存储相同内容的图片。
+/// 该函数会读取给定路径的文件内容，校验并规范化后使用内容哈希值重命名保存。
 #[tauri::command]
 pub async fn save_uploaded_icon(app: AppHandle, path: PathBuf) -> Result<String, String> {
     info!("正在处理上传的图标: {path:?}");
@@ -354,43 +628,6 @@ pub async fn save_uploaded_icon(app: AppHandle, path: PathBuf) -> Result<String,
     // 读取文件内容
     let bytes = fs::read(&path).map_err(|e| format!("读取上传的文件失败: {e}"))?;
 
-    // 计算文件内容的哈希值
-    let mut hasher = Sha256::new();
-    hasher.update(&bytes);
-    let result = hasher.finalize();
-    let hash_hex = hex::encode(result);
-
-    // 从原始路径获取文件扩展名
-    let extension = path
-        .extension()
-        .and_then(std::ffi::OsStr::to_str)
-        .unwrap_or("png") // 如果没有扩展名则默认为 png
-        .to_lowercase();
-
-    let file_name = format!("{}.{}", &hash_hex[..16], extension);
-
-    // 4. 获取应用图标目录的路径
-    let icons_dir = app
-        .path()
-        .home_dir()
-        .unwrap()
-        .join(HOME_VUST_DIR)
-        .join(APP_CONFIG_DIR)
-        .join("icons");
-    if !icons_dir.exists() {
-        create_dir_all(&icons_dir).map_err(|e| e.to_string())?;
-    }
-
-    let file_path = icons_dir.join(&file_name);
-
-    // 如果文件不存在，则写入文件
-    if !file_path.exists() {
-        fs::write(&file_path, &bytes).map_err(|e| e.to_string())?;
-        info!("已保存新的上传图标: {file_name}");
-    } else {
-        info!("上传的图标已存在，跳过写入: {file_name}");
-    }
-
     // 只返回文件名字符串
-    Ok(file_name)
+    save_icon_to_dir(&app_icons_dir_path(&app), &bytes)
 }
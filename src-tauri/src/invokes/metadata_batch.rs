@@ -0,0 +1,399 @@
+//! 该模块负责批量抓取网站的标题和图标，并直接写回 `websites` 表。
+//!
+//! 固定数量的工作任务从同一个队列中领取网站，同一主机的请求之间至少间隔 [`HOST_INTERVAL`]，
+//! 避免导入大量书签后同时向同一个网站发出几百个请求。每处理完一个网站就向主窗口发送
+//! `metadata-progress` 事件；`cancel_metadata_fetch` 会让尚未开始的网站不再抓取。
+
+use crate::invokes::favicon::ColorScheme;
+use crate::invokes::metadata::fetch_metadata;
+use crate::modules::db::{TableName, sqlite_pool};
+use log::{info, warn};
+use serde::Serialize;
+use sqlx::{FromRow, Pool, Sqlite};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_http::reqwest::Client;
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+/// 同时抓取的网站数量。
+const WORKER_COUNT: usize = 6;
+
+/// 同一主机两次请求之间的最小间隔。
+const HOST_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 批量抓取的共享状态：每次取消时递增的序号，用于取消正在进行的抓取。
+///
+/// 每次抓取开始时记下当前序号，序号变化后即视为已取消；
+/// 新的抓取不会重置序号，因此不会让之前已取消的抓取继续进行。
+pub struct MetadataBatchState {
+    generation: watch::Sender<u64>,
+}
+
+impl Default for MetadataBatchState {
+    fn default() -> Self {
+        Self {
+            generation: watch::Sender::new(0),
+        }
+    }
+}
+
+impl MetadataBatchState {
+    /// 开始一次抓取，返回的令牌在下次调用 [`Self::cancel`] 后失效。
+    fn begin(&self) -> CancelToken {
+        let receiver = self.generation.subscribe();
+        let generation = *receiver.borrow();
+        CancelToken {
+            receiver,
+            generation,
+        }
+    }
+
+    /// 取消所有已经开始的抓取。
+    fn cancel(&self) {
+        self.generation.send_modify(|generation| *generation += 1);
+    }
+}
+
+/// 一次批量抓取的取消令牌。
+#[derive(Clone)]
+struct CancelToken {
+    receiver: watch::Receiver<u64>,
+    generation: u64,
+}
+
+impl CancelToken {
+    fn is_cancelled(&self) -> bool {
+        *self.receiver.borrow() != self.generation
+    }
+}
+
+/// 需要抓取的网站。
+#[derive(FromRow, Debug)]
+struct WebsiteRow {
+    uuid: String,
+    title: String,
+    url: String,
+}
+
+/// 写回数据库的元数据。
+struct SavedMetadata {
+    title: Option<String>,
+    local_icon_path: Option<String>,
+    warnings: Vec<String>,
+}
+
+/// 单个网站的抓取结果。
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MetadataStatus {
+    Success,
+    Failed,
+}
+
+/// 通过 `metadata-progress` 事件发送给前端的单个网站的抓取结果。
+#[derive(Serialize, Clone, Debug)]
+pub struct MetadataProgress {
+    uuid: String,
+    url: String,
+    status: MetadataStatus,
+    /// 写回数据库后的标题和图标。
+    title: Option<String>,
+    local_icon_path: Option<String>,
+    error: Option<String>,
+    /// 成功时未能获取的字段及其原因，例如图标下载失败。
+    warnings: Vec<String>,
+    /// 已处理的网站数量（包括本条）。
+    completed: usize,
+    total: usize,
+}
+
+/// 批量抓取结束后的统计。
+#[derive(Serialize, Debug, Default)]
+pub struct MetadataBatchResult {
+    succeeded: usize,
+    failed: usize,
+    /// 因取消而没有抓取的网站数量。
+    cancelled: usize,
+}
+
+/// [Tauri Command] 批量抓取网站的标题和图标，并写回 `websites` 表。
+///
+/// 抓取到图标时更新 `local_icon_path`，并把 `icon_source` 标记为 `auto_fetched`；
+/// 只有当原标题为空或与 URL 相同（例如从纯文本导入的链接）时才用网页标题替换，
+/// 网站还没有描述和背景色时使用网页的描述和主题色。
+/// 用户上传了图标的网站不会被抓取。
+///
+/// # Arguments
+/// * `user_uuid` - 网站所属的用户。
+/// * `website_uuids` - 要抓取的网站，例如 `apply_bookmark_import` 返回的 `added_uuids`；
+///   为空时抓取该用户所有还没有本地图标的网站。
+/// * `color_scheme` - 当前界面主题（`light` 或 `dark`），用于挑选对应配色的图标。
+#[tauri::command]
+pub async fn fetch_websites_metadata(
+    app: AppHandle,
+    state: State<'_, MetadataBatchState>,
+    http_client: State<'_, Client>,
+    user_uuid: String,
+    website_uuids: Option<Vec<String>>,
+    color_scheme: Option<String>,
+) -> Result<MetadataBatchResult, String> {
+    let scheme = ColorScheme::parse(color_scheme.as_deref());
+    let cancel = state.begin();
+
+    let pool = sqlite_pool(&app).await?;
+    let websites = load_websites(&pool, &user_uuid, website_uuids).await?;
+    let total = websites.len();
+    info!("开始批量抓取 {total} 个网站的元数据");
+
+    let queue = Arc::new(Mutex::new(VecDeque::from(websites)));
+    let limiter = Arc::new(HostRateLimiter::default());
+    let completed = Arc::new(AtomicUsize::new(0));
+    let result = Arc::new(Mutex::new(MetadataBatchResult::default()));
+
+    let mut workers = tokio::task::JoinSet::new();
+    for _ in 0..WORKER_COUNT.min(total) {
+        let app = app.clone();
+        let http_client = http_client.inner().clone();
+        let pool = pool.clone();
+        let queue = queue.clone();
+        let limiter = limiter.clone();
+        let cancel = cancel.clone();
+        let completed = completed.clone();
+        let result = result.clone();
+
+        workers.spawn(async move {
+            loop {
+                if cancel.is_cancelled() {
+                    break;
+                }
+                let Some(website) = queue.lock().unwrap().pop_front() else {
+                    break;
+                };
+
+                if let Some(host) = url::Url::parse(&website.url)
+                    .ok()
+                    .and_then(|url| url.host_str().map(String::from))
+                {
+                    limiter.wait(&host).await;
+                }
+                // 等待期间可能已经取消，把网站放回队列以计入取消的数量
+                if cancel.is_cancelled() {
+                    queue.lock().unwrap().push_front(website);
+                    break;
+                }
+
+                let outcome = fetch_and_save(&app, &http_client, &pool, &website, scheme).await;
+                let completed = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                let progress = match outcome {
+                    Ok(saved) => {
+                        result.lock().unwrap().succeeded += 1;
+                        MetadataProgress {
+                            uuid: website.uuid,
+                            url: website.url,
+                            status: MetadataStatus::Success,
+                            title: saved.title,
+                            local_icon_path: saved.local_icon_path,
+                            error: None,
+                            warnings: saved.warnings,
+                            completed,
+                            total,
+                        }
+                    }
+                    Err(e) => {
+                        warn!("抓取 {} 的元数据失败: {e}", website.url);
+                        result.lock().unwrap().failed += 1;
+                        MetadataProgress {
+                            uuid: website.uuid,
+                            url: website.url,
+                            status: MetadataStatus::Failed,
+                            title: None,
+                            local_icon_path: None,
+                            error: Some(e),
+                            warnings: Vec::new(),
+                            completed,
+                            total,
+                        }
+                    }
+                };
+                if let Err(e) = app.emit_to("main", "metadata-progress", progress) {
+                    warn!("发送元数据抓取进度失败: {e}");
+                }
+            }
+        });
+    }
+    while let Some(joined) = workers.join_next().await {
+        if let Err(e) = joined {
+            warn!("元数据抓取任务异常退出: {e}");
+        }
+    }
+
+    let mut result = std::mem::take(&mut *result.lock().unwrap());
+    result.cancelled = queue.lock().unwrap().len();
+    info!("批量抓取元数据完成: {result:?}");
+    Ok(result)
+}
+
+/// 取消正在进行的批量抓取，已经开始的请求会继续完成。
+#[tauri::command]
+pub fn cancel_metadata_fetch(state: State<'_, MetadataBatchState>) {
+    state.cancel();
+}
+
+/// 读取需要抓取的网站，跳过用户上传了图标的网站。
+async fn load_websites(
+    pool: &Pool<Sqlite>,
+    user_uuid: &str,
+    website_uuids: Option<Vec<String>>,
+) -> Result<Vec<WebsiteRow>, String> {
+    let base = format!(
+        "SELECT uuid, title, url FROM {} WHERE user_uuid = ? AND is_deleted = 0
+         AND COALESCE(icon_source, '') != 'user_uploaded'",
+        TableName::WebsiteItems
+    );
+    let websites = match website_uuids {
+        Some(uuids) => {
+            // 逐个查询，避免 SQLite 对单条语句中参数数量的限制
+            let mut websites = Vec::with_capacity(uuids.len());
+            for uuid in uuids {
+                let website: Option<WebsiteRow> = sqlx::query_as(&format!("{base} AND uuid = ?"))
+                    .bind(user_uuid)
+                    .bind(&uuid)
+                    .fetch_optional(pool)
+                    .await
+                    .map_err(|e| format!("读取网站失败: {e}"))?;
+                websites.extend(website);
+            }
+            websites
+        }
+        None => sqlx::query_as(&format!(
+            "{base} AND COALESCE(local_icon_path, '') = '' ORDER BY group_uuid, sort_order"
+        ))
+        .bind(user_uuid)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("读取网站失败: {e}"))?,
+    };
+    Ok(websites)
+}
+
+/// 抓取单个网站的元数据并写回数据库，返回写回后的标题、图标以及需要提示的问题。
+///
+/// 标题和图标只要有一个可用就算成功，另一个字段的失败原因放在提示中。
+async fn fetch_and_save(
+    app: &AppHandle,
+    http_client: &Client,
+    pool: &Pool<Sqlite>,
+    website: &WebsiteRow,
+    scheme: ColorScheme,
+) -> Result<SavedMetadata, String> {
+    let metadata = fetch_metadata(app, http_client, &website.url, scheme).await?;
+    let mut warnings = metadata.errors();
+    warnings.extend(metadata.warnings);
+
+    let current_title = website.title.trim();
+    let title = metadata
+        .title
+        .into_value()
+        .filter(|title| !title.is_empty())
+        .filter(|_| current_title.is_empty() || current_title == website.url.trim());
+    let local_icon_path = metadata.local_icon_path.into_value();
+    let theme_color = metadata.theme_color.into_value();
+    let description = metadata.description.into_value();
+    if title.is_none() && local_icon_path.is_none() && !warnings.is_empty() {
+        return Err(warnings.join("; "));
+    }
+
+    sqlx::query(&format!(
+        "UPDATE {} SET
+             title = COALESCE(?, title),
+             description = COALESCE(NULLIF(description, ''), ?),
+             background_color = COALESCE(NULLIF(background_color, ''), ?),
+             local_icon_path = COALESCE(?, local_icon_path),
+             icon_source = CASE WHEN ? IS NULL THEN icon_source ELSE 'auto_fetched' END
+         WHERE uuid = ?",
+        TableName::WebsiteItems
+    ))
+    .bind(&title)
+    .bind(&description)
+    .bind(&theme_color)
+    .bind(&local_icon_path)
+    .bind(&local_icon_path)
+    .bind(&website.uuid)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("更新网站失败: {e}"))?;
+
+    Ok(SavedMetadata {
+        title,
+        local_icon_path,
+        warnings,
+    })
+}
+
+/// 按主机限制请求频率：同一主机的请求按到达顺序排队，两次请求之间至少间隔 [`HOST_INTERVAL`]。
+#[derive(Default)]
+struct HostRateLimiter {
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostRateLimiter {
+    /// 为 `host` 预约下一个可用的时间点并等待到该时间点。
+    async fn wait(&self, host: &str) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let now = Instant::now();
+            let slot = next_slot.get(host).map_or(now, |next| (*next).max(now));
+            next_slot.insert(host.to_string(), slot + HOST_INTERVAL);
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_only_affects_started_runs() {
+        let state = MetadataBatchState::default();
+        let first = state.begin();
+        state.cancel();
+        // 新的抓取不会让已取消的抓取继续
+        let second = state.begin();
+        assert!(first.is_cancelled());
+        assert!(!second.is_cancelled());
+
+        state.cancel();
+        assert!(second.is_cancelled());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limiter_spaces_requests_per_host() {
+        let limiter = HostRateLimiter::default();
+        let start = Instant::now();
+        let elapsed = |host: &'static str| {
+            let limiter = &limiter;
+            async move {
+                limiter.wait(host).await;
+                start.elapsed()
+            }
+        };
+
+        let (a1, a2, b1, a3) = tokio::join!(
+            elapsed("a.example"),
+            elapsed("a.example"),
+            elapsed("b.example"),
+            elapsed("a.example"),
+        );
+        assert_eq!(a1, Duration::ZERO);
+        assert!(a2 >= HOST_INTERVAL);
+        assert!(a3 - a2 >= HOST_INTERVAL);
+        // 其他主机不受影响
+        assert_eq!(b1, Duration::ZERO);
+    }
+}
@@ -1,4 +1,16 @@
+pub mod bookmark_export;
+pub mod bookmark_import;
 pub mod bookmark_parser;
 pub mod browser;
+pub mod favicon;
+pub mod html_charset;
+pub mod icon_image;
 pub mod metadata;
+pub mod metadata_batch;
+pub mod search_engine_import;
+pub mod search_engine_parser;
+pub mod search_resolver;
+pub mod search_suggest;
+pub mod search_template;
 pub mod sync;
+pub mod web_manifest;
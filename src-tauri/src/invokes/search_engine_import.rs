@@ -0,0 +1,113 @@
+//! 该模块负责把解析出的搜索引擎写入 `search_engines` 表。
+
+use crate::invokes::search_engine_parser::ImportedSearchEngine;
+use crate::invokes::search_resolver::normalize_keyword;
+use crate::modules::db::{TableName, sqlite_pool};
+use log::info;
+use serde::Serialize;
+use std::collections::HashSet;
+use tauri::AppHandle;
+
+/// 导入的搜索引擎在没有图标时使用的默认图标，与前端新增搜索引擎时的默认值一致。
+const DEFAULT_ICON: &str = "IoSearchOutline";
+
+/// 导入搜索引擎后的统计。
+#[derive(Serialize, Debug, Default)]
+pub struct SearchEngineImportResult {
+    added_engines: usize,
+    skipped_engines: usize,
+}
+
+/// [Tauri Command] 在一个事务中导入搜索引擎。
+///
+/// 与已有搜索引擎同名或 URL 模板相同的会被跳过；新增的搜索引擎追加到列表末尾，不会成为默认引擎。
+///
+/// # Arguments
+/// * `user_uuid` - 导入的目标用户。
+/// * `engines` - `auto_search_engine_parser` 等命令解析出、经用户勾选的搜索引擎。
+#[tauri::command]
+pub async fn import_search_engines(
+    app: AppHandle,
+    user_uuid: String,
+    engines: Vec<ImportedSearchEngine>,
+) -> Result<SearchEngineImportResult, String> {
+    let pool = sqlite_pool(&app).await?;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let mut result = SearchEngineImportResult::default();
+
+    // 表上的 `UNIQUE(user_uuid, name)` 也覆盖已软删除的记录
+    let existing: Vec<(String, String, Option<String>, i64)> = sqlx::query_as(&format!(
+        "SELECT name, url_template, keyword, is_deleted FROM {} WHERE user_uuid = ?",
+        TableName::SearchEngines
+    ))
+    .bind(&user_uuid)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("读取搜索引擎失败: {e}"))?;
+    let mut names: HashSet<String> = existing
+        .iter()
+        .map(|(name, _, _, _)| name.clone())
+        .collect();
+    let live = existing
+        .into_iter()
+        .filter(|(_, _, _, is_deleted)| *is_deleted == 0);
+    let mut templates = HashSet::new();
+    let mut keywords = HashSet::new();
+    for (_, template, keyword, _) in live {
+        templates.insert(template);
+        keywords.extend(keyword.as_deref().map(normalize_keyword));
+    }
+
+    let mut next_order: i64 = sqlx::query_scalar(&format!(
+        "SELECT COALESCE(MAX(sort_order), -1) + 1 FROM {} WHERE user_uuid = ? AND is_deleted = 0",
+        TableName::SearchEngines
+    ))
+    .bind(&user_uuid)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| format!("读取搜索引擎排序失败: {e}"))?;
+
+    for engine in engines {
+        if engine.name.is_empty()
+            || names.contains(&engine.name)
+            || templates.contains(&engine.url_template)
+        {
+            result.skipped_engines += 1;
+            continue;
+        }
+
+        // 关键字已被其他搜索引擎使用时不保留，避免同一个关键字对应多个引擎
+        let keyword = engine
+            .keyword
+            .filter(|keyword| keywords.insert(normalize_keyword(keyword)));
+
+        sqlx::query(&format!(
+            "INSERT INTO {} (uuid, user_uuid, name, url_template, suggest_url_template, keyword, default_icon, local_icon_path, sort_order)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            TableName::SearchEngines
+        ))
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&user_uuid)
+        .bind(&engine.name)
+        .bind(&engine.url_template)
+        .bind(&engine.suggest_url_template)
+        .bind(&keyword)
+        .bind(DEFAULT_ICON)
+        .bind(&engine.local_icon_path)
+        .bind(next_order)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("添加搜索引擎 {} 失败: {e}", engine.name))?;
+
+        next_order += 1;
+        result.added_engines += 1;
+        names.insert(engine.name);
+        templates.insert(engine.url_template);
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("提交导入事务失败: {e}"))?;
+    info!("搜索引擎导入完成: {result:?}");
+    Ok(result)
+}
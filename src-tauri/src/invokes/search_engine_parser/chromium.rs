@@ -0,0 +1,73 @@
+//! 解析 Chromium 系浏览器配置目录中的 `Web Data` 数据库。
+//!
+//! 搜索引擎保存在 `keywords` 表中：`short_name` 为名称，`keyword` 为地址栏关键字，
+//! `url` 和 `suggest_url` 为带 `{searchTerms}` 占位符的搜索和搜索建议模板。浏览器运行时会锁定数据库，因此先复制一份再读取。
+
+use super::{ImportedSearchEngine, to_url_template};
+use crate::utils::copy_database_to_temp;
+use log::warn;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::{Connection, FromRow};
+use std::fs;
+use std::path::Path;
+
+/// `keywords` 表中的一行记录。
+#[derive(FromRow, Debug)]
+struct KeywordRow {
+    short_name: String,
+    keyword: String,
+    url: String,
+    suggest_url: Option<String>,
+}
+
+/// 读取并解析 `Web Data` 中的搜索引擎。
+pub(super) async fn parse_file(path: &Path) -> Result<Vec<ImportedSearchEngine>, String> {
+    let copy_dir = copy_database_to_temp(path)?;
+    let result = read_engines(&copy_dir.join(path.file_name().unwrap_or_default())).await;
+    if let Err(e) = fs::remove_dir_all(&copy_dir) {
+        warn!("清理 Web Data 临时副本失败 {copy_dir:?}: {e}");
+    }
+    result
+}
+
+/// 以只读方式打开数据库副本并读取 `keywords` 表。
+async fn read_engines(db_path: &Path) -> Result<Vec<ImportedSearchEngine>, String> {
+    let options = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true);
+    let mut conn = SqliteConnection::connect_with(&options)
+        .await
+        .map_err(|e| format!("打开 Web Data 失败: {e}"))?;
+
+    // 浏览器从访问过的网站中自动收集的搜索引擎 `safe_for_autoreplace = 1` 且不是内置的
+    let rows: Vec<KeywordRow> = sqlx::query_as(
+        "SELECT short_name, keyword, url, suggest_url FROM keywords
+         WHERE NOT (safe_for_autoreplace = 1 AND prepopulate_id = 0)
+         ORDER BY prepopulate_id = 0 DESC, id",
+    )
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| format!("读取 keywords 失败: {e}"))?;
+
+    let _ = conn.close().await;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let url_template = to_url_template(&row.url, &[])?;
+            let keyword = row.keyword.trim();
+            Some(ImportedSearchEngine {
+                name: row.short_name.trim().to_string(),
+                url_template,
+                suggest_url_template: row
+                    .suggest_url
+                    .as_deref()
+                    .and_then(|suggest_url| to_url_template(suggest_url, &[])),
+                // 没有设置关键字的引擎以域名作为关键字，这不是真正的快捷关键字
+                keyword: (!keyword.is_empty() && !keyword.contains('.'))
+                    .then(|| keyword.to_string()),
+                local_icon_path: None,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,200 @@
+//! 解析 Firefox 配置目录中的 `search.json.mozlz4`。
+//!
+//! 文件以 `mozLz40\0` 魔数和 4 字节小端序的解压后长度开头，其后是一个 LZ4 数据块，
+//! 解压后是保存所有搜索引擎的 JSON。Firefox 内置的搜索引擎由远程配置提供、文件中没有 URL，
+//! 因此只有用户添加的和通过网站安装的搜索引擎会被导入。
+
+use super::{ImportedSearchEngine, MOZLZ4_MAGIC, SUGGESTIONS_TYPE, to_url_template};
+use crate::invokes::metadata::save_data_uri_icon;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// 解压后长度的上限。正常的 `search.json` 只有几百 KB，超出上限的文件视为损坏。
+const MAX_DECOMPRESSED_SIZE: usize = 32 * 1024 * 1024;
+
+/// `search.json` 的顶层结构。
+#[derive(Deserialize, Debug)]
+struct SearchConfig {
+    #[serde(default)]
+    engines: Vec<Engine>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Engine {
+    #[serde(rename = "_name", default)]
+    name: String,
+    #[serde(rename = "_metaData", default)]
+    meta_data: MetaData,
+    #[serde(rename = "_urls", default)]
+    urls: Vec<EngineUrl>,
+    #[serde(rename = "_definedAliases", default)]
+    defined_aliases: Vec<String>,
+    #[serde(rename = "_iconURL")]
+    icon_url: Option<String>,
+    /// 较新版本以尺寸为键保存多个图标。
+    #[serde(rename = "_iconMapObj", default)]
+    icon_map: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+struct MetaData {
+    /// 用户设置的关键字。
+    alias: Option<String>,
+    /// 用户是否在设置中隐藏了该引擎。
+    #[serde(default)]
+    hidden: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct EngineUrl {
+    #[serde(default)]
+    template: String,
+    /// 结果类型，缺省为 `text/html`；搜索建议为 `application/x-suggestions+json`。
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    method: Option<String>,
+    #[serde(default)]
+    params: Vec<EngineParam>,
+}
+
+/// 追加到查询字符串中的参数，带有 `condition` 的参数只在特定场景下使用，没有 `value`。
+#[derive(Deserialize, Debug)]
+struct EngineParam {
+    name: String,
+    value: Option<String>,
+}
+
+/// 读取并解析 `search.json.mozlz4`，图标保存到 `icons_dir` 中。
+pub(super) fn parse_file(
+    path: &Path,
+    icons_dir: &Path,
+) -> Result<Vec<ImportedSearchEngine>, String> {
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    let json = decompress_mozlz4(&bytes)?;
+    let config: SearchConfig =
+        serde_json::from_slice(&json).map_err(|e| format!("解析 search.json 失败: {e}"))?;
+
+    Ok(config
+        .engines
+        .into_iter()
+        .filter(|engine| !engine.meta_data.hidden)
+        .filter_map(|engine| {
+            let find_url = |kind: &str| {
+                engine.urls.iter().find(|url| {
+                    url.kind.as_deref().unwrap_or("text/html") == kind
+                        && url
+                            .method
+                            .as_deref()
+                            .is_none_or(|method| method.eq_ignore_ascii_case("get"))
+                })
+            };
+            let url_template = template_with_params(find_url("text/html")?)?;
+            let suggest_url_template = find_url(SUGGESTIONS_TYPE).and_then(template_with_params);
+
+            let keyword = engine
+                .meta_data
+                .alias
+                .iter()
+                .chain(engine.defined_aliases.iter())
+                .map(|alias| alias.trim())
+                .find(|alias| !alias.is_empty())
+                .map(String::from);
+            // 优先使用尺寸最大的图标
+            let mut sized_icons: Vec<(u32, &String)> = engine
+                .icon_map
+                .iter()
+                .map(|(size, icon)| (size.parse().unwrap_or_default(), icon))
+                .collect();
+            sized_icons.sort_by_key(|(size, _)| std::cmp::Reverse(*size));
+            let local_icon_path = sized_icons
+                .into_iter()
+                .map(|(_, icon)| icon)
+                .chain(engine.icon_url.iter())
+                .find_map(|icon| save_data_uri_icon(icon, icons_dir));
+
+            Some(ImportedSearchEngine {
+                name: engine.name.trim().to_string(),
+                url_template,
+                suggest_url_template,
+                keyword,
+                local_icon_path,
+            })
+        })
+        .collect())
+}
+
+/// 把 `_urls` 中的一项转换为 `%s` 模板，只保留有固定值的参数。
+fn template_with_params(url: &EngineUrl) -> Option<String> {
+    let params: Vec<(String, String)> = url
+        .params
+        .iter()
+        .filter_map(|param| Some((param.name.clone(), param.value.clone()?)))
+        .collect();
+    to_url_template(&url.template, &params)
+}
+
+/// 解压 Mozilla 的 `mozlz4` 格式。
+fn decompress_mozlz4(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let body = bytes
+        .strip_prefix(MOZLZ4_MAGIC)
+        .ok_or_else(|| "不是有效的 mozlz4 文件".to_string())?;
+    let (size, block) = body
+        .split_first_chunk::<4>()
+        .ok_or_else(|| "mozlz4 文件不完整".to_string())?;
+    let size = u32::from_le_bytes(*size) as usize;
+    // 解压前会按声明的长度分配内存，不能直接信任文件头
+    if size > MAX_DECOMPRESSED_SIZE {
+        return Err(format!(
+            "mozlz4 文件声明的解压后长度过大: {size} 字节（上限 {MAX_DECOMPRESSED_SIZE} 字节）"
+        ));
+    }
+    lz4_flex::block::decompress(block, size).map_err(|e| format!("解压 mozlz4 失败: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mozlz4(size: u32, block: &[u8]) -> Vec<u8> {
+        let mut bytes = MOZLZ4_MAGIC.to_vec();
+        bytes.extend_from_slice(&size.to_le_bytes());
+        bytes.extend_from_slice(block);
+        bytes
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = br#"{"engines":[{"_name":"Example","_urls":[]}]}"#;
+        let bytes = mozlz4(json.len() as u32, &lz4_flex::block::compress(json));
+        assert_eq!(decompress_mozlz4(&bytes).unwrap(), json);
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let json = b"{}";
+        let mut bytes = mozlz4(json.len() as u32, &lz4_flex::block::compress(json));
+        bytes[0] = b'M';
+        assert_eq!(
+            decompress_mozlz4(&bytes).unwrap_err(),
+            "不是有效的 mozlz4 文件"
+        );
+        assert_eq!(
+            decompress_mozlz4(MOZLZ4_MAGIC).unwrap_err(),
+            "mozlz4 文件不完整"
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_length() {
+        let json = b"{}";
+        let size = MAX_DECOMPRESSED_SIZE as u32 + 1;
+        let bytes = mozlz4(size, &lz4_flex::block::compress(json));
+        assert!(
+            decompress_mozlz4(&bytes)
+                .unwrap_err()
+                .starts_with("mozlz4 文件声明的解压后长度过大")
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! 该模块负责从 OpenSearch 描述文件和浏览器配置中导入搜索引擎。
+//!
+//! 各种来源的 URL 模板都使用 OpenSearch 的 `{searchTerms}` 等占位符，
+//! 这里统一转换为启动台使用的 `%s` 语法，再由前端选择后通过 `import_search_engines` 写入数据库。
+
+mod chromium;
+mod firefox;
+mod opensearch;
+
+use crate::utils::app_icons_dir_path;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// Firefox `search.json.mozlz4` 文件头部的魔数。
+const MOZLZ4_MAGIC: &[u8] = b"mozLz40\0";
+
+/// OpenSearch Suggestions 接口的结果类型。
+const SUGGESTIONS_TYPE: &str = "application/x-suggestions+json";
+
+/// 从外部来源解析出的一个搜索引擎。
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ImportedSearchEngine {
+    /// 搜索引擎的名称。
+    pub(crate) name: String,
+    /// 使用 `%s` 作为查询占位符的搜索 URL 模板。
+    pub(crate) url_template: String,
+    /// 使用 `%s` 作为查询占位符的搜索建议（OpenSearch Suggestions）地址模板。
+    #[serde(default)]
+    pub(crate) suggest_url_template: Option<String>,
+    /// 在地址栏中触发该搜索引擎的关键字（Chromium 的 `keyword`、Firefox 的别名）。
+    pub(crate) keyword: Option<String>,
+    /// 内嵌图标保存到图标目录后的文件名。
+    pub(crate) local_icon_path: Option<String>,
+}
+
+/// 可以导入的搜索引擎来源格式。
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchEngineFormat {
+    /// OpenSearch 描述文件（XML）。
+    OpenSearch,
+    /// Chromium 系浏览器的 `Web Data` 数据库。
+    Chromium,
+    /// Firefox 的 `search.json.mozlz4`。
+    Firefox,
+}
+
+/// 解析 OpenSearch 描述文件，内嵌的图标会被保存到图标目录中。
+///
+/// # Arguments
+/// * `path` - 指向 OpenSearch 描述 XML 的路径。
+#[tauri::command]
+pub async fn opensearch_parser(
+    app: AppHandle,
+    path: PathBuf,
+) -> Result<Vec<ImportedSearchEngine>, String> {
+    let engine = opensearch::parse_file(&path, &app_icons_dir_path(&app))?;
+    Ok(vec![engine])
+}
+
+/// 读取 Chromium 系浏览器配置目录中的 `Web Data` 数据库，返回其中的搜索引擎。
+///
+/// 浏览器自动从访问过的网站中收集的搜索引擎会被忽略，只保留内置的和用户手动添加的。
+///
+/// # Arguments
+/// * `path` - 指向 `Web Data` 文件的路径。
+#[tauri::command]
+pub async fn chromium_search_engine_parser(
+    path: PathBuf,
+) -> Result<Vec<ImportedSearchEngine>, String> {
+    Ok(dedupe(chromium::parse_file(&path).await?))
+}
+
+/// 解析 Firefox 配置目录中的 `search.json.mozlz4`，返回用户没有隐藏的搜索引擎。
+///
+/// # Arguments
+/// * `path` - 指向 `search.json.mozlz4` 文件的路径。
+#[tauri::command]
+pub async fn firefox_search_engine_parser(
+    app: AppHandle,
+    path: PathBuf,
+) -> Result<Vec<ImportedSearchEngine>, String> {
+    let engines = firefox::parse_file(&path, &app_icons_dir_path(&app))?;
+    Ok(dedupe(engines))
+}
+
+/// 自动识别文件格式并解析其中的搜索引擎。
+///
+/// # Arguments
+/// * `path` - 指向 OpenSearch 描述文件、Chromium `Web Data` 或 Firefox `search.json.mozlz4` 的路径。
+#[tauri::command]
+pub async fn auto_search_engine_parser(
+    app: AppHandle,
+    path: PathBuf,
+) -> Result<Vec<ImportedSearchEngine>, String> {
+    let format = detect_format(&path)?;
+    info!("识别到搜索引擎文件格式 {format:?}: {path:?}");
+
+    match format {
+        SearchEngineFormat::OpenSearch => opensearch_parser(app, path).await,
+        SearchEngineFormat::Chromium => chromium_search_engine_parser(path).await,
+        SearchEngineFormat::Firefox => firefox_search_engine_parser(app, path).await,
+    }
+}
+
+/// 根据文件头部判断搜索引擎文件的格式。
+fn detect_format(path: &Path) -> Result<SearchEngineFormat, String> {
+    let mut head = Vec::new();
+    File::open(path)
+        .and_then(|file| file.take(16).read_to_end(&mut head))
+        .map_err(|e| e.to_string())?;
+
+    if head.starts_with(b"SQLite format 3\0") {
+        Ok(SearchEngineFormat::Chromium)
+    } else if head.starts_with(MOZLZ4_MAGIC) {
+        Ok(SearchEngineFormat::Firefox)
+    } else {
+        Ok(SearchEngineFormat::OpenSearch)
+    }
+}
+
+/// 按 URL 模板去重，保留最先出现的搜索引擎。
+fn dedupe(engines: Vec<ImportedSearchEngine>) -> Vec<ImportedSearchEngine> {
+    let mut seen = HashSet::new();
+    engines
+        .into_iter()
+        .filter(|engine| seen.insert(engine.url_template.clone()))
+        .collect()
+}
+
+/// 把 OpenSearch 风格的 URL 模板转换为 `%s` 语法，`params` 中的参数会追加到查询字符串中。
+///
+/// `{searchTerms}` 替换为 `%s`，编码类参数固定为 `UTF-8`，Chromium 的 `{google:baseURL}`
+/// 替换为 Google 的地址、`{google:suggestClient}` 替换为 `firefox`；其余占位符（如 `{language?}`、`{google:RLZ}`）替换为空，
+/// 替换后值为空的查询参数会被整个去掉。
+/// 结果不是 `http(s)` 地址或不包含 `%s` 时返回 `None`。
+pub(super) fn to_url_template(template: &str, params: &[(String, String)]) -> Option<String> {
+    let template = template.trim();
+    let (base, query) = match template.split_once('?') {
+        Some((base, query)) => (base, query),
+        None => (template, ""),
+    };
+
+    let pairs: Vec<String> = query
+        .split('&')
+        .map(str::to_string)
+        .chain(params.iter().map(|(name, value)| format!("{name}={value}")))
+        .filter_map(|pair| {
+            let replaced = replace_placeholders(&pair);
+            // 可选参数替换后为空时整个参数一起去掉
+            let emptied = pair.contains('{')
+                && replaced
+                    .split_once('=')
+                    .is_none_or(|(_, value)| value.is_empty());
+            (!replaced.is_empty() && !emptied).then_some(replaced)
+        })
+        .collect();
+
+    let mut url = replace_placeholders(base);
+    if !pairs.is_empty() {
+        url.push('?');
+        url.push_str(&pairs.join("&"));
+    }
+
+    let is_web = url.starts_with("http://") || url.starts_with("https://");
+    (is_web && url.contains("%s")).then_some(url)
+}
+
+/// 替换文本中的 `{name}` 占位符。
+fn replace_placeholders(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let name = rest[start + 1..start + len].trim_end_matches('?');
+        result.push_str(match name {
+            "searchTerms" => "%s",
+            "inputEncoding" | "outputEncoding" => "UTF-8",
+            "google:baseURL" => "https://www.google.com/",
+            // 以 Firefox 的身份请求时，Google 的建议接口返回标准的 OpenSearch Suggestions JSON
+            "google:suggestClient" => "firefox",
+            _ => "",
+        });
+        rest = &rest[start + len + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_terms_become_placeholder() {
+        assert_eq!(
+            to_url_template("https://duckduckgo.com/?q={searchTerms}", &[]).as_deref(),
+            Some("https://duckduckgo.com/?q=%s")
+        );
+        assert_eq!(
+            to_url_template(
+                "https://example.com/search",
+                &[
+                    ("q".to_string(), "{searchTerms}".to_string()),
+                    ("ie".to_string(), "{inputEncoding}".to_string()),
+                ]
+            )
+            .as_deref(),
+            Some("https://example.com/search?q=%s&ie=UTF-8")
+        );
+    }
+
+    #[test]
+    fn optional_params_are_dropped() {
+        assert_eq!(
+            to_url_template(
+                "https://example.com/?q={searchTerms}&page={startPage?}&hl={language?}&src=os",
+                &[]
+            )
+            .as_deref(),
+            Some("https://example.com/?q=%s&src=os")
+        );
+        assert_eq!(
+            to_url_template("{google:baseURL}search?q={searchTerms}&{google:RLZ}", &[]).as_deref(),
+            Some("https://www.google.com/search?q=%s")
+        );
+    }
+
+    #[test]
+    fn templates_without_search_terms_are_rejected() {
+        assert_eq!(to_url_template("https://example.com/?q=rust", &[]), None);
+        assert_eq!(
+            to_url_template("https://example.com/?q={language}", &[]),
+            None
+        );
+        assert_eq!(
+            to_url_template("ftp://example.com/?q={searchTerms}", &[]),
+            None
+        );
+    }
+}
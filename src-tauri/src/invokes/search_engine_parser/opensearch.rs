@@ -0,0 +1,163 @@
+//! 解析 OpenSearch 描述文件（网站通过 `<link rel="search">` 提供的 XML）。
+//!
+//! `<ShortName>` 作为名称；在多个 `<Url>` 中优先选择 `type="text/html"` 且使用 GET 的那一个，
+//! 其 `<Param>` 子节点会追加到查询字符串中；`type="application/x-suggestions+json"` 的 `<Url>` 作为搜索建议地址；`<Image>` 中内嵌的 `data:` 图标会被保存到图标目录。
+
+use super::{ImportedSearchEngine, SUGGESTIONS_TYPE, to_url_template};
+use crate::invokes::metadata::save_data_uri_icon;
+use quick_xml::Reader;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use std::fs;
+use std::path::Path;
+
+/// 描述文件中的一个 `<Url>`。
+#[derive(Default, Debug)]
+struct UrlElement {
+    kind: String,
+    method: String,
+    template: String,
+    params: Vec<(String, String)>,
+}
+
+/// 正在收集文本的元素。
+enum Capture {
+    ShortName,
+    Image,
+}
+
+/// 读取并解析 OpenSearch 描述文件。
+pub(super) fn parse_file(path: &Path, icons_dir: &Path) -> Result<ImportedSearchEngine, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut reader = Reader::from_str(&content);
+
+    let mut short_name = String::new();
+    let mut images: Vec<String> = Vec::new();
+    let mut urls: Vec<UrlElement> = Vec::new();
+    let mut in_url = false;
+    let mut capture: Option<Capture> = None;
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"ShortName" => capture = Some(Capture::ShortName),
+                b"Image" => capture = Some(Capture::Image),
+                b"Url" => {
+                    urls.push(url_element(&e));
+                    in_url = true;
+                }
+                b"Param" if in_url => push_param(&mut urls, &e),
+                _ => {}
+            },
+            Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                b"Url" => urls.push(url_element(&e)),
+                b"Param" if in_url => push_param(&mut urls, &e),
+                _ => {}
+            },
+            Ok(Event::Text(e)) if capture.is_some() => {
+                let decoded = e
+                    .decode()
+                    .map_err(|e| format!("解析 OpenSearch 失败: {e}"))?;
+                text.push_str(&decoded);
+            }
+            // 文本中的 `&amp;` 等实体会被单独作为一个事件返回
+            Ok(Event::GeneralRef(e)) if capture.is_some() => {
+                if let Ok(Some(c)) = e.resolve_char_ref() {
+                    text.push(c);
+                } else if let Ok(name) = e.decode()
+                    && let Some(value) = resolve_predefined_entity(&name)
+                {
+                    text.push_str(value);
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"Url" => in_url = false,
+                b"ShortName" | b"Image" => {
+                    let value = std::mem::take(&mut text).trim().to_string();
+                    match capture.take() {
+                        Some(Capture::ShortName) => short_name = value,
+                        Some(Capture::Image) => images.push(value),
+                        None => {}
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("解析 OpenSearch 失败: {e}")),
+            _ => {}
+        }
+    }
+
+    let is_get =
+        |url: &&UrlElement| url.method.is_empty() || url.method.eq_ignore_ascii_case("get");
+    let url = urls
+        .iter()
+        .filter(is_get)
+        .find(|url| url.kind.eq_ignore_ascii_case("text/html"))
+        .or_else(|| urls.iter().filter(is_get).find(|url| url.kind.is_empty()))
+        .ok_or_else(|| "OpenSearch 描述中没有可用于网页搜索的 <Url>".to_string())?;
+    let url_template = to_url_template(&url.template, &url.params)
+        .ok_or_else(|| format!("无法识别的搜索地址模板: {}", url.template))?;
+    let suggest_url_template = urls
+        .iter()
+        .filter(is_get)
+        .filter(|url| url.kind.eq_ignore_ascii_case(SUGGESTIONS_TYPE))
+        .find_map(|url| to_url_template(&url.template, &url.params));
+
+    let name = short_name.split_whitespace().collect::<Vec<_>>().join(" ");
+    let name = if name.is_empty() {
+        url::Url::parse(&url_template)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from))
+            .unwrap_or_default()
+    } else {
+        name
+    };
+
+    Ok(ImportedSearchEngine {
+        name,
+        url_template,
+        suggest_url_template,
+        keyword: None,
+        local_icon_path: images
+            .iter()
+            .find_map(|image| save_data_uri_icon(image, icons_dir)),
+    })
+}
+
+/// 读取 `<Url>` 的属性。
+fn url_element(element: &BytesStart) -> UrlElement {
+    let mut url = UrlElement::default();
+    for attr in element.attributes().flatten() {
+        let Ok(value) = attr.unescape_value() else {
+            continue;
+        };
+        match attr.key.local_name().as_ref() {
+            b"type" => url.kind = value.trim().to_string(),
+            b"method" => url.method = value.trim().to_string(),
+            b"template" => url.template = value.trim().to_string(),
+            _ => {}
+        }
+    }
+    url
+}
+
+/// 把 `<Param name value>` 加入最近的 `<Url>`。
+fn push_param(urls: &mut [UrlElement], element: &BytesStart) {
+    let mut name = None;
+    let mut value = None;
+    for attr in element.attributes().flatten() {
+        let Ok(attr_value) = attr.unescape_value() else {
+            continue;
+        };
+        match attr.key.local_name().as_ref() {
+            b"name" => name = Some(attr_value.trim().to_string()),
+            b"value" => value = Some(attr_value.trim().to_string()),
+            _ => {}
+        }
+    }
+    if let (Some(name), Some(value), Some(url)) = (name, value, urls.last_mut()) {
+        url.params.push((name, value));
+    }
+}
@@ -0,0 +1,163 @@
+//! 该模块负责解析启动台搜索框中的输入，根据关键字选择搜索引擎并生成最终的搜索地址。
+//!
+//! 支持两种写法：任意位置的 `!gh rust` 形式的 bang，以及开头的 `gh rust` 形式的关键字。
+//! 没有匹配到关键字时使用用户的默认搜索引擎（`is_default = 1`）。搜索地址由 [`search_template`] 生成。
+//!
+//! [`search_template`]: crate::invokes::search_template
+
+use crate::invokes::search_template::{
+    EngineTemplate, SearchRequest, TemplateContext, build_request,
+};
+use crate::modules::db::{TableName, sqlite_pool};
+use serde::Serialize;
+use sqlx::{FromRow, Pool, Sqlite};
+use tauri::AppHandle;
+
+/// `search_engines` 表中参与解析的字段。
+#[derive(FromRow, Debug)]
+pub(crate) struct EngineRow {
+    pub(crate) uuid: String,
+    pub(crate) name: String,
+    pub(crate) url_template: String,
+    pub(crate) suggest_url_template: Option<String>,
+    pub(crate) keyword: Option<String>,
+    pub(crate) method: Option<String>,
+    pub(crate) post_body: Option<String>,
+    pub(crate) encoding: Option<String>,
+    pub(crate) is_default: i64,
+}
+
+/// 解析后的搜索。
+#[derive(Serialize, Debug)]
+pub struct ResolvedSearch {
+    /// 要打开的搜索请求。
+    #[serde(flatten)]
+    request: SearchRequest,
+    engine_uuid: String,
+    engine_name: String,
+    /// 命中的关键字；使用默认搜索引擎时为空。
+    keyword: Option<String>,
+    /// 去掉关键字之后的搜索词。
+    query: String,
+}
+
+/// [Tauri Command] 解析搜索框输入，返回要打开的搜索地址。
+///
+/// 用户既没有命中关键字、也没有设置默认搜索引擎时返回 `None`，由前端使用内置搜索引擎。
+///
+/// # Arguments
+/// * `user_uuid` - 当前用户。
+/// * `query` - 搜索框中的原始输入。
+/// * `lang` - 界面语言，用于 `{lang}` 占位符。
+/// * `selection` - 当前选中的文本，用于 `{selection}` 占位符。
+#[tauri::command]
+pub async fn resolve_search_query(
+    app: AppHandle,
+    user_uuid: String,
+    query: String,
+    lang: Option<String>,
+    selection: Option<String>,
+) -> Result<Option<ResolvedSearch>, String> {
+    let pool = sqlite_pool(&app).await?;
+    let engines = load_engines(&pool, &user_uuid).await?;
+    let (engine, keyword, term) = select_engine(&query, &engines);
+    let Some(engine) = engine else {
+        return Ok(None);
+    };
+    let request = build_request(
+        &EngineTemplate {
+            url_template: &engine.url_template,
+            method: engine.method.as_deref(),
+            post_body: engine.post_body.as_deref(),
+            encoding: engine.encoding.as_deref(),
+        },
+        &TemplateContext {
+            query: term.clone(),
+            lang,
+            selection,
+        },
+    )
+    .map_err(|e| format!("搜索引擎 {} 的模板无效: {e}", engine.name))?;
+
+    Ok(Some(ResolvedSearch {
+        request,
+        engine_uuid: engine.uuid.clone(),
+        engine_name: engine.name.clone(),
+        keyword,
+        query: term,
+    }))
+}
+
+/// 读取用户未删除的搜索引擎。
+pub(crate) async fn load_engines(
+    pool: &Pool<Sqlite>,
+    user_uuid: &str,
+) -> Result<Vec<EngineRow>, String> {
+    sqlx::query_as(&format!(
+        "SELECT uuid, name, url_template, suggest_url_template, keyword, method, post_body, encoding, is_default
+         FROM {} WHERE user_uuid = ? AND is_deleted = 0
+         ORDER BY sort_order",
+        TableName::SearchEngines
+    ))
+    .bind(user_uuid)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("读取搜索引擎失败: {e}"))
+}
+
+/// 根据输入选择搜索引擎，返回搜索引擎、命中的关键字和去掉关键字后的搜索词。
+///
+/// 没有命中关键字时使用默认搜索引擎；用户没有设置默认搜索引擎时搜索引擎为 `None`。
+pub(crate) fn select_engine<'a>(
+    query: &str,
+    engines: &'a [EngineRow],
+) -> (Option<&'a EngineRow>, Option<String>, String) {
+    match match_keyword(query, engines) {
+        Some((engine, term)) => (Some(engine), engine.keyword.clone(), term),
+        None => (
+            engines.iter().find(|engine| engine.is_default == 1),
+            None,
+            query.trim().to_string(),
+        ),
+    }
+}
+
+/// 统一关键字的写法：忽略大小写以及保存时带上的 `!` 前缀。
+pub(crate) fn normalize_keyword(keyword: &str) -> String {
+    keyword.trim().trim_start_matches('!').to_lowercase()
+}
+
+/// 在输入中查找关键字，返回对应的搜索引擎和去掉关键字后的搜索词。
+///
+/// 先查找任意位置的 `!bang`，再检查第一个词是否为关键字；后者要求之后还有搜索词，
+/// 避免单独搜索一个恰好与关键字相同的词时被误判。
+fn match_keyword<'a>(query: &str, engines: &'a [EngineRow]) -> Option<(&'a EngineRow, String)> {
+    let find_engine = |token: &str| {
+        let token = normalize_keyword(token);
+        engines.iter().find(|engine| {
+            engine
+                .keyword
+                .as_deref()
+                .map(normalize_keyword)
+                .is_some_and(|keyword| !keyword.is_empty() && keyword == token)
+        })
+    };
+
+    let tokens: Vec<&str> = query.split_whitespace().collect();
+    for (index, token) in tokens.iter().enumerate() {
+        if let Some(bang) = token.strip_prefix('!')
+            && !bang.is_empty()
+            && let Some(engine) = find_engine(bang)
+        {
+            let mut rest = tokens.clone();
+            rest.remove(index);
+            return Some((engine, rest.join(" ")));
+        }
+    }
+
+    let (first, rest) = query.trim().split_once(char::is_whitespace)?;
+    if first.starts_with('!') {
+        return None;
+    }
+    find_engine(first).map(|engine| (engine, rest.trim().to_string()))
+}
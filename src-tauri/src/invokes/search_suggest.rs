@@ -0,0 +1,534 @@
+//! 该模块为启动台搜索框提供搜索建议。
+//!
+//! 远程建议来自搜索引擎的 OpenSearch Suggestions 接口（`suggest_url_template`），
+//! 响应为 `["查询", ["建议1", "建议2", ...], ...]` 形式的 JSON；本地建议来自 `websites` 表的模糊匹配。
+//!
+//! 每次输入都会调用一次命令：命令先等待一小段时间，期间或请求过程中有更新的输入到达时直接放弃，
+//! 被放弃的调用返回 `None`，前端忽略即可。
+
+use crate::invokes::search_resolver::{load_engines, normalize_keyword, select_engine};
+use crate::invokes::search_template::{
+    EngineTemplate, TemplateContext, build_request, resolve_encoding,
+};
+use crate::modules::db::{TableName, sqlite_pool};
+use encoding_rs::Encoding;
+use log::{debug, warn};
+use serde::Serialize;
+use sqlx::FromRow;
+use std::time::Duration;
+use tauri::{AppHandle, State};
+use tauri_plugin_http::reqwest::Client;
+use tokio::sync::watch;
+
+/// 输入停止多久后才开始请求。
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// 远程建议的请求超时时间。
+const SUGGEST_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// 最多返回的本地网站数量。
+const MAX_WEBSITES: usize = 5;
+
+/// 最多返回的建议总数。
+const MAX_SUGGESTIONS: usize = 10;
+
+/// 搜索建议的共享状态：每次请求递增的序号，用于放弃过期的请求。
+pub struct SearchSuggestState {
+    generation: watch::Sender<u64>,
+}
+
+impl Default for SearchSuggestState {
+    fn default() -> Self {
+        Self {
+            generation: watch::Sender::new(0),
+        }
+    }
+}
+
+/// 一条搜索建议。
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Suggestion {
+    /// 本地保存的网站，选中后直接打开。
+    Website {
+        uuid: String,
+        title: String,
+        url: String,
+        default_icon: Option<String>,
+        local_icon_path: Option<String>,
+    },
+    /// 搜索引擎返回的搜索词。
+    Search {
+        text: String,
+        /// 选中后用于搜索的完整输入，保留了原输入中的关键字。
+        query: String,
+    },
+}
+
+/// `websites` 表中参与匹配的字段。
+#[derive(FromRow, Debug)]
+struct WebsiteRow {
+    uuid: String,
+    title: String,
+    url: String,
+    default_icon: Option<String>,
+    local_icon_path: Option<String>,
+}
+
+/// [Tauri Command] 获取搜索建议，本地网站排在前面，其后是搜索引擎的建议。
+///
+/// 有更新的输入到达时返回 `None`。远程请求失败或超时只记录日志，仍然返回本地匹配结果。
+///
+/// # Arguments
+/// * `user_uuid` - 当前用户。
+/// * `query` - 搜索框中的原始输入，可以带有关键字。
+/// * `lang` - 界面语言，用于 `{lang}` 占位符。
+/// * `fallback_suggest_url_template` - 用户没有设置默认搜索引擎时，前端内置搜索引擎的建议地址模板。
+#[tauri::command]
+pub async fn fetch_search_suggestions(
+    app: AppHandle,
+    state: State<'_, SearchSuggestState>,
+    http_client: State<'_, Client>,
+    user_uuid: String,
+    query: String,
+    lang: Option<String>,
+    fallback_suggest_url_template: Option<String>,
+) -> Result<Option<Vec<Suggestion>>, String> {
+    let generation = {
+        state.generation.send_modify(|generation| *generation += 1);
+        *state.generation.borrow()
+    };
+    let mut newer = state.generation.subscribe();
+    let superseded = async move {
+        // 发送端保存在状态中，不会被关闭
+        let _ = newer.wait_for(|current| *current != generation).await;
+    };
+
+    let suggestions = async {
+        tokio::time::sleep(DEBOUNCE).await;
+        collect_suggestions(
+            &app,
+            &http_client,
+            &user_uuid,
+            &query,
+            lang,
+            fallback_suggest_url_template,
+        )
+        .await
+    };
+
+    tokio::select! {
+        _ = superseded => {
+            debug!("搜索建议已被更新的输入取代: {query}");
+            Ok(None)
+        }
+        suggestions = suggestions => suggestions.map(Some),
+    }
+}
+
+/// 查询本地网站并请求远程建议，合并后返回。
+async fn collect_suggestions(
+    app: &AppHandle,
+    http_client: &Client,
+    user_uuid: &str,
+    query: &str,
+    lang: Option<String>,
+    fallback_suggest_url_template: Option<String>,
+) -> Result<Vec<Suggestion>, String> {
+    let pool = sqlite_pool(app).await?;
+    let engines = load_engines(&pool, user_uuid).await?;
+    let (engine, keyword, term) = select_engine(query, &engines);
+    if term.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let websites: Vec<WebsiteRow> = sqlx::query_as(&format!(
+        "SELECT uuid, title, url, default_icon, local_icon_path FROM {}
+         WHERE user_uuid = ? AND is_deleted = 0",
+        TableName::WebsiteItems
+    ))
+    .bind(user_uuid)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| format!("读取网站失败: {e}"))?;
+    let websites = match_websites(&term, websites);
+
+    let (suggest_template, encoding) = match engine {
+        Some(engine) => (engine.suggest_url_template.clone(), engine.encoding.clone()),
+        None => (fallback_suggest_url_template, None),
+    };
+    let remote = match suggest_template.filter(|template| !template.trim().is_empty()) {
+        Some(template) => {
+            let context = TemplateContext {
+                query: term.clone(),
+                lang,
+                selection: None,
+            };
+            let engine = EngineTemplate {
+                url_template: &template,
+                method: None,
+                post_body: None,
+                encoding: encoding.as_deref(),
+            };
+            match build_request(&engine, &context) {
+                Ok(request) => {
+                    fetch_remote_suggestions(http_client, &request.url, encoding.as_deref())
+                        .await
+                        .unwrap_or_else(|e| {
+                            warn!("获取搜索建议失败 {}: {e}", request.url);
+                            Vec::new()
+                        })
+                }
+                Err(e) => {
+                    warn!("搜索建议地址模板无效 {template}: {e}");
+                    Vec::new()
+                }
+            }
+        }
+        None => Vec::new(),
+    };
+
+    // 以 bang 的形式保留关键字，使选中的建议仍然由同一个搜索引擎搜索
+    let prefix = keyword
+        .map(|keyword| format!("!{} ", normalize_keyword(&keyword)))
+        .unwrap_or_default();
+    Ok(merge_suggestions(&term, &prefix, websites, remote))
+}
+
+/// 请求 OpenSearch Suggestions 接口，返回其中的建议词。
+///
+/// 响应按 `encoding` 解码（为空时为 UTF-8），以兼容返回 GBK 等编码的接口。
+pub(crate) async fn fetch_remote_suggestions(
+    http_client: &Client,
+    url: &str,
+    encoding: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let response = http_client
+        .get(url)
+        .timeout(SUGGEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| {
+            if e.is_timeout() {
+                format!("请求超时（{} 毫秒）", SUGGEST_TIMEOUT.as_millis())
+            } else {
+                format!("请求失败: {e}")
+            }
+        })?;
+    if !response.status().is_success() {
+        return Err(format!("响应状态 {}", response.status()));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("读取响应失败: {e}"))?;
+
+    let encoding: &'static Encoding = resolve_encoding(encoding)?;
+    let (body, _, _) = encoding.decode(&bytes);
+    parse_open_suggestions(&body)
+}
+
+/// 解析 `["查询", ["建议1", "建议2"], ...]` 形式的响应。
+fn parse_open_suggestions(body: &str) -> Result<Vec<String>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(body.trim()).map_err(|e| format!("解析建议失败: {e}"))?;
+    let suggestions = value
+        .get(1)
+        .and_then(|suggestions| suggestions.as_array())
+        .ok_or_else(|| "响应不是 OpenSearch Suggestions 格式".to_string())?;
+    Ok(suggestions
+        .iter()
+        .filter_map(|suggestion| suggestion.as_str())
+        .map(|suggestion| suggestion.trim().to_string())
+        .filter(|suggestion| !suggestion.is_empty())
+        .collect())
+}
+
+/// 按模糊匹配的得分筛选本地网站，得分相同时保持原有顺序。
+fn match_websites(term: &str, websites: Vec<WebsiteRow>) -> Vec<WebsiteRow> {
+    let mut matched: Vec<(i64, WebsiteRow)> = websites
+        .into_iter()
+        .filter_map(|website| {
+            let score = fuzzy_score(&website.title, term)
+                .into_iter()
+                .chain(fuzzy_score(&website.url, term).map(|score| score / 2))
+                .max()?;
+            Some((score, website))
+        })
+        .collect();
+    matched.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    matched
+        .into_iter()
+        .take(MAX_WEBSITES)
+        .map(|(_, website)| website)
+        .collect()
+}
+
+/// 计算 `pattern` 在 `text` 中的模糊匹配得分，不匹配时返回 `None`。
+///
+/// 忽略大小写；前缀匹配得分最高，其次是子串匹配，再次是按顺序出现的子序列匹配，
+/// 子序列中连续的字符和出现在单词开头的字符会加分。
+fn fuzzy_score(text: &str, pattern: &str) -> Option<i64> {
+    let text = text.to_lowercase();
+    let pattern = pattern.to_lowercase();
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return None;
+    }
+    if text.starts_with(pattern) {
+        return Some(1000 - text.chars().count() as i64);
+    }
+    if let Some(index) = text.find(pattern) {
+        return Some(500 - index as i64);
+    }
+
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+    let mut chars = text.chars().enumerate();
+    let mut last_char = ' ';
+    for wanted in pattern.chars().filter(|c| !c.is_whitespace()) {
+        loop {
+            let (index, c) = chars.next()?;
+            let at_word_start = !last_char.is_alphanumeric();
+            last_char = c;
+            if c == wanted {
+                score += 1;
+                if previous.is_some_and(|previous| previous + 1 == index) {
+                    score += 5;
+                }
+                if at_word_start {
+                    score += 3;
+                }
+                previous = Some(index);
+                break;
+            }
+        }
+    }
+    Some(score)
+}
+
+/// 合并本地网站和远程建议：去掉与输入相同或重复的建议，总数不超过 [`MAX_SUGGESTIONS`]。
+fn merge_suggestions(
+    term: &str,
+    prefix: &str,
+    websites: Vec<WebsiteRow>,
+    remote: Vec<String>,
+) -> Vec<Suggestion> {
+    let mut seen = vec![term.to_lowercase()];
+    let mut suggestions: Vec<Suggestion> = websites
+        .into_iter()
+        .map(|website| Suggestion::Website {
+            uuid: website.uuid,
+            title: website.title,
+            url: website.url,
+            default_icon: website.default_icon,
+            local_icon_path: website.local_icon_path,
+        })
+        .collect();
+
+    for text in remote {
+        if suggestions.len() >= MAX_SUGGESTIONS {
+            break;
+        }
+        let key = text.to_lowercase();
+        if !seen.contains(&key) {
+            seen.push(key);
+            suggestions.push(Suggestion::Search {
+                query: format!("{prefix}{text}"),
+                text,
+            });
+        }
+    }
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 启动只响应一次请求的本地服务器，等待 `delay` 后返回 `body`，返回请求地址。
+    async fn stub_server(body: &'static [u8], delay: Duration) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).await.unwrap();
+                if read == 0 {
+                    return;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+            tokio::time::sleep(delay).await;
+            let header = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let _ = stream.write_all(header.as_bytes()).await;
+            let _ = stream.write_all(body).await;
+        });
+        format!("http://{addr}/complete?q=rust")
+    }
+
+    fn website(uuid: &str, title: &str, url: &str) -> WebsiteRow {
+        WebsiteRow {
+            uuid: uuid.to_string(),
+            title: title.to_string(),
+            url: url.to_string(),
+            default_icon: None,
+            local_icon_path: None,
+        }
+    }
+
+    fn search(text: &str, query: &str) -> Suggestion {
+        Suggestion::Search {
+            text: text.to_string(),
+            query: query.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn parses_open_suggestions() {
+        let url = stub_server(
+            br#"["rust", ["rust lang", " rustup ", "", 42, "rust book"], [], []]"#,
+            Duration::ZERO,
+        )
+        .await;
+        let suggestions = fetch_remote_suggestions(&Client::new(), &url, None)
+            .await
+            .unwrap();
+        assert_eq!(suggestions, ["rust lang", "rustup", "rust book"]);
+    }
+
+    #[tokio::test]
+    async fn decodes_with_engine_encoding() {
+        // GBK 编码的 `["中", ["中文"]]`
+        let url = stub_server(b"[\"\xd6\xd0\", [\"\xd6\xd0\xce\xc4\"]]", Duration::ZERO).await;
+        let suggestions = fetch_remote_suggestions(&Client::new(), &url, Some("gbk"))
+            .await
+            .unwrap();
+        assert_eq!(suggestions, ["中文"]);
+    }
+
+    #[tokio::test]
+    async fn times_out_slow_servers() {
+        let url = stub_server(br#"["rust", ["rust lang"]]"#, Duration::from_secs(3)).await;
+        let error = fetch_remote_suggestions(&Client::new(), &url, None)
+            .await
+            .unwrap_err();
+        assert!(error.contains("超时"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn rejects_non_json_body() {
+        let url = stub_server(b"<html>Service unavailable</html>", Duration::ZERO).await;
+        let error = fetch_remote_suggestions(&Client::new(), &url, None)
+            .await
+            .unwrap_err();
+        assert!(error.starts_with("解析建议失败"), "{error}");
+    }
+
+    #[test]
+    fn rejects_json_without_suggestion_list() {
+        assert!(parse_open_suggestions(r#"{"q": "rust"}"#).is_err());
+        assert!(parse_open_suggestions(r#"["rust"]"#).is_err());
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_prefix_then_substring_then_subsequence() {
+        let prefix = fuzzy_score("GitHub", "git").unwrap();
+        let substring = fuzzy_score("My GitHub", "git").unwrap();
+        let subsequence = fuzzy_score("Gitea Tracker", "gtt").unwrap();
+        assert!(prefix > substring);
+        assert!(substring > subsequence);
+        // 忽略大小写，空白不参与子序列匹配
+        assert_eq!(
+            fuzzy_score("GITHUB", "github"),
+            fuzzy_score("github", "GitHub")
+        );
+        assert!(fuzzy_score("Grafana Dashboards", "gr db").is_some());
+    }
+
+    #[test]
+    fn fuzzy_score_rewards_word_starts_and_runs() {
+        let word_starts = fuzzy_score("home assistant", "ha").unwrap();
+        let scattered = fuzzy_score("hxxxaxxx", "ha").unwrap();
+        assert!(word_starts > scattered);
+        let run = fuzzy_score("xabx", "ab").unwrap();
+        let gap = fuzzy_score("xaxb", "ab").unwrap();
+        assert!(run > gap);
+    }
+
+    #[test]
+    fn fuzzy_score_rejects_non_matches() {
+        assert_eq!(fuzzy_score("GitHub", "gitlab"), None);
+        assert_eq!(fuzzy_score("GitHub", "   "), None);
+    }
+
+    #[test]
+    fn match_websites_orders_by_score_and_limits() {
+        let websites = vec![
+            website("1", "My Grafana", "https://grafana.example.com"),
+            website("2", "Grafana", "https://example.com/grafana"),
+            website("3", "Wiki", "https://wiki.example.com"),
+        ];
+        let matched: Vec<String> = match_websites("grafana", websites)
+            .into_iter()
+            .map(|website| website.uuid)
+            .collect();
+        assert_eq!(matched, ["2", "1"]);
+
+        let many = (0..MAX_WEBSITES + 3)
+            .map(|i| website(&i.to_string(), "Grafana", "https://grafana.example.com"))
+            .collect();
+        assert_eq!(match_websites("grafana", many).len(), MAX_WEBSITES);
+    }
+
+    #[test]
+    fn merge_puts_websites_first_and_dedups_remote() {
+        let suggestions = merge_suggestions(
+            "Rust",
+            "!ddg ",
+            vec![website("1", "Rust", "https://www.rust-lang.org")],
+            vec![
+                "rust".to_string(),
+                "rust lang".to_string(),
+                "Rust Lang".to_string(),
+                "rustup".to_string(),
+            ],
+        );
+        assert_eq!(
+            suggestions,
+            [
+                Suggestion::Website {
+                    uuid: "1".to_string(),
+                    title: "Rust".to_string(),
+                    url: "https://www.rust-lang.org".to_string(),
+                    default_icon: None,
+                    local_icon_path: None,
+                },
+                search("rust lang", "!ddg rust lang"),
+                search("rustup", "!ddg rustup"),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_limits_total_suggestions() {
+        let remote = (0..MAX_SUGGESTIONS * 2)
+            .map(|i| format!("rust {i}"))
+            .collect();
+        let suggestions = merge_suggestions(
+            "rust",
+            "",
+            vec![website("1", "Rust", "https://www.rust-lang.org")],
+            remote,
+        );
+        assert_eq!(suggestions.len(), MAX_SUGGESTIONS);
+        assert_eq!(suggestions[1], search("rust 0", "rust 0"));
+    }
+}
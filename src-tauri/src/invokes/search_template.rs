@@ -0,0 +1,555 @@
+//! 该模块实现搜索引擎的地址模板：解析与校验模板，并按搜索词生成 GET 地址或 POST 表单。
+//!
+//! 模板中可以使用以下占位符，`{{` 和 `}}` 分别表示字面量 `{` 和 `}`：
+//! - `{query}`（兼容旧写法 `%s` 与 OpenSearch 的 `{searchTerms}`）：搜索词；
+//! - `{lang}`：界面语言，例如 `zh`；
+//! - `{date}`：本地日期，格式为 `YYYY-MM-DD`；
+//! - `{selection}`：当前选中的文本，没有选中时使用搜索词。
+//!
+//! 地址中的占位符按搜索引擎的字符编码（默认 UTF-8，也可以是 GBK 等）进行百分号编码；
+//! POST 请求的表单字段不做编码，由浏览器按 `accept-charset` 提交。
+
+use crate::utils::escape_html;
+use encoding_rs::{Encoding, UTF_8};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::{Duration, SystemTime};
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+/// 旧版模板中的搜索词占位符。
+const LEGACY_PLACEHOLDER: &str = "%s";
+
+/// 自动提交表单的临时文件保留时长，超过后在下次提交时清理。
+const FORM_FILE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// 模板中可用的占位符。
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Placeholder {
+    Query,
+    Lang,
+    Date,
+    Selection,
+}
+
+impl Placeholder {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "query" | "searchterms" => Some(Self::Query),
+            "lang" => Some(Self::Lang),
+            "date" => Some(Self::Date),
+            "selection" => Some(Self::Selection),
+            _ => None,
+        }
+    }
+}
+
+/// 表单模板中的一个字段：字段名和值。
+type FormField = (Vec<Segment>, Vec<Segment>);
+
+/// 模板解析后的片段。
+#[derive(PartialEq, Debug)]
+enum Segment {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// 搜索请求的方式。
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SearchMethod {
+    Get,
+    Post,
+}
+
+impl SearchMethod {
+    /// 解析数据库中的 `method` 列，为空时为 GET。
+    fn parse(method: Option<&str>) -> Result<Self, String> {
+        match method.map(str::trim).filter(|method| !method.is_empty()) {
+            None => Ok(Self::Get),
+            Some(method) if method.eq_ignore_ascii_case("get") => Ok(Self::Get),
+            Some(method) if method.eq_ignore_ascii_case("post") => Ok(Self::Post),
+            Some(method) => Err(format!("不支持的请求方式: {method}")),
+        }
+    }
+}
+
+/// 搜索引擎中与请求相关的字段，对应 `search_engines` 表中的同名列。
+#[derive(Debug)]
+pub(crate) struct EngineTemplate<'a> {
+    pub(crate) url_template: &'a str,
+    pub(crate) method: Option<&'a str>,
+    pub(crate) post_body: Option<&'a str>,
+    pub(crate) encoding: Option<&'a str>,
+}
+
+/// 填充占位符时使用的值。
+#[derive(Debug, Default)]
+pub(crate) struct TemplateContext {
+    pub(crate) query: String,
+    pub(crate) lang: Option<String>,
+    pub(crate) selection: Option<String>,
+}
+
+impl TemplateContext {
+    fn value(&self, placeholder: Placeholder) -> String {
+        match placeholder {
+            Placeholder::Query => self.query.clone(),
+            Placeholder::Lang => self.lang.clone().unwrap_or_else(|| "en".to_string()),
+            Placeholder::Date => chrono::Local::now().format("%Y-%m-%d").to_string(),
+            Placeholder::Selection => self
+                .selection
+                .clone()
+                .filter(|selection| !selection.trim().is_empty())
+                .unwrap_or_else(|| self.query.clone()),
+        }
+    }
+}
+
+/// 生成的搜索请求。GET 请求直接打开 `url`；POST 请求通过 `open_search_form` 打开。
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SearchRequest {
+    pub(crate) url: String,
+    pub(crate) method: SearchMethod,
+    /// POST 表单字段，GET 请求时为空。
+    #[serde(default)]
+    pub(crate) fields: Vec<(String, String)>,
+    /// 提交表单时使用的字符编码。
+    pub(crate) encoding: String,
+}
+
+/// [Tauri Command] 保存搜索引擎前校验模板、请求方式和字符编码。
+///
+/// # Arguments
+/// * `url_template` - 搜索地址模板。
+/// * `method` - `GET` 或 `POST`，为空时为 `GET`。
+/// * `post_body` - POST 请求的表单，例如 `q={query}&lang={lang}`。
+/// * `encoding` - 字符编码，例如 `GBK`，为空时为 UTF-8。
+/// * `suggest_url_template` - 搜索建议地址模板，可以为空。
+#[tauri::command]
+pub fn validate_search_template(
+    url_template: String,
+    method: Option<String>,
+    post_body: Option<String>,
+    encoding: Option<String>,
+    suggest_url_template: Option<String>,
+) -> Result<(), String> {
+    let engine = EngineTemplate {
+        url_template: &url_template,
+        method: method.as_deref(),
+        post_body: post_body.as_deref(),
+        encoding: encoding.as_deref(),
+    };
+    let request = build_request(
+        &engine,
+        &TemplateContext {
+            query: "test".to_string(),
+            ..Default::default()
+        },
+    )?;
+
+    validate_http_url(&request.url).map_err(|e| format!("无效的搜索地址: {e}"))?;
+
+    if let Some(suggest_url_template) = suggest_url_template
+        .as_deref()
+        .filter(|template| !template.trim().is_empty())
+    {
+        let suggest = build_request(
+            &EngineTemplate {
+                url_template: suggest_url_template,
+                method: None,
+                post_body: None,
+                encoding: encoding.as_deref(),
+            },
+            &TemplateContext {
+                query: "test".to_string(),
+                ..Default::default()
+            },
+        )
+        .map_err(|e| format!("无效的搜索建议地址: {e}"))?;
+        validate_http_url(&suggest.url).map_err(|e| format!("无效的搜索建议地址: {e}"))?;
+    }
+
+    let has_query = |template: &str| {
+        parse_template(template).is_ok_and(|segments| {
+            segments.iter().any(|segment| {
+                matches!(
+                    segment,
+                    Segment::Placeholder(Placeholder::Query | Placeholder::Selection)
+                )
+            })
+        })
+    };
+    if request.method == SearchMethod::Post
+        && !has_query(&url_template)
+        && !post_body.as_deref().is_some_and(has_query)
+    {
+        return Err("POST 搜索引擎的地址或表单中必须包含 {query} 或 {selection}".to_string());
+    }
+    Ok(())
+}
+
+/// 检查地址是否为有效的 http(s) 地址。
+fn validate_http_url(url: &str) -> Result<(), String> {
+    let url = url::Url::parse(url).map_err(|e| e.to_string())?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("地址必须以 http:// 或 https:// 开头".to_string());
+    }
+    Ok(())
+}
+
+/// [Tauri Command] 在浏览器中提交 POST 搜索请求。
+///
+/// 浏览器无法直接打开 POST 请求，因此先在临时目录中生成一个加载后自动提交的表单页面，再用浏览器打开它。
+///
+/// # Arguments
+/// * `request` - `resolve_search_query` 返回的搜索请求。
+/// * `browser` - 打开链接使用的浏览器，为空时使用系统默认浏览器。
+#[tauri::command]
+pub fn open_search_form(
+    app: AppHandle,
+    request: SearchRequest,
+    browser: Option<String>,
+) -> Result<(), String> {
+    let forms_dir = std::env::temp_dir().join("vust-search-forms");
+    fs::create_dir_all(&forms_dir).map_err(|e| format!("创建表单目录失败: {e}"))?;
+    remove_expired_forms(&forms_dir);
+
+    let form_path = forms_dir.join(format!("{}.html", uuid::Uuid::new_v4()));
+    fs::write(&form_path, render_form(&request)).map_err(|e| format!("写入表单失败: {e}"))?;
+    let form_url = url::Url::from_file_path(&form_path)
+        .map_err(|_| format!("无效的表单路径: {form_path:?}"))?;
+
+    app.opener()
+        .open_url(
+            form_url.as_str(),
+            browser.filter(|browser| browser != "default"),
+        )
+        .map_err(|e| format!("打开搜索页面失败: {e}"))
+}
+
+/// 按模板生成搜索请求。
+pub(crate) fn build_request(
+    engine: &EngineTemplate,
+    context: &TemplateContext,
+) -> Result<SearchRequest, String> {
+    let method = SearchMethod::parse(engine.method)?;
+    let encoding = resolve_encoding(engine.encoding)?;
+
+    let mut url_segments = parse_template(engine.url_template.trim())?;
+    if url_segments.is_empty() {
+        return Err("搜索地址不能为空".to_string());
+    }
+    // 与旧版行为一致：GET 模板中没有任何占位符时，把搜索词追加到末尾
+    if method == SearchMethod::Get
+        && !url_segments
+            .iter()
+            .any(|segment| matches!(segment, Segment::Placeholder(_)))
+    {
+        url_segments.push(Segment::Placeholder(Placeholder::Query));
+    }
+
+    let fields = match method {
+        SearchMethod::Get => Vec::new(),
+        SearchMethod::Post => parse_form(engine.post_body.unwrap_or_default())?
+            .into_iter()
+            .map(|(name, value)| (render(&name, context), render(&value, context)))
+            .collect(),
+    };
+
+    Ok(SearchRequest {
+        url: render_url(&url_segments, context, encoding),
+        method,
+        fields,
+        encoding: encoding.name().to_string(),
+    })
+}
+
+/// 查找字符编码，为空时为 UTF-8。
+pub(crate) fn resolve_encoding(label: Option<&str>) -> Result<&'static Encoding, String> {
+    match label.map(str::trim).filter(|label| !label.is_empty()) {
+        None => Ok(UTF_8),
+        Some(label) => Encoding::for_label(label.as_bytes())
+            .ok_or_else(|| format!("不支持的字符编码: {label}")),
+    }
+}
+
+/// 把模板拆分为字面量和占位符。
+fn parse_template(template: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '{' if chars.next_if(|(_, next)| *next == '{').is_some() => literal.push('{'),
+            '}' if chars.next_if(|(_, next)| *next == '}').is_some() => literal.push('}'),
+            '{' => {
+                let rest = &template[index + 1..];
+                let end = rest
+                    .find('}')
+                    .ok_or_else(|| format!("模板中的 {{ 没有闭合: {template}"))?;
+                let name = &rest[..end];
+                let placeholder = Placeholder::from_name(name).ok_or_else(|| {
+                    format!(
+                        "未知的占位符 {{{name}}}，可用的占位符有 {{query}}、{{lang}}、{{date}}、{{selection}}"
+                    )
+                })?;
+                // 跳过占位符名称和右括号
+                for _ in 0..=name.chars().count() {
+                    chars.next();
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Placeholder(placeholder));
+            }
+            '}' => return Err(format!("模板中有多余的 }}: {template}")),
+            '%' if template[index..].starts_with(LEGACY_PLACEHOLDER) => {
+                chars.next();
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Placeholder(Placeholder::Query));
+            }
+            _ => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+/// 渲染模板，占位符的值不做编码。
+fn render(segments: &[Segment], context: &TemplateContext) -> String {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Literal(literal) => literal.clone(),
+            Segment::Placeholder(placeholder) => context.value(*placeholder),
+        })
+        .collect()
+}
+
+/// 渲染搜索地址。
+///
+/// 位于查询字符串中的占位符按 `application/x-www-form-urlencoded` 编码，
+/// 位于路径或片段中的占位符把空格编码为 `%20`，避免 `+` 被当作字面量。
+fn render_url(
+    segments: &[Segment],
+    context: &TemplateContext,
+    encoding: &'static Encoding,
+) -> String {
+    let mut url = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Literal(literal) => url.push_str(literal),
+            Segment::Placeholder(placeholder) => {
+                let in_query = url.contains('?') && !url.contains('#');
+                let value = context.value(*placeholder);
+                let (bytes, _, _) = encoding.encode(&value);
+                let encoded: String = url::form_urlencoded::byte_serialize(&bytes).collect();
+                if in_query {
+                    url.push_str(&encoded);
+                } else {
+                    url.push_str(&encoded.replace('+', "%20"));
+                }
+            }
+        }
+    }
+    url
+}
+
+/// 解析 POST 表单模板：字段之间以 `&` 或换行分隔，字段名与值以 `=` 分隔，字面量部分可以使用百分号编码。
+fn parse_form(body: &str) -> Result<Vec<FormField>, String> {
+    body.split(['&', '\n'])
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            if name.trim().is_empty() {
+                return Err(format!("表单字段缺少名称: {pair}"));
+            }
+            Ok((decode_form_part(name.trim())?, decode_form_part(value)?))
+        })
+        .collect()
+}
+
+/// 解析表单模板中的一个部分，并对其中的字面量进行百分号解码。
+fn decode_form_part(part: &str) -> Result<Vec<Segment>, String> {
+    Ok(parse_template(part)?
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Literal(literal) => Segment::Literal(
+                url::form_urlencoded::parse(format!("x={literal}").as_bytes())
+                    .next()
+                    .map(|(_, value)| value.into_owned())
+                    .unwrap_or(literal),
+            ),
+            placeholder => placeholder,
+        })
+        .collect())
+}
+
+/// 生成加载后自动提交的表单页面。
+fn render_form(request: &SearchRequest) -> String {
+    let inputs: String = request
+        .fields
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                r#"<input type="hidden" name="{}" value="{}">"#,
+                escape_html(name),
+                escape_html(value)
+            )
+        })
+        .collect();
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>Search</title></head>
+<body onload="document.forms[0].submit()">
+<form method="post" action="{}" accept-charset="{}">{inputs}<noscript><button type="submit">Search</button></noscript></form>
+</body>
+</html>
+"#,
+        escape_html(&request.url),
+        escape_html(&request.encoding)
+    )
+}
+
+/// 删除过期的表单页面；页面被浏览器加载后就不再需要。
+fn remove_expired_forms(forms_dir: &std::path::Path) {
+    let Ok(entries) = fs::read_dir(forms_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age > FORM_FILE_TTL);
+        if expired && let Err(e) = fs::remove_file(entry.path()) {
+            warn!("删除过期的搜索表单失败 {:?}: {e}", entry.path());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_url(url_template: &str, encoding: Option<&str>, query: &str) -> Result<String, String> {
+        let engine = EngineTemplate {
+            url_template,
+            method: None,
+            post_body: None,
+            encoding,
+        };
+        let context = TemplateContext {
+            query: query.to_string(),
+            ..Default::default()
+        };
+        build_request(&engine, &context).map(|request| request.url)
+    }
+
+    #[test]
+    fn legacy_placeholder() {
+        assert_eq!(
+            get_url("https://www.google.com/search?q=%s", None, "rust lang").unwrap(),
+            "https://www.google.com/search?q=rust+lang"
+        );
+        // 没有占位符时把搜索词追加到末尾
+        assert_eq!(
+            get_url("https://www.google.com/search?q=", None, "a&b").unwrap(),
+            "https://www.google.com/search?q=a%26b"
+        );
+    }
+
+    #[test]
+    fn query_in_path_and_query_string() {
+        assert_eq!(
+            get_url("https://example.com/wiki/{query}", None, "rust lang").unwrap(),
+            "https://example.com/wiki/rust%20lang"
+        );
+        assert_eq!(
+            get_url("https://example.com/s?q={query}#{query}", None, "a b").unwrap(),
+            "https://example.com/s?q=a+b#a%20b"
+        );
+        assert_eq!(
+            get_url("https://example.com/s?q={searchTerms}", None, "a b").unwrap(),
+            "https://example.com/s?q=a+b"
+        );
+    }
+
+    #[test]
+    fn escaped_braces_are_literals() {
+        assert_eq!(
+            get_url("https://example.com/{{query}}?q={query}", None, "rust").unwrap(),
+            "https://example.com/{query}?q=rust"
+        );
+    }
+
+    #[test]
+    fn encodes_with_engine_encoding() {
+        assert_eq!(
+            get_url("https://www.baidu.com/s?wd={query}", Some("GBK"), "中文").unwrap(),
+            "https://www.baidu.com/s?wd=%D6%D0%CE%C4"
+        );
+        assert_eq!(
+            get_url("https://www.baidu.com/s?wd={query}", None, "中文").unwrap(),
+            "https://www.baidu.com/s?wd=%E4%B8%AD%E6%96%87"
+        );
+        assert!(get_url("https://example.com/?q={query}", Some("klingon"), "x").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_placeholders() {
+        let unknown = get_url("https://example.com/?q={foo}", None, "x").unwrap_err();
+        assert!(unknown.contains("{foo}"), "{unknown}");
+        let unclosed = get_url("https://example.com/?q={query", None, "x").unwrap_err();
+        assert!(unclosed.contains("没有闭合"), "{unclosed}");
+        assert!(get_url("https://example.com/?q=}", None, "x").is_err());
+    }
+
+    #[test]
+    fn post_form_fields() {
+        let engine = EngineTemplate {
+            url_template: "https://example.com/search",
+            method: Some("post"),
+            post_body: Some("q={query}&x=a%20b\nlang={lang}"),
+            encoding: Some("gbk"),
+        };
+        let context = TemplateContext {
+            query: "中文 搜索".to_string(),
+            lang: Some("zh".to_string()),
+            ..Default::default()
+        };
+        let request = build_request(&engine, &context).unwrap();
+        assert_eq!(request.method, SearchMethod::Post);
+        assert_eq!(request.url, "https://example.com/search");
+        assert_eq!(request.encoding, "GBK");
+        assert_eq!(
+            request.fields,
+            [
+                ("q".to_string(), "中文 搜索".to_string()),
+                ("x".to_string(), "a b".to_string()),
+                ("lang".to_string(), "zh".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_method() {
+        let engine = EngineTemplate {
+            url_template: "https://example.com/?q={query}",
+            method: Some("PUT"),
+            post_body: None,
+            encoding: None,
+        };
+        assert!(build_request(&engine, &TemplateContext::default()).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+//! 该模块负责读取网页通过 `<link rel="manifest">` 声明的 Web App Manifest。
+//!
+//! Grafana、Home Assistant、Nextcloud 等自托管应用往往只在 Manifest 中提供高清图标，
+//! `<title>` 也常常是 “Loading…” 这类占位文字，此时 Manifest 中的 `short_name`/`name` 更准确。
+//! 字段类型不符合规范时只忽略该字段，不影响其他字段。
+
+use crate::invokes::favicon::{ColorScheme, IconCandidate, IconDeclaration};
+use scraper::{Html, Selector};
+use serde_json::Value;
+use std::time::Duration;
+use tauri_plugin_http::reqwest::Client;
+use url::Url;
+
+/// 请求 Manifest 的超时时间。
+const MANIFEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 只能作为遮罩使用的图标（`purpose: "maskable"`）四周留有安全边距，显示时偏小。
+const MASKABLE_PENALTY: i64 = 40;
+
+/// Manifest 中与网站相关的字段。
+#[derive(Debug, Default)]
+pub(crate) struct WebManifest {
+    name: Option<String>,
+    short_name: Option<String>,
+    theme_color: Option<String>,
+    icons: Vec<ManifestIcon>,
+}
+
+/// `icons[]` 中的一项。
+#[derive(Debug)]
+struct ManifestIcon {
+    src: String,
+    sizes: Option<String>,
+    mime: Option<String>,
+    purpose: Option<String>,
+}
+
+/// 查找页面中声明的 Manifest 地址。
+pub(crate) fn find_manifest_url(document: &Html, base_url: &str) -> Option<String> {
+    let base_url = Url::parse(base_url).ok()?;
+    let selector = Selector::parse("link[rel][href]").unwrap();
+    document
+        .select(&selector)
+        .filter(|element| {
+            element.value().attr("rel").is_some_and(|rel| {
+                rel.split_whitespace()
+                    .any(|token| token.eq_ignore_ascii_case("manifest"))
+            })
+        })
+        .filter_map(|element| element.value().attr("href"))
+        .map(str::trim)
+        .filter(|href| !href.is_empty())
+        .find_map(|href| base_url.join(href).ok())
+        .map(String::from)
+}
+
+/// 请求并解析 Manifest。
+pub(crate) async fn fetch_manifest(http_client: &Client, url: &str) -> Result<WebManifest, String> {
+    let response = http_client
+        .get(url)
+        .timeout(MANIFEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Request failed with status: {}", response.status()));
+    }
+    let body = response.text().await.map_err(|e| e.to_string())?;
+    parse_manifest(&body)
+}
+
+/// 解析 Manifest 的 JSON 内容。
+fn parse_manifest(body: &str) -> Result<WebManifest, String> {
+    let value: Value = serde_json::from_str(body.trim_start_matches('\u{feff}').trim())
+        .map_err(|e| format!("Invalid manifest JSON: {e}"))?;
+    let string_field = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(String::from)
+    };
+
+    let icons = value
+        .get("icons")
+        .and_then(Value::as_array)
+        .map(|icons| {
+            icons
+                .iter()
+                .filter_map(|icon| {
+                    Some(ManifestIcon {
+                        src: string_field(icon, "src")?,
+                        sizes: string_field(icon, "sizes"),
+                        mime: string_field(icon, "type"),
+                        purpose: string_field(icon, "purpose"),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(WebManifest {
+        name: string_field(&value, "name"),
+        short_name: string_field(&value, "short_name"),
+        theme_color: string_field(&value, "theme_color"),
+        icons,
+    })
+}
+
+impl WebManifest {
+    /// 适合作为网站标题的名称，优先使用专为有限空间准备的 `short_name`。
+    pub(crate) fn display_name(&self) -> Option<String> {
+        self.short_name.clone().or_else(|| self.name.clone())
+    }
+
+    /// 规范化为 `#rrggbb` 形式的 `theme_color`。
+    pub(crate) fn theme_color(&self) -> Option<String> {
+        self.theme_color.as_deref().and_then(normalize_hex_color)
+    }
+
+    /// Manifest 中的图标候选，`src` 相对于 Manifest 自身的地址解析。
+    ///
+    /// 只能用作单色图标（`purpose: "monochrome"`）的图标会被忽略。
+    pub(crate) fn icon_candidates(
+        &self,
+        manifest_url: &str,
+        scheme: ColorScheme,
+    ) -> Vec<IconCandidate> {
+        let Ok(manifest_url) = Url::parse(manifest_url) else {
+            return Vec::new();
+        };
+        self.icons
+            .iter()
+            .filter_map(|icon| {
+                let purposes: Vec<String> = icon
+                    .purpose
+                    .as_deref()
+                    .unwrap_or("any")
+                    .split_whitespace()
+                    .map(str::to_lowercase)
+                    .collect();
+                let usable_as_is = purposes.iter().any(|purpose| purpose == "any");
+                if !usable_as_is && !purposes.iter().any(|purpose| purpose == "maskable") {
+                    return None;
+                }
+                let url = manifest_url.join(&icon.src).ok()?;
+                let declaration = IconDeclaration {
+                    mime: icon.mime.as_deref(),
+                    sizes: icon.sizes.as_deref(),
+                    penalty: if usable_as_is { 0 } else { MASKABLE_PENALTY },
+                    ..Default::default()
+                };
+                Some(IconCandidate::new(url, &declaration, scheme))
+            })
+            .collect()
+    }
+}
+
+/// 把 `#rgb` 或 `#rrggbb` 形式的颜色规范化为小写的 `#rrggbb`，其他写法返回 `None`。
+pub(crate) fn normalize_hex_color(color: &str) -> Option<String> {
+    let hex = color.trim().strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = hex.to_lowercase();
+    match hex.len() {
+        3 => Some(format!(
+            "#{}",
+            hex.chars().flat_map(|c| [c, c]).collect::<String>()
+        )),
+        6 => Some(format!("#{hex}")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::invokes::favicon::rank_favicon_candidates;
+
+    const MANIFEST_URL: &str = "https://example.com/static/site.webmanifest";
+
+    fn icon_urls(manifest: &WebManifest) -> Vec<String> {
+        let candidates = manifest.icon_candidates(MANIFEST_URL, ColorScheme::Light);
+        rank_favicon_candidates(candidates, &Url::parse(MANIFEST_URL).unwrap())
+            .into_iter()
+            .map(|candidate| candidate.url)
+            .collect()
+    }
+
+    #[test]
+    fn wrong_typed_fields_are_ignored() {
+        let manifest = parse_manifest(
+            r##"{
+                "name": "Grafana",
+                "short_name": 42,
+                "theme_color": ["#fff"],
+                "icons": [{ "src": 1 }, { "src": "icon.png", "sizes": 192 }]
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(manifest.display_name().as_deref(), Some("Grafana"));
+        assert_eq!(manifest.theme_color(), None);
+        assert_eq!(manifest.icons.len(), 1);
+        assert_eq!(manifest.icons[0].sizes, None);
+
+        let manifest = parse_manifest(r#"{ "icons": {} }"#).unwrap();
+        assert!(manifest.icons.is_empty());
+        assert!(parse_manifest("<html>").is_err());
+    }
+
+    #[test]
+    fn short_name_preferred() {
+        let manifest =
+            parse_manifest("\u{feff}{ \"name\": \"Home Assistant\", \"short_name\": \" HA \" }")
+                .unwrap();
+        assert_eq!(manifest.display_name().as_deref(), Some("HA"));
+    }
+
+    #[test]
+    fn icons_resolved_and_filtered_by_purpose() {
+        let manifest = parse_manifest(
+            r#"{
+                "icons": [
+                    { "src": "mono.png", "sizes": "512x512", "purpose": "monochrome" },
+                    { "src": "mask.png", "sizes": "192x192", "purpose": "maskable" },
+                    { "src": "/icons/any.png", "sizes": "192x192", "purpose": "any maskable" },
+                    { "src": "https://cdn.example.net/logo.svg" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            icon_urls(&manifest),
+            [
+                "https://cdn.example.net/logo.svg",
+                // 尺寸相同时只能用作遮罩的图标排在后面
+                "https://example.com/icons/any.png",
+                "https://example.com/static/mask.png",
+                "https://example.com/favicon.ico"
+            ]
+        );
+    }
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!(normalize_hex_color("#ABC").as_deref(), Some("#aabbcc"));
+        assert_eq!(normalize_hex_color(" #1E88E5 ").as_deref(), Some("#1e88e5"));
+        assert_eq!(normalize_hex_color("#abcd"), None);
+        assert_eq!(normalize_hex_color("#ggg"), None);
+        assert_eq!(normalize_hex_color("rgb(0, 0, 0)"), None);
+    }
+}
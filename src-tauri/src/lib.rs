@@ -41,7 +41,28 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             invokes::metadata::fetch_website_metadata,
             invokes::metadata::save_uploaded_icon,
+            invokes::metadata_batch::fetch_websites_metadata,
+            invokes::metadata_batch::cancel_metadata_fetch,
             invokes::bookmark_parser::bookmark_parser,
+            invokes::bookmark_parser::cancel_bookmark_import,
+            invokes::bookmark_parser::auto_bookmark_parser,
+            invokes::bookmark_import::preview_bookmark_import,
+            invokes::bookmark_import::apply_bookmark_import,
+            invokes::bookmark_export::export_bookmarks,
+            invokes::bookmark_parser::chromium_bookmark_parser,
+            invokes::bookmark_parser::firefox_bookmark_parser,
+            invokes::bookmark_parser::safari_bookmark_parser,
+            invokes::bookmark_parser::dashboard_config_parser,
+            invokes::bookmark_parser::proxy_config_parser,
+            invokes::search_engine_parser::opensearch_parser,
+            invokes::search_engine_parser::chromium_search_engine_parser,
+            invokes::search_engine_parser::firefox_search_engine_parser,
+            invokes::search_engine_parser::auto_search_engine_parser,
+            invokes::search_engine_import::import_search_engines,
+            invokes::search_resolver::resolve_search_query,
+            invokes::search_suggest::fetch_search_suggestions,
+            invokes::search_template::validate_search_template,
+            invokes::search_template::open_search_form,
             invokes::sync::check_token_and_user,
             invokes::sync::check_client_version,
             invokes::sync::check_server_version,
@@ -51,6 +72,7 @@ pub fn run() {
             invokes::sync::upload_icon,
             invokes::sync::download_icon,
             invokes::browser::detect_installed_browsers,
+            invokes::browser::detect_browser_profiles,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
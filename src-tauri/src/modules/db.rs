@@ -1,6 +1,8 @@
 use crate::utils::app_data_dir_path;
+use sqlx::{Pool, Sqlite};
 use std::fmt;
-use tauri_plugin_sql::{Migration, MigrationKind};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_sql::{DbInstances, DbPool, Migration, MigrationKind};
 
 #[cfg(debug_assertions)]
 const DATABASE_FILE: &str = "dev.db"; // 开发环境 (tauri dev)
@@ -15,6 +17,7 @@ pub enum TableName {
     WebsiteItems,
     AssetCategories,
     Assets,
+    SearchEngines,
 }
 
 impl fmt::Display for TableName {
@@ -25,13 +28,14 @@ impl fmt::Display for TableName {
             TableName::WebsiteItems => "websites",
             TableName::AssetCategories => "asset_categories",
             TableName::Assets => "assets",
+            TableName::SearchEngines => "search_engines",
         };
         write!(f, "{s}")
     }
 }
 
 /// 数据库文件或数据库url
-fn db_url(app: &tauri::App) -> String {
+fn db_url(app: &AppHandle) -> String {
     // 在 `tauri build --debug` 模式下前端是开发环境后端是生成环境需要注意
     let db_file = app_data_dir_path(app).join(DATABASE_FILE);
 
@@ -57,6 +61,30 @@ fn all_migrations() -> Vec<Migration> {
             kind: MigrationKind::Up,
             sql: include_str!("../../migrations/0002_add_refresh_token.sql"),
         },
+        Migration {
+            version: 3,
+            description: "add_search_engine_keyword",
+            kind: MigrationKind::Up,
+            sql: include_str!("../../migrations/0003_add_search_engine_keyword.sql"),
+        },
+        Migration {
+            version: 4,
+            description: "add_search_engine_request_options",
+            kind: MigrationKind::Up,
+            sql: include_str!("../../migrations/0004_add_search_engine_request_options.sql"),
+        },
+        Migration {
+            version: 5,
+            description: "add_search_engine_suggest_url",
+            kind: MigrationKind::Up,
+            sql: include_str!("../../migrations/0005_add_search_engine_suggest_url.sql"),
+        },
+        Migration {
+            version: 6,
+            description: "add_website_keyword_tags",
+            kind: MigrationKind::Up,
+            sql: include_str!("../../migrations/0006_add_website_keyword_tags.sql"),
+        },
     ]
 }
 
@@ -65,9 +93,22 @@ pub fn migrations(app: &tauri::App) -> tauri::Result<()> {
     let migrations = all_migrations();
 
     let db_plugin = tauri_plugin_sql::Builder::default()
-        .add_migrations(&db_url(app), migrations)
+        .add_migrations(&db_url(app.handle()), migrations)
         .build();
 
     app.handle().plugin(db_plugin).unwrap();
     Ok(())
 }
+
+/// 获取前端已经加载的 SQLite 连接池，供 Rust 端直接读写应用数据库。
+///
+/// 数据库由前端通过 `Database.load` 打开，连接池以连接字符串为键保存在 `tauri-plugin-sql` 的 `DbInstances` 中，
+/// 这里按注册迁移时使用的同一个连接字符串查找。
+pub async fn sqlite_pool(app: &AppHandle) -> Result<Pool<Sqlite>, String> {
+    let instances = app.state::<DbInstances>();
+    let instances = instances.0.read().await;
+    let DbPool::Sqlite(pool) = instances
+        .get(&db_url(app))
+        .ok_or_else(|| "数据库尚未加载".to_string())?;
+    Ok(pool.clone())
+}
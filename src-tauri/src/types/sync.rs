@@ -32,6 +32,8 @@ pub struct WebsitesDto {
     pub local_icon_path: Option<String>,
     pub background_color: Option<String>,
     pub description: Option<String>,
+    pub keyword: Option<String>,
+    pub tags: Option<String>,
     pub sort_order: Option<i64>,
     pub is_deleted: i64,
     pub rev: i64,
@@ -70,6 +72,11 @@ pub struct SearchEngineDto {
     pub uuid: String,
     pub name: String,
     pub url_template: String,
+    pub suggest_url_template: Option<String>,
+    pub keyword: Option<String>,
+    pub method: Option<String>,
+    pub post_body: Option<String>,
+    pub encoding: Option<String>,
     pub default_icon: Option<String>,
     pub local_icon_path: Option<String>,
     pub is_default: i64,
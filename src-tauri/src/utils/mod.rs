@@ -1,13 +1,16 @@
 use crate::types::{APP_CONFIG_DIR, APP_DATA_DIR, HOME_VUST_DIR};
-use std::{path::PathBuf, str::FromStr};
-use tauri::Manager;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tauri::{AppHandle, Manager};
 
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri_plugin_http::reqwest::{Client, header};
 
 /// 获取应用配置目录路径 `~/.vust/vust-desk`
-pub fn app_config_dir_path(app: &tauri::App) -> PathBuf {
+pub fn app_config_dir_path(app: &AppHandle) -> PathBuf {
     app.path()
         .home_dir()
         .unwrap()
@@ -16,10 +19,59 @@ pub fn app_config_dir_path(app: &tauri::App) -> PathBuf {
 }
 
 /// 获取应用数据目录路径 `~/.vust/vust-desk/data`
-pub fn app_data_dir_path(app: &tauri::App) -> PathBuf {
+pub fn app_data_dir_path(app: &AppHandle) -> PathBuf {
     app_config_dir_path(app).join(APP_DATA_DIR)
 }
 
+/// 获取图标目录路径 `~/.vust/vust-desk/icons`
+pub fn app_icons_dir_path(app: &AppHandle) -> PathBuf {
+    app.path()
+        .home_dir()
+        .unwrap()
+        .join(HOME_VUST_DIR)
+        .join(APP_CONFIG_DIR)
+        .join("icons")
+}
+
+/// 把浏览器正在使用的 SQLite 数据库（以及尚未合并的 `-wal` 文件）复制到一个新的临时目录中，返回该目录。
+///
+/// 浏览器运行时会锁定自己的数据库，因此读取前先复制一份；副本与原文件同名，用完后由调用方删除整个目录。
+pub fn copy_database_to_temp(path: &Path) -> Result<PathBuf, String> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("无效的数据库路径: {path:?}"))?;
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let copy_dir = std::env::temp_dir().join(format!("vust-db-{}-{nanos}", std::process::id()));
+    fs::create_dir_all(&copy_dir).map_err(|e| format!("创建临时目录失败: {e}"))?;
+
+    // 复制失败时调用方拿不到目录，由这里负责删除
+    if let Err(e) = copy_database_files(path, file_name, &copy_dir) {
+        let _ = fs::remove_dir_all(&copy_dir);
+        return Err(e);
+    }
+    Ok(copy_dir)
+}
+
+/// 把数据库文件和存在的 `-wal` 文件以原文件名复制到 `copy_dir` 中。
+fn copy_database_files(path: &Path, file_name: &OsStr, copy_dir: &Path) -> Result<(), String> {
+    fs::copy(path, copy_dir.join(file_name))
+        .map_err(|e| format!("复制 {} 失败: {e}", file_name.to_string_lossy()))?;
+
+    let mut wal = path.as_os_str().to_owned();
+    wal.push("-wal");
+    let wal = PathBuf::from(wal);
+    if let Some(wal_name) = wal.file_name()
+        && wal.exists()
+    {
+        fs::copy(&wal, copy_dir.join(wal_name))
+            .map_err(|e| format!("复制 {} 失败: {e}", wal_name.to_string_lossy()))?;
+    }
+    Ok(())
+}
+
 /// HTTP 客户端配置。
 ///
 /// 支持设置超时、自定义请求头、认证 Token 等。
@@ -137,3 +189,19 @@ pub fn build_http_client(http_config: HttpClientConfig) -> Result<Client, String
         .build()
         .map_err(|e| format!("构建 HTTP 客户端失败: {e}"))
 }
+
+/// 转义 HTML 文本和属性值中的特殊字符。
+pub fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
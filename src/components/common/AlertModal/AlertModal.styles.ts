@@ -21,6 +21,7 @@ export const AlertMessage = styled.p<{ theme: Theme }>`
   color: ${(props) => props.theme.colors.textSecondary};
   margin-bottom: calc(${(props) => props.theme.spacing.unit} * 4);
   line-height: 1.5;
+  white-space: pre-line;
 `;
 
 export const ButtonGroup = styled.div<{ theme: Theme }>`
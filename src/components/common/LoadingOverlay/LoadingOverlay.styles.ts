@@ -39,3 +39,31 @@ export const LoadingText = styled.p<{ theme: Theme }>`
   font-size: 1rem;
   font-weight: bold;
 `;
+
+export const ProgressTrack = styled.div<{ theme: Theme }>`
+  width: 240px;
+  height: 6px;
+  margin-top: ${(props) => props.theme.spacing.unit};
+  border-radius: 3px;
+  background: rgba(255, 255, 255, 0.3);
+  overflow: hidden;
+`;
+
+export const ProgressFill = styled.div<{ theme: Theme }>`
+  height: 100%;
+  background: ${(props) => props.theme.colors.primary};
+  transition: width 0.2s ease-out;
+`;
+
+export const CancelButton = styled.button<{ theme: Theme }>`
+  margin-top: calc(${(props) => props.theme.spacing.unit} * 2);
+  padding: 6px 16px;
+  border: 1px solid white;
+  border-radius: 5px;
+  color: white;
+  font-weight: bold;
+
+  &:hover {
+    background: rgba(255, 255, 255, 0.15);
+  }
+`;
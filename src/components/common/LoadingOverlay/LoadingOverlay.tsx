@@ -1,9 +1,21 @@
 ﻿import React from "react";
-import { Overlay, Spinner, LoadingText } from "./LoadingOverlay.styles";
+import {
+  Overlay,
+  Spinner,
+  LoadingText,
+  ProgressTrack,
+  ProgressFill,
+  CancelButton,
+} from "./LoadingOverlay.styles";
 
 interface LoadingOverlayProps {
   isOpen: boolean;
   text?: string;
+  /** 进度百分比（0-100），提供时在文本下方显示进度条。 */
+  progress?: number | null;
+  /** 提供时显示取消按钮。 */
+  onCancel?: () => void;
+  cancelText?: string;
 }
 
 /**
@@ -11,10 +23,16 @@ interface LoadingOverlayProps {
  * @description 一个通用的加载遮罩层组件，用于覆盖在某个容器上显示加载状态。
  * @param {boolean} isOpen - 控制遮罩层的显示与隐藏。
  * @param {string} [text="正在加载中..."] - 加载时显示的文本。
+ * @param {number} [progress] - 进度百分比，提供时显示进度条。
+ * @param {Function} [onCancel] - 取消回调，提供时显示取消按钮。
+ * @param {string} [cancelText="取消"] - 取消按钮的文本。
  */
 const LoadingOverlay: React.FC<LoadingOverlayProps> = ({
   isOpen,
   text = "正在加载中...",
+  progress,
+  onCancel,
+  cancelText = "取消",
 }) => {
   if (!isOpen) return null;
 
@@ -27,6 +45,18 @@ const LoadingOverlay: React.FC<LoadingOverlayProps> = ({
     >
       <Spinner className="loading-spinner" />
       <LoadingText className="loading-text">{text}</LoadingText>
+      {progress != null && (
+        <ProgressTrack className="loading-progress">
+          <ProgressFill
+            style={{ width: `${Math.min(Math.max(progress, 0), 100)}%` }}
+          />
+        </ProgressTrack>
+      )}
+      {onCancel && (
+        <CancelButton className="loading-cancel" onClick={onCancel}>
+          {cancelText}
+        </CancelButton>
+      )}
     </Overlay>
   );
 };
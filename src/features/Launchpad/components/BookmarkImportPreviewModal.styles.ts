@@ -0,0 +1,173 @@
+﻿import styled from "styled-components";
+
+/**
+ * @description 预览内容的整体布局
+ */
+export const PreviewContainer = styled.div`
+  display: flex;
+  flex-direction: column;
+  gap: 16px;
+  width: min(760px, 80vw);
+`;
+
+/**
+ * @description 顶部的统计信息
+ */
+export const PreviewSummary = styled.p`
+  margin: 0;
+  font-size: 0.9rem;
+  color: ${(props) => props.theme.colors.textSecondary};
+`;
+
+/**
+ * @description 可滚动的分组列表
+ */
+export const PreviewGroupList = styled.div`
+  display: flex;
+  flex-direction: column;
+  gap: 16px;
+  max-height: 55vh;
+  overflow-y: auto;
+  padding-right: 4px;
+`;
+
+/**
+ * @description 单个分组
+ */
+export const PreviewGroup = styled.section`
+  border: 1px solid ${(props) => props.theme.colors.border};
+  border-radius: 8px;
+  overflow: hidden;
+`;
+
+/**
+ * @description 分组标题栏，包含分组名称和批量操作
+ */
+export const PreviewGroupHeader = styled.div`
+  display: flex;
+  align-items: center;
+  justify-content: space-between;
+  gap: 12px;
+  padding: 8px 12px;
+  background-color: ${(props) => props.theme.colors.surface};
+  border-bottom: 1px solid ${(props) => props.theme.colors.border};
+
+  h4 {
+    margin: 0;
+    font-size: 1rem;
+    color: ${(props) => props.theme.colors.textPrimary};
+  }
+
+  small {
+    margin-left: 8px;
+    font-weight: normal;
+    color: ${(props) => props.theme.colors.textSecondary};
+  }
+`;
+
+/**
+ * @description 分组的批量操作按钮
+ */
+export const GroupActions = styled.div`
+  display: flex;
+  gap: 8px;
+  flex-shrink: 0;
+
+  button {
+    font-size: 0.8rem;
+    color: ${(props) => props.theme.colors.primary};
+  }
+`;
+
+/**
+ * @description 单个书签
+ */
+export const PreviewItem = styled.div`
+  display: flex;
+  align-items: center;
+  gap: 12px;
+  padding: 8px 12px;
+
+  & + & {
+    border-top: 1px solid ${(props) => props.theme.colors.border};
+  }
+`;
+
+/**
+ * @description 书签和与之重复的已有网站的对比
+ */
+export const ItemDiff = styled.div`
+  flex-grow: 1;
+  min-width: 0;
+  display: flex;
+  flex-direction: column;
+  gap: 2px;
+  font-size: 0.85rem;
+
+  span {
+    overflow: hidden;
+    text-overflow: ellipsis;
+    white-space: nowrap;
+  }
+
+  .item-title {
+    color: ${(props) => props.theme.colors.textPrimary};
+    font-weight: bold;
+  }
+
+  .item-url,
+  .item-existing {
+    color: ${(props) => props.theme.colors.textSecondary};
+  }
+`;
+
+/**
+ * @description 重复状态标记
+ */
+export const StatusBadge = styled.span<{ $isNew: boolean }>`
+  flex-shrink: 0;
+  padding: 2px 8px;
+  border-radius: 10px;
+  font-size: 0.75rem;
+  color: white;
+  background-color: ${(props) =>
+    props.$isNew ? props.theme.colors.success : props.theme.colors.warning};
+`;
+
+/**
+ * @description 单个书签的操作选择
+ */
+export const ItemActionSelect = styled.div`
+  flex-shrink: 0;
+  width: 120px;
+`;
+
+/**
+ * @description 底部的确认和取消按钮
+ */
+export const PreviewFooter = styled.div`
+  display: flex;
+  justify-content: flex-end;
+  gap: 12px;
+
+  button {
+    padding: 8px 16px;
+    border-radius: 5px;
+    font-weight: bold;
+  }
+
+  .confirm-btn {
+    background-color: ${(props) => props.theme.colors.primary};
+    color: white;
+
+    &:disabled {
+      opacity: 0.5;
+      cursor: not-allowed;
+    }
+  }
+
+  .cancel-btn {
+    color: ${(props) => props.theme.colors.textSecondary};
+    border: 1px solid ${(props) => props.theme.colors.border};
+  }
+`;
@@ -0,0 +1,234 @@
+﻿import React, { useEffect, useMemo, useState } from "react";
+import { useTranslation } from "react-i18next";
+import Modal from "@/features/Assets/Modal/Modal";
+import CustomSelect from "@/components/common/CustomSelect/CustomSelect";
+import {
+  DuplicateStatus,
+  ImportPreview,
+  PlannedImportGroup,
+} from "@/features/Launchpad/types";
+import {
+  PreviewContainer,
+  PreviewSummary,
+  PreviewGroupList,
+  PreviewGroup,
+  PreviewGroupHeader,
+  GroupActions,
+  PreviewItem,
+  ItemDiff,
+  StatusBadge,
+  ItemActionSelect,
+  PreviewFooter,
+} from "./BookmarkImportPreviewModal.styles";
+
+type ActionKind = "add" | "update" | "skip";
+
+interface PreviewModalProps {
+  /** 为空时不显示模态框。 */
+  preview: ImportPreview | null;
+  onCancel: () => void;
+  onConfirm: (plan: PlannedImportGroup[]) => void;
+}
+
+/** 各个重复状态在界面上的文案。 */
+const STATUS_KEYS: Record<DuplicateStatus, string> = {
+  new: "launchpad.importStatusNew",
+  exactDuplicate: "launchpad.importStatusExactDuplicate",
+  nearDuplicate: "launchpad.importStatusNearDuplicate",
+  duplicateInImport: "launchpad.importStatusDuplicateInImport",
+};
+
+/**
+ * @function defaultActions
+ * @description 默认只添加新书签，所有重复项都跳过
+ */
+const defaultActions = (preview: ImportPreview): ActionKind[][] =>
+  preview.groups.map((group) =>
+    group.items.map((entry) => (entry.status === "new" ? "add" : "skip")),
+  );
+
+/**
+ * @component BookmarkImportPreviewModal
+ * @description 展示书签导入预览，重复的书签与已有网站并排显示，
+ * 可以逐个或按分组选择添加、覆盖已有网站或跳过
+ */
+const BookmarkImportPreviewModal: React.FC<PreviewModalProps> = ({
+  preview,
+  onCancel,
+  onConfirm,
+}) => {
+  const { t } = useTranslation();
+  const [actions, setActions] = useState<ActionKind[][]>([]);
+
+  useEffect(() => {
+    setActions(preview ? defaultActions(preview) : []);
+  }, [preview]);
+
+  const selectedCount = useMemo(
+    () => actions.flat().filter((action) => action !== "skip").length,
+    [actions],
+  );
+
+  if (!preview) return null;
+
+  const setItemAction = (
+    groupIndex: number,
+    itemIndex: number,
+    action: ActionKind,
+  ) => {
+    setActions((current) =>
+      current.map((group, g) =>
+        g === groupIndex
+          ? group.map((value, i) => (i === itemIndex ? action : value))
+          : group,
+      ),
+    );
+  };
+
+  // 对分组中的所有重复项执行同一操作，无法覆盖的书签（本次导入内部的重复）保持跳过
+  const setGroupDuplicateAction = (groupIndex: number, action: ActionKind) => {
+    const items = preview.groups[groupIndex].items;
+    setActions((current) =>
+      current.map((group, g) =>
+        g === groupIndex
+          ? group.map((value, i) => {
+              if (items[i].status === "new") return value;
+              if (action === "update" && !items[i].existing) return "skip";
+              return action;
+            })
+          : group,
+      ),
+    );
+  };
+
+  const handleConfirm = () => {
+    const plan: PlannedImportGroup[] = preview.groups.map((group, g) => ({
+      name: group.name,
+      group_uuid: group.existing_group_uuid,
+      items: group.items.map(
+        (entry, i): PlannedImportGroup["items"][number] => {
+          const action = actions[g]?.[i] ?? "skip";
+          if (action === "update" && entry.existing) {
+            return { item: entry.item, action, uuid: entry.existing.uuid };
+          }
+          return {
+            item: entry.item,
+            action: action === "update" ? "skip" : action,
+          };
+        },
+      ),
+    }));
+    onConfirm(plan);
+  };
+
+  return (
+    <Modal isOpen onClose={onCancel} title={t("launchpad.importPreviewTitle")}>
+      <PreviewContainer>
+        <PreviewSummary>
+          {t("launchpad.importPreviewSummary", {
+            newCount: preview.new_count,
+            exactCount: preview.exact_duplicate_count,
+            nearCount: preview.near_duplicate_count,
+            inImportCount: preview.duplicate_in_import_count,
+          })}
+        </PreviewSummary>
+        <PreviewGroupList>
+          {preview.groups.map((group, g) => {
+            const hasDuplicates = group.items.some(
+              (entry) => entry.status !== "new",
+            );
+            return (
+              <PreviewGroup key={`${group.name}-${g}`}>
+                <PreviewGroupHeader>
+                  <h4>
+                    {group.name}
+                    <small>
+                      {group.existing_group_uuid
+                        ? t("launchpad.importGroupExisting")
+                        : t("launchpad.importGroupNew")}
+                    </small>
+                  </h4>
+                  {hasDuplicates && (
+                    <GroupActions>
+                      <button
+                        onClick={() => setGroupDuplicateAction(g, "add")}
+                      >
+                        {t("launchpad.importAddDuplicates")}
+                      </button>
+                      <button
+                        onClick={() => setGroupDuplicateAction(g, "update")}
+                      >
+                        {t("launchpad.importUpdateDuplicates")}
+                      </button>
+                      <button
+                        onClick={() => setGroupDuplicateAction(g, "skip")}
+                      >
+                        {t("launchpad.importSkipDuplicates")}
+                      </button>
+                    </GroupActions>
+                  )}
+                </PreviewGroupHeader>
+                {group.items.map((entry, i) => (
+                  <PreviewItem key={`${entry.item.url}-${i}`}>
+                    <StatusBadge $isNew={entry.status === "new"}>
+                      {t(STATUS_KEYS[entry.status])}
+                    </StatusBadge>
+                    <ItemDiff>
+                      <span className="item-title">{entry.item.title}</span>
+                      <span className="item-url">{entry.item.url}</span>
+                      {entry.existing && (
+                        <span className="item-existing">
+                          {t("launchpad.importExistingWebsite", {
+                            title: entry.existing.title,
+                            url: entry.existing.url,
+                          })}
+                        </span>
+                      )}
+                    </ItemDiff>
+                    <ItemActionSelect>
+                      <CustomSelect
+                        options={[
+                          {
+                            value: "add",
+                            label: t("launchpad.importActionAdd"),
+                          },
+                          {
+                            value: "update",
+                            label: t("launchpad.importActionUpdate"),
+                            disabled: !entry.existing,
+                          },
+                          {
+                            value: "skip",
+                            label: t("launchpad.importActionSkip"),
+                          },
+                        ]}
+                        value={actions[g]?.[i]}
+                        onChange={(value) =>
+                          setItemAction(g, i, value as ActionKind)
+                        }
+                      />
+                    </ItemActionSelect>
+                  </PreviewItem>
+                ))}
+              </PreviewGroup>
+            );
+          })}
+        </PreviewGroupList>
+        <PreviewFooter>
+          <button className="cancel-btn" onClick={onCancel}>
+            {t("button.cancel")}
+          </button>
+          <button
+            className="confirm-btn"
+            disabled={selectedCount === 0}
+            onClick={handleConfirm}
+          >
+            {t("launchpad.importConfirm", { count: selectedCount })}
+          </button>
+        </PreviewFooter>
+      </PreviewContainer>
+    </Modal>
+  );
+};
+
+export default BookmarkImportPreviewModal;
@@ -38,6 +38,41 @@ export const ImportButton = styled.button`
   }
 `;
 
+/**
+ * @description 书签导入选项（分组方式等）
+ */
+export const ImportOptionRow = styled.div`
+  display: flex;
+  align-items: center;
+  gap: 12px;
+  min-width: 360px;
+
+  label {
+    color: ${(props) => props.theme.colors.textSecondary};
+    white-space: nowrap;
+  }
+
+  & > div {
+    flex-grow: 1;
+  }
+`;
+
+/**
+ * @description 导入后抓取网站图标的进度
+ */
+export const MetadataProgressText = styled.div`
+  display: flex;
+  align-items: center;
+  gap: 12px;
+  font-size: 0.9rem;
+  color: ${(props) => props.theme.colors.textSecondary};
+
+  button {
+    color: ${(props) => props.theme.colors.primary};
+    font-size: 0.9rem;
+  }
+`;
+
 export const Toolbar = styled.div`
   display: flex;
   justify-content: flex-end;
@@ -1,9 +1,15 @@
-﻿import React, { useState, useEffect, useCallback } from "react";
+﻿import React, { useState, useEffect, useCallback, useRef } from "react";
 
-import { WebsiteGroup } from "@/features/Launchpad/types";
+import {
+  BookmarkItem,
+  ImportPreview,
+  PlannedImportGroup,
+  WebsiteGroup,
+} from "@/features/Launchpad/types";
 import * as launchpadDb from "@/services/launchpadDb";
 import Loading from "@/components/common/Loading";
 import GroupModal from "@/features/Launchpad/components/GroupModal";
+import BookmarkImportPreviewModal from "@/features/Launchpad/components/BookmarkImportPreviewModal";
 import {
   IoAddCircleOutline,
   IoPencil,
@@ -34,9 +40,12 @@ import {
 } from "react-icons/vsc";
 import { useModal } from "@/contexts/ModalContext";
 import { useAuth } from "@/contexts/AuthContext";
+import { useAppTheme } from "@/contexts/ThemeContext";
 import { open } from "@tauri-apps/plugin-dialog";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import LoadingOverlay from "@/components/common/LoadingOverlay/LoadingOverlay";
+import CustomSelect from "@/components/common/CustomSelect/CustomSelect";
 import {
   DndContext,
   closestCenter,
@@ -65,25 +74,63 @@ import {
   Toolbar,
   BookmarkImportContainer,
   ImportButton,
+  ImportOptionRow,
+  MetadataProgressText,
 } from "./ConfigModal.styles";
 import LaunchpadPersonalizationSettings from "./LaunchpadPersonalizationSettings";
 
-/**
- * @interface BookmarkItem
- * @description 定义从后端返回的单个书签项的结构
- */
-interface BookmarkItem {
-  title: string;
-  url: string;
-}
-
 /**
  * @interface BookmarkGroup
  * @description 定义从后端返回的书签分组的结构
  */
 interface BookmarkGroup {
   name: string;
+  path: string[];
   items: BookmarkItem[];
+  children: BookmarkGroup[];
+}
+
+/**
+ * @type BookmarkImportMode
+ * @description 书签文件夹整理为分组的方式，与后端的 `BookmarkImportMode` 对应
+ */
+type BookmarkImportMode = "flatten" | "perFolder" | "joinedPath";
+
+/**
+ * @interface ImportResult
+ * @description 定义后端执行导入计划后返回的统计
+ */
+interface ImportResult {
+  created_groups: number;
+  added_items: number;
+  updated_items: number;
+  skipped_items: number;
+  added_uuids: string[];
+}
+
+/**
+ * @interface BookmarkImportProgress
+ * @description 定义解析书签文件时 `bookmark-import-progress` 事件的内容
+ */
+interface BookmarkImportProgress {
+  processed: number;
+  total: number;
+}
+
+/**
+ * @interface MetadataProgress
+ * @description 定义批量抓取网站元数据时 `metadata-progress` 事件的内容
+ */
+interface MetadataProgress {
+  uuid: string;
+  url: string;
+  status: "success" | "failed";
+  title: string | null;
+  local_icon_path: string | null;
+  error: string | null;
+  warnings: string[];
+  completed: number;
+  total: number;
 }
 
 // 可排序的列表项组件
@@ -153,12 +200,26 @@ const ConfigModal: React.FC<NavigationConfigModalProps> = ({
   const { t } = useTranslation();
   const { openAlert, openConfirm } = useModal();
   const { activeUser } = useAuth(); // 获取当前活跃用户
+  const { themeName } = useAppTheme();
 
   const [activeMenu, setActiveMenu] = useState<MenuKey>("group_management");
   const [dataChanged, setDataChanged] = useState(false);
   const [groups, setGroups] = useState<WebsiteGroup[]>([]);
   const [isLoading, setIsLoading] = useState(true);
   const [isImporting, setIsImporting] = useState(false);
+  const [importMode, setImportMode] = useState<BookmarkImportMode>("flatten");
+  const [importProgress, setImportProgress] = useState<number | null>(null);
+  // 写入数据库的阶段在同一个事务中完成，不能取消
+  const [isApplyingImport, setIsApplyingImport] = useState(false);
+  // 用户取消后，仍在进行的解析结果会被丢弃
+  const importCancelledRef = useRef(false);
+  const [importPreview, setImportPreview] = useState<ImportPreview | null>(
+    null,
+  );
+  const [metadataProgress, setMetadataProgress] = useState<{
+    completed: number;
+    total: number;
+  } | null>(null);
   const [isGroupModalOpen, setIsGroupModalOpen] = useState(false);
   const [editingGroup, setEditingGroup] = useState<WebsiteGroup | null>(null);
 
@@ -291,6 +352,37 @@ const ConfigModal: React.FC<NavigationConfigModalProps> = ({
     [groups, loadGroups, openAlert, activeUser],
   );
 
+  /**
+   * @function fetchImportedMetadata
+   * @description 在后台为新导入的网站抓取标题和图标，并显示进度
+   */
+  const fetchImportedMetadata = async (websiteUuids: string[]) => {
+    if (!activeUser?.uuid || websiteUuids.length === 0) return;
+    setMetadataProgress({ completed: 0, total: websiteUuids.length });
+    const unlisten = await listen<MetadataProgress>(
+      "metadata-progress",
+      (event) => {
+        setMetadataProgress({
+          completed: event.payload.completed,
+          total: event.payload.total,
+        });
+      },
+    );
+    try {
+      await invoke("fetch_websites_metadata", {
+        userUuid: activeUser.uuid,
+        websiteUuids,
+        colorScheme: themeName,
+      });
+      setDataChanged(true);
+    } catch (err) {
+      console.error("抓取网站元数据失败:", err);
+    } finally {
+      unlisten();
+      setMetadataProgress(null);
+    }
+  };
+
   /**
    * @function handleImportBookmarks
    * @description 处理书签导入逻辑：选择文件、调用后端解析、数据入库
@@ -300,17 +392,52 @@ const ConfigModal: React.FC<NavigationConfigModalProps> = ({
     try {
       const selectedPath = await open({
         multiple: false,
-        filters: [{ name: "HTML", extensions: ["html"] }],
+        filters: [
+          {
+            name: "Bookmarks",
+            extensions: [
+              "html",
+              "htm",
+              "opml",
+              "xml",
+              "plist",
+              "yml",
+              "yaml",
+              "md",
+              "markdown",
+              "txt",
+              "csv",
+              "conf",
+              "caddy",
+              "toml",
+            ],
+          },
+          // Caddyfile 等没有扩展名的配置文件
+          { name: "All Files", extensions: ["*"] },
+        ],
       });
 
       if (typeof selectedPath === "string") {
+        importCancelledRef.current = false;
         setIsImporting(true);
-        const importedGroups: BookmarkGroup[] = await invoke(
-          "bookmark_parser",
-          {
-            path: selectedPath,
+        const unlisten = await listen<BookmarkImportProgress>(
+          "bookmark-import-progress",
+          (event) => {
+            const { processed, total } = event.payload;
+            setImportProgress(total > 0 ? (processed / total) * 100 : null);
           },
         );
+        let importedGroups: BookmarkGroup[];
+        try {
+          importedGroups = await invoke("auto_bookmark_parser", {
+            path: selectedPath,
+            mode: importMode,
+          });
+        } finally {
+          unlisten();
+          setImportProgress(null);
+        }
+        if (importCancelledRef.current) return;
 
         if (
           importedGroups.length === 0 ||
@@ -324,58 +451,70 @@ const ConfigModal: React.FC<NavigationConfigModalProps> = ({
           return;
         }
 
-        let newGroupCount = 0;
-        let newItemCount = 0;
-        const existingGroupNames = new Set(groups.map((g) => g.name));
-
-        for (const importedGroup of importedGroups) {
-          if (!existingGroupNames.has(importedGroup.name)) {
-            await launchpadDb.saveGroup({
-              name: importedGroup.name,
-              user_uuid: activeUser.uuid,
-            });
-            newGroupCount++;
-          }
-        }
+        // 与已有网站比对，由用户在预览中决定每个重复书签的处理方式
+        const preview: ImportPreview = await invoke("preview_bookmark_import", {
+          userUuid: activeUser.uuid,
+          groups: importedGroups,
+        });
+        if (importCancelledRef.current) return;
+        setIsImporting(false);
+        setImportPreview(preview);
+      }
+    } catch (err) {
+      setIsImporting(false);
+      if (importCancelledRef.current) return;
+      openAlert({
+        title: t("launchpad.importErrorTitle"),
+        message: String(err),
+      });
+    }
+  };
 
-        // 重新获取所有分组数据（包含新创建的）以拿到 ID
-        const { groups: updatedGroups } = await launchpadDb.getLaunchpadData(
-          activeUser.uuid,
-        );
+  /**
+   * @function handleCancelImport
+   * @description 取消正在进行的书签文件解析
+   */
+  const handleCancelImport = () => {
+    importCancelledRef.current = true;
+    setIsImporting(false);
+    setImportProgress(null);
+    invoke("cancel_bookmark_import").catch((err) =>
+      console.error("取消书签导入失败:", err),
+    );
+  };
 
-        // 遍历导入的数据，将网站项添加到对应的分组中
-        for (const importedGroup of importedGroups) {
-          const targetGroup = updatedGroups.find(
-            (g) => g.name === importedGroup.name,
-          );
-          if (targetGroup) {
-            for (const item of importedGroup.items) {
-              await launchpadDb.saveItem({
-                group_uuid: targetGroup.uuid,
-                user_uuid: activeUser.uuid,
-                title: item.title,
-                url: item.url,
-                default_icon: "ion:globe-outline",
-              });
-              newItemCount++;
-            }
-          }
-        }
+  /**
+   * @function handleConfirmImport
+   * @description 执行用户在预览中确认的导入计划
+   * @param {PlannedImportGroup[]} plan - 每个书签及其操作
+   */
+  const handleConfirmImport = async (plan: PlannedImportGroup[]) => {
+    if (!activeUser?.uuid) return;
+    setImportPreview(null);
+    setIsImporting(true);
+    setIsApplyingImport(true);
+    try {
+      const result: ImportResult = await invoke("apply_bookmark_import", {
+        userUuid: activeUser.uuid,
+        plan,
+      });
 
-        setIsImporting(false);
-        setDataChanged(true);
-        await loadGroups(); // 刷新分组列表，以防用户切回分组管理
+      setIsImporting(false);
+      setIsApplyingImport(false);
+      setDataChanged(true);
+      await loadGroups(); // 刷新分组列表，以防用户切回分组管理
 
-        openAlert({
-          title: t("launchpad.importSuccessTitle"),
-          message: t("launchpad.importSuccessMessage", {
-            groupCount: newGroupCount,
-            itemCount: newItemCount,
-          }),
-        });
-      }
+      openAlert({
+        title: t("launchpad.importSuccessTitle"),
+        message: t("launchpad.importSuccessMessage", {
+          groupCount: result.created_groups,
+          itemCount: result.added_items + result.updated_items,
+        }),
+      });
+      fetchImportedMetadata(result.added_uuids);
     } catch (err) {
       setIsImporting(false);
+      setIsApplyingImport(false);
       openAlert({
         title: t("launchpad.importErrorTitle"),
         message: String(err),
@@ -439,10 +578,38 @@ const ConfigModal: React.FC<NavigationConfigModalProps> = ({
       case "bookmark_import":
         return (
           <BookmarkImportContainer className="bookmark-import-container">
+            <ImportOptionRow>
+              <label>{t("launchpad.importMode")}</label>
+              <CustomSelect
+                options={[
+                  { value: "flatten", label: t("launchpad.importModeFlatten") },
+                  {
+                    value: "perFolder",
+                    label: t("launchpad.importModePerFolder"),
+                  },
+                  {
+                    value: "joinedPath",
+                    label: t("launchpad.importModeJoinedPath"),
+                  },
+                ]}
+                value={importMode}
+                onChange={(value) =>
+                  setImportMode(value as BookmarkImportMode)
+                }
+              />
+            </ImportOptionRow>
             <ImportButton onClick={handleImportBookmarks}>
               <IoCloudUploadOutline />
               {t("launchpad.importButton")}
             </ImportButton>
+            {metadataProgress && (
+              <MetadataProgressText>
+                {t("launchpad.fetchingMetadata", metadataProgress)}
+                <button onClick={() => invoke("cancel_metadata_fetch")}>
+                  {t("launchpad.cancelFetchingMetadata")}
+                </button>
+              </MetadataProgressText>
+            )}
           </BookmarkImportContainer>
         );
       case "browser_settings":
@@ -523,12 +690,26 @@ const ConfigModal: React.FC<NavigationConfigModalProps> = ({
               <Content className="Launchpad-config-content">
                 <LoadingOverlay
                   isOpen={isImporting}
-                  text={t("launchpad.importing")}
+                  text={
+                    importProgress == null
+                      ? t("launchpad.importing")
+                      : t("launchpad.importingProgress", {
+                          percent: Math.round(importProgress),
+                        })
+                  }
+                  progress={importProgress}
+                  onCancel={isApplyingImport ? undefined : handleCancelImport}
+                  cancelText={t("button.cancel")}
                 />
                 {renderContent()}
               </Content>
             </MainContentWrapper>
 
+            <BookmarkImportPreviewModal
+              preview={importPreview}
+              onCancel={() => setImportPreview(null)}
+              onConfirm={handleConfirmImport}
+            />
             <GroupModal
               isOpen={isGroupModalOpen}
               onClose={() => setIsGroupModalOpen(false)}
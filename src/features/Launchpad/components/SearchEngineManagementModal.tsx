@@ -5,7 +5,13 @@ import { useModal } from "@/contexts/ModalContext";
 import * as launchpadDb from "@/services/launchpadDb";
 import { SearchEngine } from "@/features/Launchpad/types";
 import Modal from "@/features/Assets/Modal/Modal";
-import { Input, FormGroup, Label } from "@/components/styled/StyledForm";
+import {
+  Input,
+  Textarea,
+  FormGroup,
+  Label,
+} from "@/components/styled/StyledForm";
+import CustomSelect from "@/components/common/CustomSelect/CustomSelect";
 import DynamicIcon from "@/components/common/DynamicIcon";
 import { useAuth } from "@/contexts/AuthContext";
 import { invoke } from "@tauri-apps/api/core";
@@ -49,10 +55,16 @@ const SearchEngineManagementModal: React.FC<
   const { openConfirm, openAlert } = useModal();
   const { activeUser } = useAuth();
   const [isUploading, setIsUploading] = useState(false);
+  const [isImporting, setIsImporting] = useState(false);
   const [editingEngine, setEditingEngine] = useState<SearchEngine | null>(null);
 
   const [name, setName] = useState("");
   const [urlTemplate, setUrlTemplate] = useState("");
+  const [suggestUrlTemplate, setSuggestUrlTemplate] = useState("");
+  const [keyword, setKeyword] = useState("");
+  const [method, setMethod] = useState<"GET" | "POST">("GET");
+  const [postBody, setPostBody] = useState("");
+  const [encoding, setEncoding] = useState("");
   const [iconPath, setIconPath] = useState<string | null>(null);
   const { t } = useTranslation();
 
@@ -61,10 +73,20 @@ const SearchEngineManagementModal: React.FC<
       if (editingEngine) {
         setName(editingEngine.name);
         setUrlTemplate(editingEngine.url_template);
+        setSuggestUrlTemplate(editingEngine.suggest_url_template || "");
+        setKeyword(editingEngine.keyword || "");
+        setMethod(editingEngine.method === "POST" ? "POST" : "GET");
+        setPostBody(editingEngine.post_body || "");
+        setEncoding(editingEngine.encoding || "");
         setIconPath(editingEngine.local_icon_path || null);
       } else {
         setName("");
         setUrlTemplate("");
+        setSuggestUrlTemplate("");
+        setKeyword("");
+        setMethod("GET");
+        setPostBody("");
+        setEncoding("");
         setIconPath(null);
       }
     } else {
@@ -106,6 +128,51 @@ const SearchEngineManagementModal: React.FC<
     }
   };
 
+  /**
+   * @function handleImportEngines
+   * @description 从 OpenSearch 描述文件、Chromium `Web Data` 或 Firefox `search.json.mozlz4` 中导入搜索引擎
+   */
+  const handleImportEngines = async () => {
+    if (!activeUser) return;
+    try {
+      const selectedPath = await openFileDialog({
+        multiple: false,
+        filters: [
+          { name: "OpenSearch", extensions: ["xml", "osdx"] },
+          { name: "Firefox", extensions: ["mozlz4"] },
+          // Chromium 的 `Web Data` 没有扩展名
+          { name: "All Files", extensions: ["*"] },
+        ],
+      });
+      if (typeof selectedPath !== "string") return;
+
+      setIsImporting(true);
+      const parsed = await invoke("auto_search_engine_parser", {
+        path: selectedPath,
+      });
+      const result: { added_engines: number; skipped_engines: number } =
+        await invoke("import_search_engines", {
+          userUuid: activeUser.uuid,
+          engines: parsed,
+        });
+      onEnginesUpdate();
+      openAlert({
+        title: t("launchpad.importSuccessTitle"),
+        message: t("launchpad.searchEngine.importSuccessMessage", {
+          added: result.added_engines,
+          skipped: result.skipped_engines,
+        }),
+      });
+    } catch (err) {
+      openAlert({
+        title: t("launchpad.importErrorTitle"),
+        message: `${t("common.operationFailed")}: ${err}`,
+      });
+    } finally {
+      setIsImporting(false);
+    }
+  };
+
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
     if (!name.trim() || !urlTemplate.trim()) {
@@ -132,18 +199,67 @@ const SearchEngineManagementModal: React.FC<
       return;
     }
 
+    // 关键字忽略大小写和 `!` 前缀，且不能与其他搜索引擎重复
+    const normalizeKeyword = (value: string) =>
+      value.trim().replace(/^!+/, "").toLowerCase();
+    const trimmedKeyword = keyword.trim();
+    const isDuplicateKeyword =
+      normalizeKeyword(trimmedKeyword) !== "" &&
+      engines.some(
+        (engine) =>
+          engine.keyword &&
+          normalizeKeyword(engine.keyword) ===
+            normalizeKeyword(trimmedKeyword) &&
+          engine.uuid !== editingEngine?.uuid,
+      );
+
+    if (isDuplicateKeyword) {
+      openAlert({
+        title: t("launchpad.searchEngine.duplicateKeyword"),
+        message: t("launchpad.searchEngine.duplicateKeywordMessage"),
+      });
+      return;
+    }
+
+    // 由后端校验模板中的占位符、请求方式和字符编码
+    try {
+      await invoke("validate_search_template", {
+        urlTemplate: urlTemplate.trim(),
+        method,
+        postBody: method === "POST" ? postBody.trim() : null,
+        encoding: encoding.trim() || null,
+        suggestUrlTemplate: suggestUrlTemplate.trim() || null,
+      });
+    } catch (error) {
+      openAlert({
+        title: t("launchpad.searchEngine.invalidTemplate"),
+        message: String(error),
+      });
+      return;
+    }
+
     try {
       await launchpadDb.saveSearchEngine({
         ...(editingEngine ? { uuid: editingEngine.uuid } : {}),
         user_uuid: activeUser.uuid,
         name: name.trim(),
         url_template: urlTemplate.trim(),
+        suggest_url_template: suggestUrlTemplate.trim() || null,
+        keyword: trimmedKeyword || null,
+        method,
+        post_body: method === "POST" ? postBody.trim() : null,
+        encoding: encoding.trim() || null,
         local_icon_path: iconPath,
         default_icon: "IoSearchOutline",
       });
       onEnginesUpdate();
       setName("");
       setUrlTemplate("");
+      setSuggestUrlTemplate("");
+      setKeyword("");
+      setMethod("GET");
+      setPostBody("");
+      setEncoding("");
       setIconPath(null);
       setEditingEngine(null);
     } catch (error) {
@@ -230,6 +346,10 @@ const SearchEngineManagementModal: React.FC<
 
         <AddEngineForm onSubmit={handleSubmit}>
           <LoadingOverlay isOpen={isUploading} text={t("common.uploading")} />
+          <LoadingOverlay
+            isOpen={isImporting}
+            text={t("launchpad.searchEngine.importing")}
+          />
           <FormGroup>
             <Label htmlFor="engine-name">
               {t("launchpad.searchEngine.engineName")}
@@ -255,8 +375,82 @@ const SearchEngineManagementModal: React.FC<
               {t("launchpad.searchEngine.searchPlaceholderHint")}
             </HintText>
           </FormGroup>
+          <FormGroup>
+            <Label htmlFor="suggest-url-template">
+              {t("launchpad.searchEngine.suggestUrlTemplate")}
+            </Label>
+            <Input
+              id="suggest-url-template"
+              placeholder={t("launchpad.searchEngine.exampleSuggestUrl")}
+              value={suggestUrlTemplate}
+              onChange={(e) => setSuggestUrlTemplate(e.target.value)}
+            />
+            <HintText>
+              {t("launchpad.searchEngine.suggestUrlTemplateHint")}
+            </HintText>
+          </FormGroup>
+          <FormGroup>
+            <Label htmlFor="engine-keyword">
+              {t("launchpad.searchEngine.keyword")}
+            </Label>
+            <Input
+              id="engine-keyword"
+              placeholder={t("launchpad.searchEngine.exampleKeyword")}
+              value={keyword}
+              onChange={(e) => setKeyword(e.target.value)}
+            />
+            <HintText>{t("launchpad.searchEngine.keywordHint")}</HintText>
+          </FormGroup>
+          <FormRow>
+            <FormGroup style={{ flex: 1 }}>
+              <Label>{t("launchpad.searchEngine.method")}</Label>
+              <CustomSelect
+                options={[
+                  { value: "GET", label: "GET" },
+                  { value: "POST", label: "POST" },
+                ]}
+                value={method}
+                onChange={(value) => setMethod(value as "GET" | "POST")}
+              />
+            </FormGroup>
+            <FormGroup style={{ flex: 1 }}>
+              <Label htmlFor="engine-encoding">
+                {t("launchpad.searchEngine.encoding")}
+              </Label>
+              <Input
+                id="engine-encoding"
+                placeholder="UTF-8"
+                value={encoding}
+                onChange={(e) => setEncoding(e.target.value)}
+              />
+            </FormGroup>
+          </FormRow>
+          {method === "POST" && (
+            <FormGroup>
+              <Label htmlFor="engine-post-body">
+                {t("launchpad.searchEngine.postBody")}
+              </Label>
+              <Textarea
+                id="engine-post-body"
+                placeholder="q={query}&lang={lang}"
+                value={postBody}
+                onChange={(e) => setPostBody(e.target.value)}
+              />
+              <HintText>{t("launchpad.searchEngine.postBodyHint")}</HintText>
+            </FormGroup>
+          )}
 
           <FormRow style={{ justifyContent: "flex-end" }}>
+            {!editingEngine && (
+              <CompactButton
+                type="button"
+                variant="ghost"
+                onClick={handleImportEngines}
+                disabled={isImporting}
+              >
+                {t("launchpad.searchEngine.importFromFile")}
+              </CompactButton>
+            )}
             {iconPath && (
               <IconPreview>
                 <DynamicIcon localIconPath={iconPath} />
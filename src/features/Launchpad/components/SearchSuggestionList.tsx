@@ -0,0 +1,126 @@
+import React from "react";
+import styled from "styled-components";
+import { IoSearchOutline } from "react-icons/io5";
+import DynamicIcon from "@/components/common/DynamicIcon";
+import { SearchSuggestion } from "@/features/Launchpad/types";
+
+const SuggestionList = styled.ul`
+  position: absolute;
+  top: calc(100% + 6px);
+  left: 0;
+  right: 0;
+  background-color: ${(props) => props.theme.colors.surface};
+  border: ${(props) => props.theme.borderWidths.standard} solid
+    ${(props) => props.theme.colors.border};
+  border-radius: ${(props) => props.theme.radii.base};
+  box-shadow: 0 5px 15px rgba(0, 0, 0, 0.2);
+  z-index: ${(props) => props.theme.zIndices.dropdown};
+  list-style: none;
+  padding: 5px;
+  margin: 0;
+  text-align: left;
+`;
+
+const SuggestionItem = styled.li<{ $isHighlighted: boolean }>`
+  display: flex;
+  align-items: center;
+  gap: 0.75rem;
+  padding: 8px 12px;
+  cursor: pointer;
+  border-radius: ${(props) => props.theme.radii.small};
+  color: ${(props) =>
+    props.$isHighlighted ? "white" : props.theme.colors.textPrimary};
+  background-color: ${(props) =>
+    props.$isHighlighted ? props.theme.colors.primary : "transparent"};
+`;
+
+const SuggestionIcon = styled.span`
+  display: flex;
+  align-items: center;
+  justify-content: center;
+  width: 18px;
+  height: 18px;
+  flex-shrink: 0;
+  img {
+    width: 100%;
+    height: 100%;
+    object-fit: cover;
+  }
+`;
+
+const SuggestionText = styled.span`
+  flex-grow: 1;
+  overflow: hidden;
+  text-overflow: ellipsis;
+  white-space: nowrap;
+`;
+
+const SuggestionUrl = styled.span`
+  max-width: 40%;
+  overflow: hidden;
+  text-overflow: ellipsis;
+  white-space: nowrap;
+  font-size: 0.85rem;
+  opacity: 0.7;
+`;
+
+interface SearchSuggestionListProps {
+  suggestions: SearchSuggestion[];
+  highlightedIndex: number;
+  onHighlight: (index: number) => void;
+  onSelect: (suggestion: SearchSuggestion) => void;
+}
+
+/**
+ * 搜索框下方的建议列表：本地网站在前，搜索引擎的建议在后。
+ */
+const SearchSuggestionList: React.FC<SearchSuggestionListProps> = ({
+  suggestions,
+  highlightedIndex,
+  onHighlight,
+  onSelect,
+}) => {
+  if (suggestions.length === 0) return null;
+
+  return (
+    <SuggestionList role="listbox">
+      {suggestions.map((suggestion, index) => (
+        <SuggestionItem
+          key={
+            suggestion.kind === "website"
+              ? `website-${suggestion.uuid}`
+              : `search-${suggestion.text}`
+          }
+          role="option"
+          aria-selected={index === highlightedIndex}
+          $isHighlighted={index === highlightedIndex}
+          onMouseEnter={() => onHighlight(index)}
+          // 使用 onMouseDown 并阻止默认行为，避免输入框先失去焦点导致列表关闭
+          onMouseDown={(e) => {
+            e.preventDefault();
+            onSelect(suggestion);
+          }}
+        >
+          <SuggestionIcon>
+            {suggestion.kind === "website" ? (
+              <DynamicIcon
+                defaultIcon={suggestion.default_icon}
+                localIconPath={suggestion.local_icon_path}
+              />
+            ) : (
+              <IoSearchOutline />
+            )}
+          </SuggestionIcon>
+          <SuggestionText>
+            {suggestion.kind === "website" ? suggestion.title : suggestion.text}
+          </SuggestionText>
+          {suggestion.kind === "website" && (
+            <SuggestionUrl>{suggestion.url}</SuggestionUrl>
+          )}
+        </SuggestionItem>
+      ))}
+    </SuggestionList>
+  );
+};
+
+export default SearchSuggestionList;
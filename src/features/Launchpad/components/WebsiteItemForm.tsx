@@ -8,9 +8,14 @@ import {
   IoCloudUploadOutline,
 } from "react-icons/io5";
 import { invoke } from "@tauri-apps/api/core";
-import { WebsiteItem, WebsiteGroup } from "@/features/Launchpad/types";
+import {
+  WebsiteItem,
+  WebsiteGroup,
+  WebsiteMetadata,
+} from "@/features/Launchpad/types";
 import DynamicIcon from "@/components/common/DynamicIcon";
 import { useModal } from "@/contexts/ModalContext";
+import { useAppTheme } from "@/contexts/ThemeContext";
 import * as log from "@tauri-apps/plugin-log";
 import { isValidUrl } from "@/utils";
 import LoadingOverlay from "@/components/common/LoadingOverlay/LoadingOverlay";
@@ -58,6 +63,7 @@ const WebsiteItemForm: React.FC<WebsiteItemFormProps> = ({
   const { t } = useTranslation();
   const theme = useTheme();
   const { openAlert } = useModal();
+  const { themeName } = useAppTheme();
   const [isFetching, setIsFetching] = useState(false);
   const [isIconInvalid, setIsIconInvalid] = useState(false); // 跟踪图标是否无效
 
@@ -77,18 +83,57 @@ const WebsiteItemForm: React.FC<WebsiteItemFormProps> = ({
     }
     setIsFetching(true);
     try {
-      const metadata: { title?: string; local_icon_path?: string } =
-        await invoke("fetch_website_metadata", { url: urlToFetch });
+      const metadata: WebsiteMetadata = await invoke(
+        "fetch_website_metadata",
+        { url: urlToFetch, colorScheme: themeName },
+      );
       // 只要获取到 title，就更新它
-      if (metadata.title) {
-        onItemChange("title", metadata.title);
+      if (metadata.title.status === "found") {
+        onItemChange("title", metadata.title.value);
+      }
+      if (metadata.description.status === "found" && !item.description) {
+        onItemChange("description", metadata.description.value);
       }
-      if (metadata.local_icon_path) {
-        onItemChange("local_icon_path", metadata.local_icon_path);
+      if (metadata.theme_color.status === "found" && !item.background_color) {
+        onItemChange("background_color", metadata.theme_color.value);
+      }
+      if (metadata.local_icon_path.status === "found") {
+        onItemChange("local_icon_path", metadata.local_icon_path.value);
         onItemChange("icon_source", "auto_fetched");
         onFetchSuccess(); // 获取图标成功后，调用回调函数刷新图标
         setIsIconInvalid(false); // 重新获取图标后重置无效状态, 修改提示信息
       }
+
+      // 逐项说明未能获取的字段，页面请求失败时各字段的错误相同，只显示一次
+      const failures = [
+        ["fetchMetaFieldPage", metadata.final_url],
+        ["fetchMetaFieldTitle", metadata.title],
+        ["fetchMetaFieldIcon", metadata.local_icon_path],
+      ] as const;
+      const shownErrors = new Set<string>();
+      const details = failures.flatMap(([field, outcome]) => {
+        if (outcome.status !== "failed" || shownErrors.has(outcome.error)) {
+          return [];
+        }
+        shownErrors.add(outcome.error);
+        return [`${t(`launchpad.${field}`)}: ${outcome.error}`];
+      });
+      if (metadata.local_icon_path.status === "missing") {
+        details.push(t("launchpad.fetchMetaIconMissing"));
+      }
+      details.push(...metadata.warnings);
+      if (details.length > 0) {
+        const nothingFound =
+          metadata.title.status !== "found" &&
+          metadata.local_icon_path.status !== "found";
+        openAlert({
+          title: nothingFound
+            ? t("launchpad.fetchMetaErrorTitle")
+            : t("launchpad.fetchMetaPartialTitle"),
+          message: details.join("\n"),
+          confirmText: t("button.confirm"),
+        });
+      }
     } catch (error) {
       log.error(`Failed to fetch metadata: ${error}`);
       openAlert({
@@ -112,7 +157,7 @@ const WebsiteItemForm: React.FC<WebsiteItemFormProps> = ({
         filters: [
           {
             name: "Images",
-            extensions: ["png", "jpg", "jpeg", "svg", "ico", "gif"],
+            extensions: ["png", "jpg", "jpeg", "svg", "ico", "gif", "webp", "bmp"],
           },
         ],
       });
@@ -134,9 +134,29 @@ export interface SearchEngine {
    */
   name: string;
   /**
-   * 搜索 URL 模板，使用 %s 作为查询占位符。
+   * 搜索 URL 模板，支持 `{query}` (或 `%s`)、`{lang}`、`{date}`、`{selection}` 占位符。
    */
   url_template: string;
+  /**
+   * 搜索建议地址模板 (可选)，返回 OpenSearch Suggestions JSON，占位符与 `url_template` 相同。
+   */
+  suggest_url_template?: string | null;
+  /**
+   * 请求方式 (可选)，`GET` 或 `POST`，为空时为 `GET`。
+   */
+  method?: string | null;
+  /**
+   * POST 请求的表单模板 (可选)，例如 `q={query}&lang={lang}`。
+   */
+  post_body?: string | null;
+  /**
+   * 搜索词使用的字符编码 (可选)，例如 `GBK`，为空时为 UTF-8。
+   */
+  encoding?: string | null;
+  /**
+   * 快捷关键字 (可选)，例如 `gh`；在搜索框中输入 `gh 关键词` 或 `!gh 关键词` 时使用该引擎。
+   */
+  keyword?: string | null;
   /**
    * 默认图标 Iconify。
    */
@@ -150,3 +170,142 @@ export interface SearchEngine {
    */
   is_deletable: number;
 }
+
+/**
+ * @type SearchSuggestion
+ * @description 搜索框下方的一条建议，与 Rust 中的 `Suggestion` 对应。
+ * `website` 为本地保存的网站，`search` 为搜索引擎返回的搜索词。
+ */
+export type SearchSuggestion =
+  | {
+      kind: "website";
+      uuid: string;
+      title: string;
+      url: string;
+      default_icon: string | null;
+      local_icon_path: string | null;
+    }
+  | {
+      kind: "search";
+      text: string;
+      /**
+       * 选中后用于搜索的完整输入，保留了原输入中的关键字。
+       */
+      query: string;
+    };
+
+/**
+ * @type FieldOutcome
+ * @description 抓取网站元数据时单个字段的结果，与 Rust 中的 `FieldOutcome` 对应。
+ */
+export type FieldOutcome<T> =
+  | { status: "found"; value: T }
+  | { status: "missing" }
+  | { status: "failed"; error: string };
+
+/**
+ * @interface WebsiteMetadata
+ * @description `fetch_website_metadata` 返回的网站元数据，每个字段单独记录结果。
+ */
+export interface WebsiteMetadata {
+  /**
+   * 跟随重定向之后的最终地址。
+   */
+  final_url: FieldOutcome<string>;
+  title: FieldOutcome<string>;
+  /**
+   * `<meta name="description">`，没有时使用 `og:description`。
+   */
+  description: FieldOutcome<string>;
+  /**
+   * 站点名称，来自 `application-name`、`og:site_name` 或 Web App Manifest。
+   */
+  site_name: FieldOutcome<string>;
+  /**
+   * `og:image` 的绝对地址，没有可用的图标时作为备用图片下载。
+   */
+  image_url: FieldOutcome<string>;
+  /**
+   * `#rrggbb` 形式的主题色，来自 Web App Manifest 或 `<meta name="theme-color">`。
+   */
+  theme_color: FieldOutcome<string>;
+  local_icon_path: FieldOutcome<string>;
+  /**
+   * 不影响结果但值得提示的情况，例如标题为空时改用了主机名。
+   */
+  warnings: string[];
+}
+
+/**
+ * @interface BookmarkItem
+ * @description 书签解析器返回的单个书签项，与 Rust 中的 `BookmarkItem` 对应。
+ */
+export interface BookmarkItem {
+  title: string;
+  url: string;
+  add_date: number | null;
+  last_modified: number | null;
+  local_icon_path: string | null;
+  keyword: string | null;
+  tags: string[];
+  url_lan: string | null;
+  description: string | null;
+  default_icon: string | null;
+}
+
+/**
+ * @type DuplicateStatus
+ * @description 导入的书签与已有网站比对后的状态。
+ */
+export type DuplicateStatus =
+  | "new"
+  | "exactDuplicate"
+  | "nearDuplicate"
+  | "duplicateInImport";
+
+/**
+ * @interface ImportPreview
+ * @description `preview_bookmark_import` 返回的书签导入预览。
+ */
+export interface ImportPreview {
+  groups: {
+    name: string;
+    /** 同名的已有分组，不存在时导入会新建分组。 */
+    existing_group_uuid: string | null;
+    items: {
+      item: BookmarkItem;
+      status: DuplicateStatus;
+      /** 与之重复的已有网站。 */
+      existing: {
+        uuid: string;
+        group_uuid: string;
+        title: string;
+        url: string;
+      } | null;
+    }[];
+  }[];
+  new_count: number;
+  exact_duplicate_count: number;
+  near_duplicate_count: number;
+  duplicate_in_import_count: number;
+  existing_group_count: number;
+}
+
+/**
+ * @type ImportItemAction
+ * @description 对单个书签执行的操作，`update` 会覆盖与之重复的已有网站。
+ */
+export type ImportItemAction =
+  | { action: "add" }
+  | { action: "update"; uuid: string }
+  | { action: "skip" };
+
+/**
+ * @interface PlannedImportGroup
+ * @description 传给 `apply_bookmark_import` 的导入计划中的一个分组。
+ */
+export interface PlannedImportGroup {
+  name: string;
+  group_uuid: string | null;
+  items: ({ item: BookmarkItem } & ImportItemAction)[];
+}
@@ -10,6 +10,7 @@ import {
   WebsiteGroup,
   WebsiteItem,
   SearchEngine,
+  SearchSuggestion,
 } from "@/features/Launchpad/types";
 import EditWebsiteItemModal from "@/features/Launchpad/components/EditWebsiteItemModal";
 import * as launchpadDb from "@/services/launchpadDb";
@@ -19,6 +20,7 @@ import ContextMenu from "@/features/Launchpad/components/ContextMenu";
 import { useEnvironment } from "@/contexts/EnvironmentContext";
 import { useAuth } from "@/contexts/AuthContext";
 import * as log from "@tauri-apps/plugin-log";
+import { invoke } from "@tauri-apps/api/core";
 import {
   LaunchpadIndexContainer,
   LaunchpadPageHeader,
@@ -48,8 +50,10 @@ import {
 import { arrayMove } from "@dnd-kit/sortable";
 import { IconRefreshProvider } from "@/contexts/IconRefreshContext";
 import SearchEngineManagementModal from "@/features/Launchpad/components/SearchEngineManagementModal";
+import SearchSuggestionList from "@/features/Launchpad/components/SearchSuggestionList";
 import useLocalStorage from "@/hooks/useLocalStorage";
 import { openLink } from "@/utils/browser";
+import { getOpenWithBrowser } from "@/utils/config";
 import {
   LaunchpadSettingsProvider,
   useLaunchpadSettings,
@@ -62,6 +66,7 @@ const builtInSearchEngines: SearchEngine[] = [
     uuid: "bing-builtin",
     name: "Bing",
     url_template: "https://cn.bing.com/search?q=%s",
+    suggest_url_template: "https://api.bing.com/osjson.aspx?query=%s",
     default_icon: "logos:bing",
     is_deletable: 0,
   },
@@ -70,13 +75,15 @@ const builtInSearchEngines: SearchEngine[] = [
     uuid: "google-builtin",
     name: "Google",
     url_template: "https://www.google.com/search?q=%s",
+    suggest_url_template:
+      "https://suggestqueries.google.com/complete/search?client=firefox&q=%s",
     default_icon: "devicon:google",
     is_deletable: 0,
   },
 ];
 
 const LaunchpadPageContent: React.FC = () => {
-  const { t } = useTranslation();
+  const { t, i18n } = useTranslation();
   const { environment, toggleEnvironment } = useEnvironment();
   // 从 useAuth 中获取 dataVersion，认领完成后刷新界面
   const { activeUser, dataVersion } = useAuth();
@@ -86,6 +93,9 @@ const LaunchpadPageContent: React.FC = () => {
   const [isLoading, setIsLoading] = useState(true);
   const [isNavConfigModalOpen, setIsNavConfigModalOpen] = useState(false);
   const [searchTerm, setSearchTerm] = useState("");
+  const [suggestions, setSuggestions] = useState<SearchSuggestion[]>([]);
+  const [highlightedSuggestion, setHighlightedSuggestion] = useState(-1);
+  const [isSuggestionOpen, setIsSuggestionOpen] = useState(false);
   const [isItemModalOpen, setIsItemModalOpen] = useState(false);
   const [editingItem, setEditingItem] = useState<WebsiteItem | null>(null);
   const [targetGroupUuid, setTargetGroupUuid] = useState<string | null>(null);
@@ -182,10 +192,66 @@ const LaunchpadPageContent: React.FC = () => {
     }
   };
 
-  const handleSearch = async () => {
-    if (searchTerm.trim()) {
+  // 输入变化时获取搜索建议；防抖由后端负责，有更新的输入时旧请求返回 null
+  useEffect(() => {
+    if (!activeUser?.uuid || !searchTerm.trim()) {
+      setSuggestions([]);
+      return;
+    }
+    let isCurrent = true;
+    invoke<SearchSuggestion[] | null>("fetch_search_suggestions", {
+      userUuid: activeUser.uuid,
+      query: searchTerm,
+      lang: i18n.language,
+      fallbackSuggestUrlTemplate:
+        activeSearchEngine.suggest_url_template || null,
+    })
+      .then((result) => {
+        if (isCurrent && result) {
+          setSuggestions(result);
+          setHighlightedSuggestion(-1);
+        }
+      })
+      .catch((error) => {
+        log.warn(`Failed to fetch search suggestions: ${error}`);
+      });
+    return () => {
+      isCurrent = false;
+    };
+  }, [searchTerm, activeUser, activeSearchEngine, i18n.language]);
+
+  const handleSearch = async (term: string = searchTerm) => {
+    if (term.trim()) {
+      // 先按关键字 (如 `gh rust` 或 `!gh rust`) 解析，没有命中时使用默认搜索引擎
+      if (activeUser?.uuid) {
+        try {
+          const resolved = await invoke<{
+            url: string;
+            method: "GET" | "POST";
+          } | null>("resolve_search_query", {
+            userUuid: activeUser.uuid,
+            query: term,
+            lang: i18n.language,
+            selection: window.getSelection()?.toString() || null,
+          });
+          if (resolved?.method === "POST") {
+            // 浏览器无法直接打开 POST 请求，由后端生成自动提交的表单页面
+            await invoke("open_search_form", {
+              request: resolved,
+              browser: await getOpenWithBrowser(),
+            });
+            return;
+          }
+          if (resolved) {
+            await openLink(resolved.url);
+            return;
+          }
+        } catch (error: any) {
+          log.error(`Failed to resolve search query: ${error}`);
+        }
+      }
       const template = activeSearchEngine.url_template;
-      const encodedSearchTerm = encodeURIComponent(searchTerm);
+      const encodedSearchTerm = encodeURIComponent(term);
       const searchUrl = template.includes("%s")
         ? template.replace("%s", encodedSearchTerm)
         : template + encodedSearchTerm;
@@ -193,8 +259,43 @@ const LaunchpadPageContent: React.FC = () => {
     }
   };
 
+  const handleSuggestionSelect = async (suggestion: SearchSuggestion) => {
+    setIsSuggestionOpen(false);
+    if (suggestion.kind === "website") {
+      await openLink(suggestion.url);
+      return;
+    }
+    setSearchTerm(suggestion.query);
+    await handleSearch(suggestion.query);
+  };
+
   const handleSearchKeyDown = (e: React.KeyboardEvent) => {
-    if (e.key === "Enter") handleSearch();
+    if (isSuggestionOpen && suggestions.length > 0) {
+      if (e.key === "ArrowDown") {
+        e.preventDefault();
+        setHighlightedSuggestion((index) => (index + 1) % suggestions.length);
+        return;
+      }
+      if (e.key === "ArrowUp") {
+        e.preventDefault();
+        setHighlightedSuggestion((index) =>
+          index <= 0 ? suggestions.length - 1 : index - 1,
+        );
+        return;
+      }
+      if (e.key === "Escape") {
+        setIsSuggestionOpen(false);
+        return;
+      }
+      if (e.key === "Enter" && highlightedSuggestion >= 0) {
+        handleSuggestionSelect(suggestions[highlightedSuggestion]);
+        return;
+      }
+    }
+    if (e.key === "Enter") {
+      setIsSuggestionOpen(false);
+      handleSearch();
+    }
   };
 
   const handleCardClick = async (item: WebsiteItem) => {
@@ -390,13 +491,27 @@ const LaunchpadPageContent: React.FC = () => {
                 id="Launchpad-search-input"
                 placeholder={t("launchpad.searchText")}
                 value={searchTerm}
-                onChange={(e) => setSearchTerm(e.target.value)}
+                onChange={(e) => {
+                  setSearchTerm(e.target.value);
+                  setIsSuggestionOpen(true);
+                }}
+                onFocus={() => setIsSuggestionOpen(true)}
+                onBlur={() => setIsSuggestionOpen(false)}
                 onKeyDown={handleSearchKeyDown}
+                autoComplete="off"
               />
               <SearchIconsContainer>
                 {searchTerm && <ClearIcon onClick={() => setSearchTerm("")} />}
-                <SearchButtonIcon onClick={handleSearch} />
+                <SearchButtonIcon onClick={() => handleSearch()} />
               </SearchIconsContainer>
+              {isSuggestionOpen && (
+                <SearchSuggestionList
+                  suggestions={suggestions}
+                  highlightedIndex={highlightedSuggestion}
+                  onHighlight={setHighlightedSuggestion}
+                  onSelect={handleSuggestionSelect}
+                />
+              )}
             </SearchContainer>
           </HeaderSection>
         </LaunchpadPageHeader>
@@ -274,11 +274,16 @@ export async function saveSearchEngine(
   if (engine.uuid) {
     // 更新操作
     await dbClient.execute(
-      `UPDATE ${SEARCH_ENGINES_TABLE_NAME} SET name = $1, url_template = $2, local_icon_path = $3 WHERE uuid = $4 AND user_uuid = $5`,
+      `UPDATE ${SEARCH_ENGINES_TABLE_NAME} SET name = $1, url_template = $2, local_icon_path = $3, keyword = $4, method = $5, post_body = $6, encoding = $7, suggest_url_template = $8 WHERE uuid = $9 AND user_uuid = $10`,
       [
         engine.name,
         engine.url_template,
         engine.local_icon_path,
+        engine.keyword || null,
+        engine.method || null,
+        engine.post_body || null,
+        engine.encoding || null,
+        engine.suggest_url_template || null,
         engine.uuid,
         engine.user_uuid,
       ],
@@ -286,12 +291,17 @@ export async function saveSearchEngine(
   } else {
     // 新增操作
     await dbClient.execute(
-      `INSERT INTO ${SEARCH_ENGINES_TABLE_NAME} (uuid, user_uuid, name, url_template, default_icon, local_icon_path, sort_order) VALUES ($1, $2, $3, $4, $5, $6, $7)`,
+      `INSERT INTO ${SEARCH_ENGINES_TABLE_NAME} (uuid, user_uuid, name, url_template, suggest_url_template, keyword, method, post_body, encoding, default_icon, local_icon_path, sort_order) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)`,
       [
         crypto.randomUUID(),
         engine.user_uuid,
         engine.name,
         engine.url_template,
+        engine.suggest_url_template || null,
+        engine.keyword || null,
+        engine.method || null,
+        engine.post_body || null,
+        engine.encoding || null,
         engine.default_icon,
         engine.local_icon_path,
         0,
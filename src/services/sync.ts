@@ -746,7 +746,7 @@ export const startSync = async (user: User, updaters: SyncStatusUpdaters) => {
       },
       {
         type: DataType.Websites,
-        query: `SELECT uuid, group_uuid, title, url, url_lan, default_icon, local_icon_path, background_color, description, sort_order, is_deleted, rev, updated_at FROM ${WEBSITES_TABLE_NAME} WHERE user_uuid = $1`,
+        query: `SELECT uuid, group_uuid, title, url, url_lan, default_icon, local_icon_path, background_color, description, keyword, tags, sort_order, is_deleted, rev, updated_at FROM ${WEBSITES_TABLE_NAME} WHERE user_uuid = $1`,
       },
       {
         type: DataType.AssetCategories,
@@ -758,7 +758,7 @@ export const startSync = async (user: User, updaters: SyncStatusUpdaters) => {
       },
       {
         type: DataType.SearchEngines,
-        query: `SELECT uuid, name, url_template, default_icon, local_icon_path, is_default, sort_order, is_deleted, rev, updated_at FROM ${SEARCH_ENGINES_TABLE_NAME} WHERE user_uuid = $1`,
+        query: `SELECT uuid, name, url_template, suggest_url_template, keyword, method, post_body, encoding, default_icon, local_icon_path, is_default, sort_order, is_deleted, rev, updated_at FROM ${SEARCH_ENGINES_TABLE_NAME} WHERE user_uuid = $1`,
       },
     ];
 
@@ -50,6 +50,8 @@ export interface WebsitesDto {
   local_icon_path: string | null;
   background_color: string | null;
   description: string | null;
+  keyword: string | null;
+  tags: string | null;
   sort_order: number | null;
   is_deleted: number;
   rev: number;
@@ -101,6 +103,11 @@ export interface SearchEngineDto {
   uuid: string;
   name: string;
   url_template: string;
+  suggest_url_template: string | null;
+  keyword: string | null;
+  method: string | null;
+  post_body: string | null;
+  encoding: string | null;
   default_icon: string | null;
   local_icon_path: string | null;
   is_default: number;